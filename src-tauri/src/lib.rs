@@ -9,13 +9,35 @@ extern crate objc;
 pub mod llm;
 pub mod services;
 
-use services::clipboard::{ClipboardContent, ClipboardError};
+use services::clipboard::{ClipboardContent, ClipboardError, ClipboardSnapshot, ReplaceError};
+use services::clipboard_watch::{
+    self, ClipboardChangeTracker, ClipboardTranslation, ClipboardWatchState,
+};
+use services::dictionary::{
+    self, Dictionary, DictionaryError, DictionaryMatch, DictionaryState, DictionaryStatus,
+};
+use services::document::{self, DocumentError, FileTranslationResult};
+use services::domain::Domain;
+use services::explain::{ExplainError, Explanation};
+use services::localization::{self, LocalizationError, LocalizationResult};
 use services::permissions::PermissionStatus;
+use services::proofread::{ProofreadError, ProofreadResult};
+use services::quality::{self, BackTranslationResult};
+use services::reading::{self, ReadingAnnotation, ReadingError};
+use services::refinement::{RefinementError, RefinementSession, RefinementState};
+use services::rich_text::{self, RichTextFormat, RichTranslationResult};
+#[cfg(target_os = "macos")]
+use services::selection_context;
 use services::settings::{AppSettings, SettingsError};
-use services::shortcut::{self, ShortcutError, ShortcutStatus};
+use services::shortcut::{
+    self, KeymapState, RegistrationFailure, ShortcutAction, ShortcutBinding, ShortcutError,
+    ShortcutStatus, ShortcutTriggered,
+};
+use services::thread::ThreadSummary;
 use services::translation::{
-    self, Language, ProviderStatus, ReplyResult, SummarizeResult, TranslationError,
-    TranslationResult,
+    self, AlternativesResult, Formality, Language, ProviderStatus, ReplyIntent, ReplyResult,
+    SummarizeResult, SummaryLength, TranslationContext, TranslationError, TranslationOptions,
+    TranslationPrompt, TranslationResult,
 };
 
 /// Greet command for testing IPC
@@ -39,9 +61,10 @@ async fn get_settings(app: tauri::AppHandle) -> Result<AppSettings, SettingsErro
         .store("settings.json")
         .map_err(|e| SettingsError::LoadFailed(e.to_string()))?;
 
+    // 旧表記（"Meta"）で保存されたショートカットは読み込み時に移行する
     let shortcut = store
         .get("shortcut")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .and_then(|v| v.as_str().map(shortcut::migrate_shortcut))
         .unwrap_or_else(|| AppSettings::default().shortcut);
 
     let ollama_model = store
@@ -63,12 +86,48 @@ async fn get_settings(app: tauri::AppHandle) -> Result<AppSettings, SettingsErro
         .get("claudeCliPath")
         .and_then(|v| v.as_str().map(|s| s.to_string()));
 
+    let keymap = store
+        .get("keymap")
+        .and_then(|v| serde_json::from_value::<Vec<ShortcutBinding>>(v).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|binding| ShortcutBinding {
+            shortcut: shortcut::migrate_shortcut(&binding.shortcut),
+            ..binding
+        })
+        .collect();
+
+    let native_language = store
+        .get("nativeLanguage")
+        .and_then(|v| serde_json::from_value::<Language>(v).ok())
+        .unwrap_or_else(|| AppSettings::default().native_language);
+
+    let formality = store
+        .get("formality")
+        .and_then(|v| serde_json::from_value::<Formality>(v).ok())
+        .unwrap_or_default();
+
+    let domain = store
+        .get("domain")
+        .and_then(|v| serde_json::from_value::<Domain>(v).ok())
+        .unwrap_or_default();
+
+    let furigana = store
+        .get("furigana")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
     Ok(AppSettings {
         shortcut,
         ollama_model,
         ollama_endpoint,
         provider,
         claude_cli_path,
+        keymap,
+        native_language,
+        formality,
+        domain,
+        furigana,
     })
 }
 
@@ -89,6 +148,14 @@ async fn save_settings(app: tauri::AppHandle, settings: AppSettings) -> Result<(
     );
     store.set("provider", serde_json::json!(settings.provider));
     store.set("claudeCliPath", serde_json::json!(settings.claude_cli_path));
+    store.set("keymap", serde_json::json!(settings.keymap));
+    store.set(
+        "nativeLanguage",
+        serde_json::json!(settings.native_language),
+    );
+    store.set("formality", serde_json::json!(settings.formality));
+    store.set("domain", serde_json::json!(settings.domain));
+    store.set("furigana", serde_json::json!(settings.furigana));
 
     store
         .save()
@@ -111,13 +178,19 @@ async fn reset_settings(app: tauri::AppHandle) -> Result<AppSettings, SettingsEr
 
 /// テキストをClaude CLIで翻訳する
 ///
-/// 言語検出はフロントエンドで行い、翻訳元・翻訳先言語を指定して呼び出す
+/// 言語検出はフロントエンドで行い、翻訳元・翻訳先言語を指定して呼び出す。
+/// 文体・ドメインを省略した場合は設定の既定値を使用する。
+/// 前後の文脈を指定した場合は参考情報として渡し、選択テキストの訳文のみを返す。
+/// 翻訳先が日本語でふりがな表示が有効な場合は訳文の読みを付ける
 #[tauri::command]
 async fn translate_with_claude_cli(
     app: tauri::AppHandle,
     text: String,
     source_lang: Language,
     target_lang: Language,
+    formality: Option<Formality>,
+    domain: Option<Domain>,
+    context: Option<TranslationContext>,
 ) -> Result<TranslationResult, TranslationError> {
     use tauri_plugin_store::StoreExt;
 
@@ -128,24 +201,59 @@ async fn translate_with_claude_cli(
             .and_then(|v| v.as_str().map(|s| s.to_string()))
     });
 
-    llm::claude_cli::translate_with_claude_cli(
+    let mut result = llm::claude_cli::translate_with_claude_cli(
         &text,
         source_lang,
         target_lang,
+        &resolve_translation_options(&app, formality, domain, context),
         claude_cli_path.as_deref(),
     )
-    .await
+    .await?;
+    attach_reading(&app, &mut result).await;
+    Ok(result)
+}
+
+/// 文体・ドメインの指定を設定の既定値で補完するヘルパー関数
+fn resolve_translation_options(
+    app: &tauri::AppHandle,
+    formality: Option<Formality>,
+    domain: Option<Domain>,
+    context: Option<TranslationContext>,
+) -> TranslationOptions {
+    use tauri_plugin_store::StoreExt;
+
+    let store = app.store("settings.json").ok();
+    let stored = |key: &str| store.as_ref().and_then(|store| store.get(key));
+
+    TranslationOptions {
+        formality: formality.unwrap_or_else(|| {
+            stored("formality")
+                .and_then(|v| serde_json::from_value::<Formality>(v).ok())
+                .unwrap_or_default()
+        }),
+        domain: domain.unwrap_or_else(|| {
+            stored("domain")
+                .and_then(|v| serde_json::from_value::<Domain>(v).ok())
+                .unwrap_or_default()
+        }),
+        context,
+    }
 }
 
 /// テキストを翻訳する
 ///
-/// 言語検出はフロントエンドで行い、翻訳元・翻訳先言語を指定して呼び出す
+/// 言語検出はフロントエンドで行い、翻訳元・翻訳先言語を指定して呼び出す。
+/// 前後の文脈を指定した場合は参考情報として渡し、選択テキストの訳文のみを返す。
+/// 翻訳先が日本語でふりがな表示が有効な場合は訳文の読みを付ける
 #[tauri::command]
 async fn translate(
     app: tauri::AppHandle,
     text: String,
     source_lang: Language,
     target_lang: Language,
+    formality: Option<Formality>,
+    domain: Option<Domain>,
+    context: Option<TranslationContext>,
 ) -> Result<TranslationResult, TranslationError> {
     use tauri_plugin_store::StoreExt;
 
@@ -164,25 +272,229 @@ async fn translate(
         .and_then(|v| v.as_str().map(|s| s.to_string()))
         .unwrap_or_else(|| AppSettings::default().ollama_endpoint);
 
-    translation::translate_with_ollama(
+    let mut result = translation::translate_with_ollama(
         &text,
         source_lang,
         target_lang,
+        &resolve_translation_options(&app, formality, domain, context),
         &ollama_endpoint,
         &ollama_model,
     )
+    .await?;
+    attach_reading(&app, &mut result).await;
+    Ok(result)
+}
+
+/// 設定されたプロバイダーでテキストを翻訳するヘルパー関数
+///
+/// ドメインを省略した場合は設定の既定値を使用する。前後の文脈は参考情報としてプロンプトに含める
+async fn translate_with_provider(
+    app: &tauri::AppHandle,
+    text: &str,
+    source_lang: Language,
+    target_lang: Language,
+    domain: Option<Domain>,
+    context: Option<TranslationContext>,
+) -> Result<TranslationResult, TranslationError> {
+    let settings = get_settings(app.clone())
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+    let translation_options = TranslationOptions {
+        domain: domain.unwrap_or(settings.domain),
+        context,
+        ..settings.translation_options()
+    };
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::translate_with_claude_cli(
+            text,
+            source_lang,
+            target_lang,
+            &translation_options,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await
+    } else {
+        translation::translate_with_ollama(
+            text,
+            source_lang,
+            target_lang,
+            &translation_options,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
+
+/// 複数の翻訳候補を生成する（Ollama）
+///
+/// 通常・フォーマル・カジュアル・簡潔のスタイルから最大`count`個（省略時3個）の候補を
+/// 並行して生成し、品質チェックの警告が少ない順に返す
+#[tauri::command]
+async fn translate_alternatives(
+    app: tauri::AppHandle,
+    text: String,
+    source_lang: Language,
+    target_lang: Language,
+    count: Option<usize>,
+    domain: Option<Domain>,
+) -> Result<AlternativesResult, TranslationError> {
+    let settings = get_settings(app)
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    translation::translate_alternatives_with_ollama(
+        &text,
+        source_lang,
+        target_lang,
+        &TranslationOptions {
+            domain: domain.unwrap_or(settings.domain),
+            ..settings.translation_options()
+        },
+        &settings.ollama_endpoint,
+        &settings.ollama_model,
+        count.unwrap_or(3),
+    )
+    .await
+}
+
+/// 翻訳し、結果を翻訳元言語に逆翻訳して意味が保たれているか確認する
+///
+/// 逆翻訳には翻訳と同じプロバイダーを使用し、原文との類似度（0.0 - 1.0）を併せて返す
+#[tauri::command]
+async fn translate_with_back_translation(
+    app: tauri::AppHandle,
+    text: String,
+    source_lang: Language,
+    target_lang: Language,
+) -> Result<BackTranslationResult, TranslationError> {
+    let translation =
+        translate_with_provider(&app, &text, source_lang, target_lang, None, None).await?;
+    let back_translation = translate_with_provider(
+        &app,
+        &translation.translated_text,
+        target_lang,
+        source_lang,
+        None,
+        None,
+    )
+    .await?
+    .translated_text;
+    let similarity = quality::similarity(&text, &back_translation, source_lang);
+
+    Ok(BackTranslationResult {
+        translation,
+        back_translation,
+        similarity,
+    })
+}
+
+/// HTML・Markdownを書式を保持したまま翻訳する
+///
+/// テキスト部分のみを設定されたプロバイダーで翻訳し、太字・リンク・リスト・表などの
+/// 構造を保ったまま再構築する。HTMLの場合はプレーンテキスト版も返す
+#[tauri::command]
+async fn translate_rich_text(
+    app: tauri::AppHandle,
+    content: String,
+    format: RichTextFormat,
+    source_lang: Language,
+    target_lang: Language,
+) -> Result<RichTranslationResult, TranslationError> {
+    rich_text::translate_document(&content, format, source_lang, target_lang, |text| {
+        let app = app.clone();
+        async move {
+            translate_with_provider(&app, &text, source_lang, target_lang, None, None)
+                .await
+                .map(|result| result.translated_text)
+        }
+    })
+    .await
+}
+
+/// ファイルを翻訳し、翻訳版を元のファイルと同じ場所に書き出す
+///
+/// Markdown（フロントマター・コードブロックを保持）、テキスト、SRT/WebVTT字幕（タイミングを保持）に対応。
+/// 翻訳版が既にある場合は`overwrite`を指定しない限り別名で書き出す。
+/// 翻訳の進捗は`file-translation-progress`イベントで配信する
+#[tauri::command]
+async fn translate_file(
+    app: tauri::AppHandle,
+    path: String,
+    source_lang: Language,
+    target_lang: Language,
+    overwrite: Option<bool>,
+) -> Result<FileTranslationResult, DocumentError> {
+    use tauri::Emitter;
+
+    let progress_app = app.clone();
+
+    document::translate_file(
+        std::path::Path::new(&path),
+        source_lang,
+        target_lang,
+        overwrite.unwrap_or(false),
+        |text| {
+            let app = app.clone();
+            async move {
+                translate_with_provider(&app, &text, source_lang, target_lang, None, None)
+                    .await
+                    .map(|result| result.translated_text)
+            }
+        },
+        |progress| {
+            let _ = progress_app.emit("file-translation-progress", progress);
+        },
+    )
+    .await
+}
+
+/// ローカライズファイル（JSON i18n・gettext .po・XLIFF）を翻訳して更新する
+///
+/// 未翻訳または原文が変更されたエントリのみを翻訳し、プレースホルダーを保持する。
+/// JSONの場合は翻訳元バンドル（`source_path`）が必要で、前回の翻訳元（`previous_source_path`）を
+/// 指定すると原文が変更されたキーも翻訳する
+#[tauri::command]
+async fn translate_localization_file(
+    app: tauri::AppHandle,
+    path: String,
+    source_lang: Language,
+    target_lang: Language,
+    source_path: Option<String>,
+    previous_source_path: Option<String>,
+) -> Result<LocalizationResult, LocalizationError> {
+    localization::translate_localization_file(
+        std::path::Path::new(&path),
+        source_path.as_deref().map(std::path::Path::new),
+        previous_source_path.as_deref().map(std::path::Path::new),
+        source_lang,
+        target_lang,
+        |text| {
+            let app = app.clone();
+            async move {
+                translate_with_provider(&app, &text, source_lang, target_lang, None, None)
+                    .await
+                    .map(|result| result.translated_text)
+            }
+        },
+    )
     .await
 }
 
 /// テキストをストリーミングモードで翻訳する
 ///
-/// 翻訳結果を逐次イベントで配信し、リアルタイムに表示可能にする
+/// 翻訳結果を逐次イベントで配信し、リアルタイムに表示可能にする。
+/// 前後の文脈を指定した場合は参考情報として渡し、選択テキストの訳文のみを返す
 #[tauri::command]
 async fn translate_stream(
     app: tauri::AppHandle,
     text: String,
     source_lang: Language,
     target_lang: Language,
+    formality: Option<Formality>,
+    domain: Option<Domain>,
+    context: Option<TranslationContext>,
 ) -> Result<(), TranslationError> {
     use tauri_plugin_store::StoreExt;
 
@@ -206,12 +518,122 @@ async fn translate_stream(
         &text,
         source_lang,
         target_lang,
+        &resolve_translation_options(&app, formality, domain, context),
         &ollama_endpoint,
         &ollama_model,
     )
     .await
 }
 
+/// 翻訳結果を改善するリファインメントセッションを開始する
+///
+/// 翻訳元テキストと現在の訳文から会話履歴を作成し、メモリ上に保存する。
+/// Claude CLIでは翻訳結果の`prompt`（翻訳時に実際に送ったシステムプロンプトとメッセージ）を
+/// そのまま引き継ぐ。Ollamaでは翻訳時に指定した文体・ドメイン・前後の文脈（省略時は設定の既定値）と
+/// 設定されたモデルから、翻訳時と同じ方法でプロンプトを構築する
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn start_refinement(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, RefinementState>,
+    text: String,
+    source_lang: Language,
+    target_lang: Language,
+    translated_text: String,
+    prompt: Option<TranslationPrompt>,
+    formality: Option<Formality>,
+    domain: Option<Domain>,
+    context: Option<TranslationContext>,
+) -> Result<RefinementSession, RefinementError> {
+    let settings = get_settings(app.clone())
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    let (system_prompt, prompt) = if settings.provider == "claude-cli" {
+        let prompt = prompt.ok_or(RefinementError::PromptRequired)?;
+        (Some(prompt.system_prompt), prompt.message)
+    } else {
+        let prompt = translation::build_translation_prompt(
+            &text,
+            source_lang,
+            target_lang,
+            &settings.ollama_model,
+            &resolve_translation_options(&app, formality, domain, context),
+        );
+        (None, prompt)
+    };
+
+    Ok(state.create(
+        &text,
+        source_lang,
+        target_lang,
+        system_prompt,
+        prompt,
+        &translated_text,
+    ))
+}
+
+/// 追加指示を送信し、改訂版の訳文を生成する
+///
+/// Ollamaでは訳文を`refinement-chunk`イベントで逐次配信する。
+/// Claude CLIはストリーミングに対応しないため、完了時の通知のみ行う。
+/// いずれもセッションの会話履歴を更新したあと`refinement-complete`イベントで通知し、
+/// 生成に失敗した場合は追加指示を会話履歴から取り除く
+#[tauri::command]
+async fn refine_translation_stream(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, RefinementState>,
+    session_id: String,
+    instruction: String,
+) -> Result<(), RefinementError> {
+    use tauri::Emitter;
+
+    let settings = get_settings(app.clone())
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    let session = state.push_instruction(&session_id, &instruction)?;
+    let complete = if settings.provider == "claude-cli" {
+        llm::claude_cli::refine_with_claude_cli(&session, settings.claude_cli_path.as_deref()).await
+    } else {
+        translation::refine_with_ollama_stream(
+            &app,
+            &session,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    };
+
+    let complete = match complete {
+        Ok(complete) => complete,
+        Err(e) => {
+            state.discard_instruction(&session_id);
+            return Err(e.into());
+        }
+    };
+
+    state.push_revision(&session_id, &complete.translated_text);
+
+    let _ = app.emit("refinement-complete", complete);
+    Ok(())
+}
+
+/// リファインメントセッションを取得する
+#[tauri::command]
+fn get_refinement_session(
+    state: tauri::State<'_, RefinementState>,
+    session_id: String,
+) -> Result<RefinementSession, RefinementError> {
+    state.get(&session_id)
+}
+
+/// リファインメントセッションを終了し、会話履歴を破棄する
+#[tauri::command]
+fn end_refinement(state: tauri::State<'_, RefinementState>, session_id: String) -> bool {
+    state.remove(&session_id)
+}
+
 /// Ollamaの接続状態を確認する
 #[tauri::command]
 async fn check_provider_status(app: tauri::AppHandle) -> ProviderStatus {
@@ -255,74 +677,290 @@ async fn preload_ollama_model(app: tauri::AppHandle) -> Result<(), String> {
 
 /// テキストを要約する
 ///
-/// 翻訳後のテキストを簡潔に要約し、翻訳先言語で結果を返す
+/// 翻訳後のテキストを簡潔に要約し、翻訳先言語で結果を返す。
+/// 設定されたプロバイダー（Ollama / Claude CLI）で実行する
+///
+/// - length: 要約の長さ・形式（省略時は3文以内）
 #[tauri::command]
 async fn summarize(
     app: tauri::AppHandle,
     text: String,
     language: Language,
+    length: Option<SummaryLength>,
 ) -> Result<SummarizeResult, TranslationError> {
-    use tauri_plugin_store::StoreExt;
-
-    // 設定を取得
-    let store = app
-        .store("settings.json")
+    let settings = get_settings(app)
+        .await
         .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+    let length = length.unwrap_or_default();
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::summarize_with_claude_cli(
+            &text,
+            language,
+            length,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await
+    } else {
+        translation::summarize_with_ollama(
+            &text,
+            language,
+            length,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
 
-    let ollama_model = store
-        .get("ollamaModel")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| AppSettings::default().ollama_model);
+/// テキストをストリーミングモードで要約する
+///
+/// 要約を`summary-chunk`イベントで逐次配信し、完了時に`summary-complete`イベントで通知する。
+/// Claude CLIはストリーミングに対応しないため、完了時に`summary-complete`イベントのみ通知する
+///
+/// - length: 要約の長さ・形式（省略時は3文以内）
+#[tauri::command]
+async fn summarize_stream(
+    app: tauri::AppHandle,
+    text: String,
+    language: Language,
+    length: Option<SummaryLength>,
+) -> Result<(), TranslationError> {
+    use tauri::Emitter;
 
-    let ollama_endpoint = store
-        .get("ollamaEndpoint")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| AppSettings::default().ollama_endpoint);
+    let settings = get_settings(app.clone())
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+    let length = length.unwrap_or_default();
+
+    if settings.provider == "claude-cli" {
+        let result = llm::claude_cli::summarize_with_claude_cli(
+            &text,
+            language,
+            length,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await?;
+        let _ = app.emit("summary-complete", result);
+        return Ok(());
+    }
 
-    translation::summarize_with_ollama(&text, language, &ollama_endpoint, &ollama_model).await
+    translation::summarize_with_ollama_stream(
+        &app,
+        &text,
+        language,
+        length,
+        &settings.ollama_endpoint,
+        &settings.ollama_model,
+    )
+    .await
 }
 
 /// 返信を生成する
 ///
-/// 元の文章に対するビジネス向けの丁寧な返信を翻訳先言語で生成し、
-/// 翻訳元言語での説明も付与する
+/// 元の文章に対するビジネス向けの丁寧な返信を返信言語で生成し、
+/// 説明言語での説明（返信の翻訳）も付与する。設定されたプロバイダー（Ollama / Claude CLI）で実行する
 ///
-/// - language: 返信を作成する言語（翻訳先言語）
-/// - source_language: 説明を作成する言語（翻訳元言語）
+/// - reply_language: 返信を作成する言語（元の文章の言語）
+/// - explanation_language: 返信の説明（翻訳）を作成する言語
+/// - intent: 返信の意図（省略時は指定なし）
 #[tauri::command]
 async fn generate_reply(
     app: tauri::AppHandle,
     original_text: String,
-    language: Language,
-    source_language: Language,
+    reply_language: Language,
+    explanation_language: Language,
+    intent: Option<ReplyIntent>,
 ) -> Result<ReplyResult, TranslationError> {
-    use tauri_plugin_store::StoreExt;
-
-    // 設定を取得
-    let store = app
-        .store("settings.json")
+    let settings = get_settings(app)
+        .await
         .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+    let intent = intent.unwrap_or_default();
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::generate_reply_with_claude_cli(
+            &original_text,
+            reply_language,
+            explanation_language,
+            intent,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await
+    } else {
+        translation::generate_reply_with_ollama(
+            &original_text,
+            reply_language,
+            explanation_language,
+            intent,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
 
-    let ollama_model = store
-        .get("ollamaModel")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| AppSettings::default().ollama_model);
+/// 返信をストリーミングモードで生成する
+///
+/// 返信を`reply-chunk`イベントで逐次配信し、完了時に`reply-complete`イベントで通知する。
+/// Claude CLIはストリーミングに対応しないため、完了時に`reply-complete`イベントのみ通知する
+///
+/// - reply_language: 返信を作成する言語（元の文章の言語）
+/// - explanation_language: 返信の説明（翻訳）を作成する言語
+/// - intent: 返信の意図（省略時は指定なし）
+#[tauri::command]
+async fn generate_reply_stream(
+    app: tauri::AppHandle,
+    original_text: String,
+    reply_language: Language,
+    explanation_language: Language,
+    intent: Option<ReplyIntent>,
+) -> Result<(), TranslationError> {
+    use tauri::Emitter;
 
-    let ollama_endpoint = store
-        .get("ollamaEndpoint")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| AppSettings::default().ollama_endpoint);
+    let settings = get_settings(app.clone())
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+    let intent = intent.unwrap_or_default();
+
+    if settings.provider == "claude-cli" {
+        let result = llm::claude_cli::generate_reply_with_claude_cli(
+            &original_text,
+            reply_language,
+            explanation_language,
+            intent,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await?;
+        let _ = app.emit("reply-complete", result);
+        return Ok(());
+    }
 
-    translation::generate_reply_with_ollama(
+    translation::generate_reply_with_ollama_stream(
+        &app,
         &original_text,
-        language,
-        source_language,
-        &ollama_endpoint,
-        &ollama_model,
+        reply_language,
+        explanation_language,
+        intent,
+        &settings.ollama_endpoint,
+        &settings.ollama_model,
     )
     .await
 }
 
+/// 単語・慣用句を解説する
+///
+/// 選択テキストが単語・短いフレーズの場合に、読み（日本語の場合はふりがな・ローマ字）・品詞・意味・
+/// 例文・ニュアンスを辞書形式で返す。設定されたプロバイダー（Ollama / Claude CLI）で実行する
+///
+/// - source_lang: 語句の言語
+/// - target_lang: 解説の言語
+#[tauri::command]
+async fn explain(
+    app: tauri::AppHandle,
+    text: String,
+    source_lang: Language,
+    target_lang: Language,
+) -> Result<Explanation, ExplainError> {
+    if !services::explain::is_short_input(&text) {
+        return Err(ExplainError::InputTooLong);
+    }
+
+    let settings = get_settings(app)
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::explain_with_claude_cli(
+            &text,
+            source_lang,
+            target_lang,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await
+    } else {
+        translation::explain_with_ollama(
+            &text,
+            source_lang,
+            target_lang,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
+
+/// 文章を校正する
+///
+/// 自分で書いた文章の文法・表記・文体を校正し、校正後のテキストと
+/// 修正箇所（原文上の範囲・修正前・修正後・理由）の一覧を返す。
+/// 設定されたプロバイダー（Ollama / Claude CLI）で実行する
+///
+/// - language: 文章の言語
+#[tauri::command]
+async fn proofread(
+    app: tauri::AppHandle,
+    text: String,
+    language: Language,
+) -> Result<ProofreadResult, ProofreadError> {
+    if text.trim().is_empty() {
+        return Err(ProofreadError::EmptyInput);
+    }
+
+    let settings = get_settings(app)
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::proofread_with_claude_cli(
+            &text,
+            language,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await
+    } else {
+        translation::proofread_with_ollama(
+            &text,
+            language,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
+
+/// メール・チャットのスレッドを要約する
+///
+/// 引用付きのメール返信やチャットの`名前: 発言`の行を発言者ごとに分割し、
+/// 要約・参加者ごとのアクションアイテム・決定事項を返す。
+/// 設定されたプロバイダー（Ollama / Claude CLI）で実行する
+#[tauri::command]
+async fn summarize_thread(
+    app: tauri::AppHandle,
+    text: String,
+    language: Language,
+) -> Result<ThreadSummary, TranslationError> {
+    let settings = get_settings(app)
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::summarize_thread_with_claude_cli(
+            &text,
+            language,
+            settings.claude_cli_path.as_deref(),
+        )
+        .await
+    } else {
+        translation::summarize_thread_with_ollama(
+            &text,
+            language,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
+
 // ============================================================================
 // ショートカットコマンド
 // ============================================================================
@@ -333,6 +971,28 @@ fn validate_shortcut_format(shortcut_str: String) -> Result<(), ShortcutError> {
     shortcut::validate_shortcut(&shortcut_str)
 }
 
+/// ショートカットの競合を確認する
+///
+/// 予約済みショートカットと、設定済みキーマップ内の他アクションへの割り当てとの競合を確認する
+///
+/// - action: 割り当て先のアクション（同じアクションの既存割り当ては比較対象から除外）
+#[tauri::command]
+async fn check_shortcut_conflict(
+    app: tauri::AppHandle,
+    shortcut_str: String,
+    action: Option<ShortcutAction>,
+) -> Result<(), ShortcutError> {
+    let keymap: Vec<ShortcutBinding> = get_settings(app)
+        .await
+        .map(|settings| settings.effective_keymap())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|binding| Some(binding.action) != action)
+        .collect();
+
+    shortcut::check_shortcut_conflict(&shortcut_str, &keymap)
+}
+
 /// 現在のショートカット登録状態を取得する
 #[tauri::command]
 fn get_shortcut_status(app: tauri::AppHandle) -> ShortcutStatus {
@@ -358,24 +1018,73 @@ fn get_shortcut_status(app: tauri::AppHandle) -> ShortcutStatus {
 fn get_current_shortcut_from_settings(app: &tauri::AppHandle) -> Option<String> {
     use tauri_plugin_store::StoreExt;
 
-    app.store("settings.json").ok().and_then(|store| {
-        store
-            .get("shortcut")
-            .and_then(|v| v.as_str().map(|s| s.to_string()))
-    })
+    app.store("settings.json").ok().and_then(|store| {
+        store
+            .get("shortcut")
+            .and_then(|v| v.as_str().map(shortcut::migrate_shortcut))
+    })
+}
+
+/// ショートカットを登録し、押下時にアクション付きイベントを発行するヘルパー関数
+fn register_binding(
+    app: &tauri::AppHandle,
+    binding: &ShortcutBinding,
+) -> Result<(), ShortcutError> {
+    use tauri::Emitter;
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+    // このアプリで登録済みのショートカットは、他のアプリケーションとの競合と区別して報告する
+    if app
+        .global_shortcut()
+        .is_registered(binding.shortcut.as_str())
+    {
+        return Err(shortcut::registration_error(
+            &binding.shortcut,
+            RegistrationFailure::RegisteredByApp,
+        ));
+    }
+
+    let action = binding.action;
+    let domain = binding.domain;
+    let shortcut_str = binding.shortcut.clone();
+
+    app.global_shortcut()
+        .on_shortcut(binding.shortcut.as_str(), move |_app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                // ショートカットが押されたらアクションを添えてフロントエンドにイベントを発行
+                let _ = _app.emit(
+                    "shortcut-triggered",
+                    ShortcutTriggered {
+                        action,
+                        shortcut: shortcut_str.clone(),
+                        domain,
+                    },
+                );
+            }
+        })
+        .map_err(|e| {
+            let failure = match e {
+                tauri_plugin_global_shortcut::Error::GlobalHotkey(error) => {
+                    RegistrationFailure::RejectedByOs(error.to_string())
+                }
+                other => RegistrationFailure::Other(other.to_string()),
+            };
+            shortcut::registration_error(&binding.shortcut, failure)
+        })
 }
 
 /// グローバルショートカットを登録する
+///
+/// 単一ショートカット用。押下時は翻訳アクションとしてイベントを発行する
 #[tauri::command]
 async fn register_shortcut(
     app: tauri::AppHandle,
     shortcut_str: String,
 ) -> Result<(), ShortcutError> {
-    use tauri::Emitter;
-    use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
-    // ショートカット文字列を検証
-    shortcut::validate_shortcut(&shortcut_str)?;
+    // ショートカット文字列を検証し、予約済みショートカットとの競合を確認
+    shortcut::check_reserved_conflict(&shortcut_str)?;
 
     // 既に登録されているか確認
     if app.global_shortcut().is_registered(shortcut_str.as_str()) {
@@ -383,15 +1092,85 @@ async fn register_shortcut(
     }
 
     // ショートカットを登録
-    app.global_shortcut()
-        .on_shortcut(shortcut_str.as_str(), move |_app, _shortcut, event| {
-            if event.state == ShortcutState::Pressed {
-                // ショートカットが押されたらフロントエンドにイベントを発行
-                let _ = _app.emit("shortcut-triggered", ());
-            }
-        })
-        .map_err(|e| ShortcutError::RegistrationFailed(e.to_string()))?;
+    register_binding(
+        &app,
+        &ShortcutBinding {
+            shortcut: shortcut_str,
+            action: ShortcutAction::Translate,
+            domain: None,
+        },
+    )
+}
+
+/// 割り当てのショートカットを解除するヘルパー関数（登録されていないものはスキップ）
+fn unregister_bindings(
+    app: &tauri::AppHandle,
+    bindings: &[ShortcutBinding],
+) -> Result<(), ShortcutError> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let global_shortcut = app.global_shortcut();
+
+    for binding in bindings {
+        if !global_shortcut.is_registered(binding.shortcut.as_str()) {
+            continue;
+        }
+
+        global_shortcut
+            .unregister(binding.shortcut.as_str())
+            .map_err(|e| ShortcutError::UnregistrationFailed(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// キーマップ全体を登録する
+///
+/// 全ショートカットを検証してから、登録中のキーマップをすべて解除して新しいキーマップを登録する。
+/// キーマップから削除・変更された割り当てが残らないようにし、途中で登録に失敗した場合は
+/// 登録済みの分を解除して元のキーマップに戻す
+#[tauri::command]
+async fn register_keymap(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, KeymapState>,
+    keymap: Vec<ShortcutBinding>,
+) -> Result<(), ShortcutError> {
+    // 登録前にキーマップ全体を検証（形式・予約済みショートカット・重複）
+    shortcut::validate_keymap(&keymap)?;
+
+    let mut registered = state.lock();
+    unregister_bindings(&app, &registered)?;
+
+    if let Err(e) = keymap
+        .iter()
+        .try_for_each(|binding| register_binding(&app, binding))
+    {
+        let _ = unregister_bindings(&app, &keymap);
+        for binding in registered.iter() {
+            let _ = register_binding(&app, binding);
+        }
+        return Err(e);
+    }
+
+    *registered = keymap;
+    Ok(())
+}
+
+/// キーマップ全体を解除する
+#[tauri::command]
+async fn unregister_keymap(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, KeymapState>,
+    keymap: Vec<ShortcutBinding>,
+) -> Result<(), ShortcutError> {
+    let mut registered = state.lock();
+    unregister_bindings(&app, &keymap)?;
 
+    registered.retain(|binding| {
+        !keymap
+            .iter()
+            .any(|removed| removed.shortcut == binding.shortcut)
+    });
     Ok(())
 }
 
@@ -418,13 +1197,18 @@ async fn unregister_shortcut(
 
 /// すべてのグローバルショートカットを解除する
 #[tauri::command]
-async fn unregister_all_shortcuts(app: tauri::AppHandle) -> Result<(), ShortcutError> {
+async fn unregister_all_shortcuts(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, KeymapState>,
+) -> Result<(), ShortcutError> {
     use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
+    let mut registered = state.lock();
     app.global_shortcut()
         .unregister_all()
         .map_err(|e| ShortcutError::UnregistrationFailed(e.to_string()))?;
 
+    registered.clear();
     Ok(())
 }
 
@@ -435,46 +1219,77 @@ async fn unregister_all_shortcuts(app: tauri::AppHandle) -> Result<(), ShortcutE
 /// アクセシビリティ権限の状態を確認する
 #[tauri::command]
 async fn check_accessibility_permission_status() -> PermissionStatus {
-    // ネイティブAPIを使用して権限を確認（最も信頼できる）
-    #[cfg(target_os = "macos")]
-    let is_granted = check_accessibility_native();
+    // Linuxでは選択テキスト取得に必要なディスプレイサーバーへの接続可否で判定
+    #[cfg(target_os = "linux")]
+    {
+        check_selection_access_linux()
+    }
 
-    #[cfg(not(target_os = "macos"))]
-    let is_granted = false;
+    #[cfg(not(target_os = "linux"))]
+    {
+        // ネイティブAPIを使用して権限を確認（最も信頼できる）
+        #[cfg(target_os = "macos")]
+        let is_granted = check_accessibility_native();
 
-    PermissionStatus {
-        accessibility_granted: is_granted,
-        needs_permission_request: !is_granted,
+        #[cfg(not(target_os = "macos"))]
+        let is_granted = false;
+
+        PermissionStatus {
+            accessibility_granted: is_granted,
+            needs_permission_request: !is_granted,
+        }
     }
 }
 
+/// Linuxで選択テキスト（PRIMARYセレクション）を取得できるか確認する
+#[cfg(target_os = "linux")]
+fn check_selection_access_linux() -> PermissionStatus {
+    let x11_display = std::env::var("DISPLAY").ok();
+    let wayland_display = std::env::var("WAYLAND_DISPLAY").ok();
+
+    services::permissions::linux_permission_status(
+        x11_display.as_deref(),
+        wayland_display.as_deref(),
+        services::clipboard::is_primary_selection_available(),
+    )
+}
+
 /// アクセシビリティ権限をリクエストする
 ///
 /// システム環境設定のアクセシビリティページを開く
 #[tauri::command]
 async fn request_accessibility_permission_prompt() -> PermissionStatus {
-    // ネイティブAPIを使用して現在の権限状態を確認
-    #[cfg(target_os = "macos")]
-    let is_granted = check_accessibility_native();
+    // Linuxには権限ダイアログがないため、現在の状態をそのまま返す
+    #[cfg(target_os = "linux")]
+    {
+        check_selection_access_linux()
+    }
 
-    #[cfg(not(target_os = "macos"))]
-    let is_granted = false;
+    #[cfg(not(target_os = "linux"))]
+    {
+        // ネイティブAPIを使用して現在の権限状態を確認
+        #[cfg(target_os = "macos")]
+        let is_granted = check_accessibility_native();
 
-    if !is_granted {
-        // 権限が付与されていない場合は、システムダイアログを表示
-        tauri_plugin_macos_permissions::request_accessibility_permission().await;
-    }
+        #[cfg(not(target_os = "macos"))]
+        let is_granted = false;
 
-    // 再度ネイティブAPIで権限状態を確認（ダイアログ表示後は即座に反映されないことがある）
-    #[cfg(target_os = "macos")]
-    let is_granted_after = check_accessibility_native();
+        if !is_granted {
+            // 権限が付与されていない場合は、システムダイアログを表示
+            tauri_plugin_macos_permissions::request_accessibility_permission().await;
+        }
 
-    #[cfg(not(target_os = "macos"))]
-    let is_granted_after = false;
+        // 再度ネイティブAPIで権限状態を確認（ダイアログ表示後は即座に反映されないことがある）
+        #[cfg(target_os = "macos")]
+        let is_granted_after = check_accessibility_native();
+
+        #[cfg(not(target_os = "macos"))]
+        let is_granted_after = false;
 
-    PermissionStatus {
-        accessibility_granted: is_granted_after,
-        needs_permission_request: !is_granted_after,
+        PermissionStatus {
+            accessibility_granted: is_granted_after,
+            needs_permission_request: !is_granted_after,
+        }
     }
 }
 
@@ -528,8 +1343,14 @@ fn is_accessibility_granted() -> bool {
         result
     }
 
-    // macOS以外の場合はfalseを返す
-    #[cfg(not(target_os = "macos"))]
+    // Linuxの場合は選択テキストを取得できるかを返す
+    #[cfg(target_os = "linux")]
+    {
+        check_selection_access_linux().accessibility_granted
+    }
+
+    // その他のプラットフォームではfalseを返す
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         false
     }
@@ -592,15 +1413,19 @@ fn get_accessibility_debug_info() -> String {
 // ウィンドウ管理コマンド
 // ============================================================================
 
-/// マウスカーソルの現在位置を取得する (macOS)
+/// マウスカーソルの現在位置を取得する
+///
+/// macOSの座標系（左下原点の論理座標）で返す。Linuxでは変換して同じ座標系にそろえる
 #[tauri::command]
 #[allow(deprecated)]
 #[allow(unexpected_cfgs)]
-async fn get_cursor_position() -> Result<(f64, f64), String> {
+async fn get_cursor_position(app: tauri::AppHandle) -> Result<(f64, f64), String> {
     #[cfg(target_os = "macos")]
     {
         use cocoa::foundation::NSPoint;
 
+        let _ = app;
+
         unsafe {
             // mouseLocationはNSEventのクラスメソッド
             let ns_event_class = class!(NSEvent);
@@ -610,9 +1435,36 @@ async fn get_cursor_position() -> Result<(f64, f64), String> {
         }
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        // 物理ピクセル・左上原点の座標を取得（Waylandではコンポジタにより取得できない場合がある）
+        let cursor = app
+            .cursor_position()
+            .map_err(|e| format!("カーソル位置の取得に失敗しました: {}", e))?;
+
+        let monitors: Vec<services::window::MonitorArea> = app
+            .available_monitors()
+            .map_err(|e| format!("モニター情報の取得に失敗しました: {}", e))?
+            .iter()
+            .map(|m| services::window::MonitorArea {
+                x: m.position().x as f64,
+                y: m.position().y as f64,
+                width: m.size().width as f64,
+                height: m.size().height as f64,
+                scale_factor: m.scale_factor(),
+            })
+            .collect();
+
+        Ok(services::window::to_bottom_left_logical(
+            (cursor.x, cursor.y),
+            &monitors,
+        ))
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
-        Err("This function is only available on macOS".to_string())
+        let _ = app;
+        Err("This function is only available on macOS and Linux".to_string())
     }
 }
 
@@ -621,6 +1473,8 @@ async fn get_cursor_position() -> Result<(f64, f64), String> {
 // ============================================================================
 
 /// クリップボードからテキストを読み取る
+///
+/// ブラウザなどからリッチテキストとしてコピーされた場合はHTMLも含めて返す
 #[tauri::command]
 async fn read_clipboard(app: tauri::AppHandle) -> Result<ClipboardContent, ClipboardError> {
     use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -630,7 +1484,8 @@ async fn read_clipboard(app: tauri::AppHandle) -> Result<ClipboardContent, Clipb
             if text.is_empty() {
                 Ok(ClipboardContent::empty())
             } else {
-                Ok(ClipboardContent::from_text(text))
+                let html = ClipboardSnapshot::capture().html();
+                Ok(ClipboardContent::from_text(text).with_html(html))
             }
         }
         Err(e) => Err(ClipboardError::ReadFailed(e.to_string())),
@@ -647,33 +1502,85 @@ async fn write_clipboard(app: tauri::AppHandle, text: String) -> Result<(), Clip
         .map_err(|e| ClipboardError::WriteFailed(e.to_string()))
 }
 
-/// 選択テキストを取得する（Cmd+Cを送信してクリップボードから読み取る）
+/// クリップボードにHTMLとプレーンテキストを書き込む
 ///
-/// 注: この機能にはアクセシビリティ権限が必要
+/// HTMLに対応したアプリケーションには書式付きで、それ以外にはプレーンテキストで貼り付けられる
 #[tauri::command]
-async fn get_selected_text(app: tauri::AppHandle) -> Result<ClipboardContent, ClipboardError> {
+async fn write_rich_clipboard(
+    app: tauri::AppHandle,
+    html: String,
+    text: String,
+) -> Result<(), ClipboardError> {
     use tauri_plugin_clipboard_manager::ClipboardExt;
 
-    // 元のクリップボード内容を保存
-    let original_content = app.clipboard().read_text().ok();
+    app.clipboard()
+        .write_html(html, Some(text))
+        .map_err(|e| ClipboardError::WriteFailed(e.to_string()))
+}
 
-    // Cmd+Cキーストロークを送信
-    // 注: Tauri v2ではキーストローク送信のためにシステムAPIを直接使用する必要がある
-    // ここではAppleScriptを使用
-    let script_result = std::process::Command::new("osascript")
+/// Cmd+キーのキーストロークを最前面のアプリケーションに送信する
+///
+/// 注: Tauri v2ではキーストローク送信のためにシステムAPIを直接使用する必要がある
+/// ここではAppleScriptを使用
+#[cfg(target_os = "macos")]
+fn send_command_keystroke(key: &str) -> std::io::Result<std::process::Output> {
+    std::process::Command::new("osascript")
         .arg("-e")
-        .arg(
+        .arg(format!(
             r#"tell application "System Events"
-                keystroke "c" using command down
+                keystroke "{}" using command down
             end tell"#,
-        )
-        .output();
+            key
+        ))
+        .output()
+}
+
+/// 選択テキストを取得する
+///
+/// - macOS: Cmd+Cを送信してクリップボードから読み取る
+/// - Linux: PRIMARYセレクションを直接読み取る（キーストローク送信なし）
+/// - その他: 未対応のためエラーを返す
+///
+/// 注: macOSではこの機能にアクセシビリティ権限が必要
+#[tauri::command]
+async fn get_selected_text(app: tauri::AppHandle) -> Result<ClipboardContent, ClipboardError> {
+    #[cfg(target_os = "linux")]
+    {
+        let _ = app;
+        services::clipboard::read_primary_selection()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        copy_selected_text(app).await
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = app;
+        Err(ClipboardError::UnsupportedPlatform)
+    }
+}
+
+/// Cmd+Cを送信して選択テキストをクリップボードから読み取る
+///
+/// Cmd+C送信前にクリップボード全体（HTML、RTF、画像、ファイルリスト等）を保存し、
+/// 取得後に復元する。リッチテキストとしてコピーされた場合はHTMLも返す。
+/// アクセシビリティAPIで選択範囲の前後のテキストを取得できた場合は文脈として返す
+#[cfg(target_os = "macos")]
+async fn copy_selected_text(app: tauri::AppHandle) -> Result<ClipboardContent, ClipboardError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    // 元のクリップボード内容を全形式で保存
+    let original_snapshot = ClipboardSnapshot::capture();
+    let original_content = app.clipboard().read_text().ok();
+
+    // Cmd+Cキーストロークを送信
+    let script_result = send_command_keystroke("c");
 
     if script_result.is_err() {
         // 元のクリップボード内容を復元
-        if let Some(original) = original_content {
-            let _ = app.clipboard().write_text(original);
-        }
+        let _ = original_snapshot.restore();
         return Err(ClipboardError::ReadFailed(
             "キーストローク送信に失敗しました".to_string(),
         ));
@@ -706,27 +1613,368 @@ async fn get_selected_text(app: tauri::AppHandle) -> Result<ClipboardContent, Cl
     // リトライ上限に達した場合、元のクリップボード内容と同じままの場合
     if selected_text.is_empty() || Some(&selected_text) == original_content.as_ref() {
         // 元のクリップボード内容を復元
-        if let Some(original) = original_content {
-            let _ = app.clipboard().write_text(original);
-        }
+        let _ = original_snapshot.restore();
         return Err(ClipboardError::ReadFailed(
             "選択テキストの取得に失敗しました。アクセシビリティ権限が許可されているか確認してください。"
                 .to_string(),
         ));
     }
 
-    // 元のクリップボード内容を復元
-    if let Some(original) = original_content {
-        // 少し待ってから復元
-        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-        let _ = app.clipboard().write_text(original);
-    }
+    // コピーされたHTMLを取得（リッチテキストの場合のみ存在）
+    let selected_html = ClipboardSnapshot::capture().html();
+
+    // 選択範囲の前後の文脈を取得（取得できない場合はNone）
+    let context = selection_context::capture_context(&selected_text);
+
+    // 元のクリップボード内容を復元（少し待ってから）
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    let _ = original_snapshot.restore();
 
     if selected_text.is_empty() {
         Ok(ClipboardContent::empty())
     } else {
-        Ok(ClipboardContent::from_text(selected_text))
+        Ok(ClipboardContent::from_text(selected_text)
+            .with_html(selected_html)
+            .with_context(context))
+    }
+}
+
+/// 貼り付けのキーストローク（Cmd+V）を最前面のアプリケーションに送信する
+#[cfg(target_os = "macos")]
+fn send_paste_keystroke() -> Result<(), ClipboardError> {
+    match send_command_keystroke("v") {
+        Ok(output) if output.status.success() => Ok(()),
+        _ => Err(ClipboardError::WriteFailed(
+            "貼り付けに失敗しました。アクセシビリティ権限が許可されているか確認してください。"
+                .to_string(),
+        )),
+    }
+}
+
+/// 貼り付けのキーストローク（Ctrl+V）を最前面のアプリケーションに送信する
+///
+/// Wayland環境では`wtype`、X11環境では`xdotool`を使用する
+#[cfg(target_os = "linux")]
+fn send_paste_keystroke() -> Result<(), ClipboardError> {
+    let (program, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wtype", &["-M", "ctrl", "v", "-m", "ctrl"])
+    } else {
+        ("xdotool", &["key", "--clearmodifiers", "ctrl+v"])
+    };
+
+    match std::process::Command::new(program).args(args).output() {
+        Ok(output) if output.status.success() => Ok(()),
+        _ => Err(ClipboardError::WriteFailed(format!(
+            "貼り付けに失敗しました。{}がインストールされているか確認してください。",
+            program
+        ))),
+    }
+}
+
+/// 選択テキストを置き換える（クリップボード経由で貼り付けのキーストロークを送信する）
+///
+/// 指定テキストをクリップボードに書き込んで最前面のアプリケーションに貼り付け、
+/// 貼り付け後に元のクリップボード内容を全形式で復元する
+///
+/// - macOS: Cmd+Vを送信する（アクセシビリティ権限が必要）
+/// - Linux: `xdotool`（Waylandでは`wtype`）でCtrl+Vを送信する
+/// - その他: 未対応のためエラーを返す
+#[tauri::command]
+async fn replace_selected_text(app: tauri::AppHandle, text: String) -> Result<(), ClipboardError> {
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        paste_text(app, text).await
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = (app, text);
+        Err(ClipboardError::UnsupportedPlatform)
+    }
+}
+
+/// クリップボード経由でテキストを貼り付け、元のクリップボード内容を復元する
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+async fn paste_text(app: tauri::AppHandle, text: String) -> Result<(), ClipboardError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    // 元のクリップボード内容を全形式で保存
+    let original_snapshot = ClipboardSnapshot::capture();
+
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| ClipboardError::WriteFailed(e.to_string()))?;
+
+    // クリップボードへの反映を待ってから貼り付けのキーストロークを送信
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    let paste_result = send_paste_keystroke();
+
+    // 貼り付け先アプリケーションがクリップボードを読み取るまで待機
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+    // 元のクリップボード内容を復元
+    let _ = original_snapshot.restore();
+
+    paste_result
+}
+
+/// 選択テキストを翻訳して置き換える
+///
+/// 選択テキストの取得、言語判定、設定されたプロバイダーでの翻訳、
+/// 翻訳結果での置き換えを一度に行う。入力中のテキストをその場で翻訳する用途を想定。
+/// ドメインはショートカットごとの指定を受け取り、省略時は設定の既定値を使用する
+#[tauri::command]
+async fn translate_and_replace(
+    app: tauri::AppHandle,
+    domain: Option<Domain>,
+) -> Result<TranslationResult, ReplaceError> {
+    let selected = get_selected_text(app.clone()).await?;
+
+    // 空の選択は翻訳せず、貼り付けも行わない
+    if selected.text.trim().is_empty() {
+        return Err(ClipboardError::NoSelection.into());
+    }
+
+    let source_lang = Language::detect(&selected.text);
+    let target_lang = source_lang.opposite();
+
+    let result = translate_with_provider(
+        &app,
+        &selected.text,
+        source_lang,
+        target_lang,
+        domain,
+        selected.context,
+    )
+    .await?;
+
+    replace_selected_text(app, result.translated_text.clone()).await?;
+
+    Ok(result)
+}
+
+// ============================================================================
+// クリップボード監視コマンド
+// ============================================================================
+
+/// クリップボード監視のポーリングループ
+///
+/// クリップボードの変更をデバウンスして検出し、母国語以外のテキストであれば
+/// 母国語へ翻訳して`clipboard-translation`イベントを発行する。
+/// 監視が停止または再開始されると終了する
+async fn run_clipboard_watch(app: tauri::AppHandle, generation: u64) {
+    use tauri::{Emitter, Manager};
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    // 監視開始時点の内容は翻訳しない
+    let initial = app.clipboard().read_text().ok();
+    let mut tracker = ClipboardChangeTracker::new(initial, clipboard_watch::DEBOUNCE);
+
+    loop {
+        tokio::time::sleep(clipboard_watch::POLL_INTERVAL).await;
+
+        if !app.state::<ClipboardWatchState>().is_current(generation) {
+            break;
+        }
+
+        let Ok(text) = app.clipboard().read_text() else {
+            continue;
+        };
+        let Some(text) = tracker.observe(&text, std::time::Instant::now()) else {
+            continue;
+        };
+
+        let native_language = get_settings(app.clone())
+            .await
+            .map(|settings| settings.native_language)
+            .unwrap_or_else(|_| AppSettings::default().native_language);
+
+        // パスワード・短い文字列・URL・母国語のテキストは翻訳しない
+        let Ok(source_lang) = clipboard_watch::evaluate(&text, native_language) else {
+            continue;
+        };
+
+        match translate_with_provider(&app, text.trim(), source_lang, native_language, None, None)
+            .await
+        {
+            Ok(result) => {
+                let _ = app.emit(
+                    "clipboard-translation",
+                    ClipboardTranslation {
+                        original_text: text,
+                        result,
+                    },
+                );
+            }
+            Err(e) => {
+                let _ = app.emit("clipboard-translation-error", e.to_string());
+            }
+        }
+    }
+}
+
+/// クリップボード監視の有効・無効を切り替える
+///
+/// 有効にするとコピーされたテキストを自動翻訳し、`clipboard-translation`イベントで通知する
+#[tauri::command]
+async fn set_clipboard_watch(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ClipboardWatchState>,
+    enabled: bool,
+) -> Result<(), String> {
+    if enabled {
+        let generation = state.start();
+        tauri::async_runtime::spawn(run_clipboard_watch(app, generation));
+    } else {
+        state.stop();
+    }
+    Ok(())
+}
+
+/// クリップボード監視が有効かどうかを取得する
+#[tauri::command]
+fn is_clipboard_watch_enabled(state: tauri::State<'_, ClipboardWatchState>) -> bool {
+    state.is_enabled()
+}
+
+// ============================================================================
+// 辞書コマンド
+// ============================================================================
+
+/// 辞書の索引ファイルのパス（アプリのデータディレクトリ）
+fn dictionary_index_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, DictionaryError> {
+    use tauri::Manager;
+
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(dictionary::INDEX_FILE_NAME))
+        .map_err(|e| DictionaryError::ReadFailed(e.to_string()))
+}
+
+/// 辞書ファイル（JMdictのXML・EDICT）を取り込む
+///
+/// 索引をアプリのデータディレクトリに保存し、以降の検索に使用する
+#[tauri::command]
+async fn import_dictionary(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, DictionaryState>,
+    path: String,
+) -> Result<DictionaryStatus, DictionaryError> {
+    let index = dictionary_index_path(&app)?;
+    let dictionary = tokio::task::spawn_blocking(move || {
+        Dictionary::import(std::path::Path::new(&path), &index)
+    })
+    .await
+    .map_err(|e| DictionaryError::ReadFailed(e.to_string()))??;
+
+    let status = DictionaryStatus::of(Some(&dictionary));
+    state.set(dictionary);
+    Ok(status)
+}
+
+/// 辞書の状態を取得する（取り込み済みの索引があれば読み込む）
+#[tauri::command]
+async fn get_dictionary_status(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, DictionaryState>,
+) -> Result<DictionaryStatus, DictionaryError> {
+    let dictionary = state.get_or_load(&dictionary_index_path(&app)?).await?;
+    Ok(DictionaryStatus::of(dictionary.as_deref()))
+}
+
+/// 1語だけの選択を辞書で検索する
+///
+/// 日本語の活用形は辞書形に戻して検索する。
+/// 1語でない場合や辞書を取り込んでいない場合は空の結果を返す
+#[tauri::command]
+async fn lookup_dictionary(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, DictionaryState>,
+    text: String,
+) -> Result<Vec<DictionaryMatch>, DictionaryError> {
+    if !dictionary::is_single_token(&text) {
+        return Ok(Vec::new());
+    }
+    let dictionary = state.get_or_load(&dictionary_index_path(&app)?).await?;
+    Ok(dictionary
+        .map(|dictionary| dictionary.lookup(&text))
+        .unwrap_or_default())
+}
+
+// ============================================================================
+// 読みコマンド
+// ============================================================================
+
+/// 日本語テキストの読みを生成するヘルパー関数
+///
+/// 辞書を取り込み済みであれば辞書で単語に分割して読みを付ける。辞書にない漢字がある場合は
+/// 設定されたプロバイダー（Ollama / Claude CLI）の構造化出力で生成する
+async fn annotate_reading_with_provider(
+    app: &tauri::AppHandle,
+    text: &str,
+) -> Result<ReadingAnnotation, ReadingError> {
+    use tauri::Manager;
+
+    let dictionary = app
+        .state::<DictionaryState>()
+        .get_or_load(&dictionary_index_path(app)?)
+        .await?;
+    if let Some(annotation) =
+        dictionary.and_then(|dictionary| reading::annotate_with_dictionary(text, &dictionary))
+    {
+        return Ok(annotation);
+    }
+    if !reading::contains_kanji(text) {
+        return Ok(reading::annotate_kana(text));
+    }
+
+    let settings = get_settings(app.clone())
+        .await
+        .map_err(|e| TranslationError::ApiError(format!("設定の読み込みに失敗: {}", e)))?;
+
+    if settings.provider == "claude-cli" {
+        llm::claude_cli::annotate_reading_with_claude_cli(text, settings.claude_cli_path.as_deref())
+            .await
+    } else {
+        translation::annotate_reading_with_ollama(
+            text,
+            &settings.ollama_endpoint,
+            &settings.ollama_model,
+        )
+        .await
+    }
+}
+
+/// 翻訳先が日本語でふりがな表示が有効な場合に、訳文の読みを翻訳結果に付ける
+///
+/// 読みの生成に失敗しても翻訳結果はそのまま返す
+async fn attach_reading(app: &tauri::AppHandle, result: &mut TranslationResult) {
+    if result.target_lang != Language::Japanese {
+        return;
+    }
+    let enabled = get_settings(app.clone())
+        .await
+        .map(|settings| settings.furigana)
+        .unwrap_or(false);
+    if !enabled {
+        return;
     }
+
+    match annotate_reading_with_provider(app, &result.translated_text).await {
+        Ok(annotation) => result.reading = Some(annotation),
+        Err(e) => eprintln!("[reading] 読みの生成に失敗: {}", e),
+    }
+}
+
+/// 日本語テキストの読み（ふりがな・ローマ字）を生成する
+///
+/// 漢字にひらがなの読みを付けたルビ付きの区間と、ヘボン式ローマ字を返す
+#[tauri::command]
+async fn annotate_reading(
+    app: tauri::AppHandle,
+    text: String,
+) -> Result<ReadingAnnotation, ReadingError> {
+    annotate_reading_with_provider(&app, &text).await
 }
 
 /// Run the Tauri application
@@ -738,6 +1986,10 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_macos_permissions::init())
+        .manage(ClipboardWatchState::default())
+        .manage(KeymapState::default())
+        .manage(RefinementState::default())
+        .manage(DictionaryState::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             get_settings,
@@ -746,14 +1998,31 @@ pub fn run() {
             translate,
             translate_with_claude_cli,
             translate_stream,
+            translate_alternatives,
+            translate_with_back_translation,
+            translate_rich_text,
+            translate_file,
+            translate_localization_file,
+            start_refinement,
+            refine_translation_stream,
+            get_refinement_session,
+            end_refinement,
             check_provider_status,
             preload_ollama_model,
             summarize,
+            summarize_stream,
             generate_reply,
+            generate_reply_stream,
+            summarize_thread,
+            explain,
+            proofread,
             validate_shortcut_format,
+            check_shortcut_conflict,
             get_shortcut_status,
             register_shortcut,
             unregister_shortcut,
+            register_keymap,
+            unregister_keymap,
             unregister_all_shortcuts,
             check_accessibility_permission_status,
             request_accessibility_permission_prompt,
@@ -761,8 +2030,17 @@ pub fn run() {
             get_accessibility_debug_info,
             read_clipboard,
             write_clipboard,
+            write_rich_clipboard,
             get_selected_text,
+            replace_selected_text,
+            translate_and_replace,
             get_cursor_position,
+            set_clipboard_watch,
+            is_clipboard_watch_enabled,
+            import_dictionary,
+            get_dictionary_status,
+            lookup_dictionary,
+            annotate_reading,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -2,7 +2,17 @@
 //!
 //! Claude Code CLIを使用したテキスト翻訳機能を提供
 
-use crate::services::translation::{Language, TranslationError, TranslationResult};
+use crate::services::domain::Domain;
+use crate::services::explain::{self, ExplainError, Explanation};
+use crate::services::proofread::{self, ProofreadError, ProofreadResult};
+use crate::services::quality;
+use crate::services::reading::{self, ReadingAnnotation, ReadingError};
+use crate::services::refinement::{self, RefinementComplete, RefinementSession};
+use crate::services::thread::{self, Thread, ThreadSummary};
+use crate::services::translation::{
+    self, Formality, Language, OutputPath, ReplyIntent, ReplyResult, SummarizeResult,
+    SummaryLength, TranslationError, TranslationOptions, TranslationPrompt, TranslationResult,
+};
 use serde::Deserialize;
 use std::time::{Duration, Instant};
 use tokio::process::Command;
@@ -14,7 +24,34 @@ struct ClaudeCliOutput {
     result: String,
 }
 
-/// 構造化されたシステムプロンプトを構築
+/// システムプロンプトを構築
+///
+/// 技術文書ドメインは最適化済みの専用プロンプト、その他のドメインは
+/// ドメインプリセットの役割・方針・用語集から組み立てたプロンプトを使用する
+///
+/// # Arguments
+/// * `source_lang` - 翻訳元言語
+/// * `target_lang` - 翻訳先言語
+/// * `translation_options` - 文体・ドメイン
+fn build_system_prompt(
+    source_lang: Language,
+    target_lang: Language,
+    translation_options: &TranslationOptions,
+) -> String {
+    match translation_options.domain {
+        Domain::Technical => {
+            build_technical_prompt(source_lang, target_lang, translation_options.formality)
+        }
+        domain => build_domain_prompt(
+            source_lang,
+            target_lang,
+            domain,
+            translation_options.formality,
+        ),
+    }
+}
+
+/// 技術文書向けの構造化されたシステムプロンプトを構築
 ///
 /// 3セクション構成のプロンプトを生成:
 /// 1. 役割定義セクション
@@ -24,15 +61,31 @@ struct ClaudeCliOutput {
 /// # Arguments
 /// * `source_lang` - 翻訳元言語
 /// * `target_lang` - 翻訳先言語
+/// * `formality` - 文体（Autoの場合、日本語は「です・ます調」を優先）
 ///
 /// # Returns
 /// 構造化されたシステムプロンプト文字列
-fn build_system_prompt(source_lang: Language, target_lang: Language) -> String {
+fn build_technical_prompt(
+    source_lang: Language,
+    target_lang: Language,
+    formality: Formality,
+) -> String {
+    // 日本語以外への翻訳では、文体指示をプロンプト末尾のガイドラインに追加する
+    let tone = formality
+        .instruction(target_lang)
+        .map(|instruction| format!("- {}\n", instruction))
+        .unwrap_or_default();
+
     // 言語方向に応じて異なるプロンプトを生成
     match (source_lang, target_lang) {
         (Language::English, Language::Japanese) => {
+            let style = formality
+                .instruction(Language::Japanese)
+                .unwrap_or("日本語への翻訳では、「です・ます調」の丁寧語を優先してください");
+
             // 英→日のプロンプト
-            r#"あなたはプロフェッショナルな技術翻訳者です。
+            format!(
+                r#"あなたはプロフェッショナルな技術翻訳者です。
 
 翻訳ルール:
 - Englishから日本語への翻訳を行います
@@ -82,10 +135,12 @@ fn build_system_prompt(source_lang: Language, target_lang: Language) -> String {
 
 自然な表現:
 - 翻訳結果は対象言語のネイティブスピーカーが読んで自然に感じる表現を使用してください
-- 日本語への翻訳では、「です・ます調」の丁寧語を優先してください
+- {}
 - 冗長な表現や不自然な直訳を避け、簡潔で明瞭な表現を使用してください
 - ただし、技術的正確性を損なわない範囲で自然な表現を心がけてください
-"#.to_string()
+"#,
+                style
+            )
         }
         (Language::Japanese, Language::English) => {
             // 日→英のプロンプト
@@ -142,7 +197,9 @@ Natural Expression:
 - For English translations, appropriately use active and passive voice based on context
 - Avoid verbose expressions and unnatural literal translations; use concise and clear expressions
 - However, maintain natural expression within the scope that doesn't compromise technical accuracy
-"#.to_string()
+"#
+            .to_string()
+                + &tone
         }
         _ => {
             // その他の言語方向（将来の拡張用）
@@ -156,47 +213,92 @@ Translation Rules:
 Quality Guidelines:
 - Translation results should be natural and readable
 - Preserve context
-"#,
+{}"#,
                 source_lang.name(),
-                target_lang.name()
+                target_lang.name(),
+                tone
             )
         }
     }
 }
 
-/// Claude CLIで翻訳を実行
-///
-/// # Arguments
-/// * `text` - 翻訳するテキスト
-/// * `source_lang` - 翻訳元言語
-/// * `target_lang` - 翻訳先言語
-/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+/// ドメインプリセットからシステムプロンプトを構築
 ///
-/// # Returns
-/// 翻訳結果、またはエラー
-pub async fn translate_with_claude_cli(
-    text: &str,
+/// 役割・翻訳ルール・用語集・出力の4セクションを翻訳先言語で記述する
+fn build_domain_prompt(
     source_lang: Language,
     target_lang: Language,
-    cli_path: Option<&str>,
-) -> Result<TranslationResult, TranslationError> {
-    let start = Instant::now();
+    domain: Domain,
+    formality: Formality,
+) -> String {
+    let (rules_heading, direction, glossary_heading, output_heading, output_only) =
+        match target_lang {
+            Language::Japanese => (
+                "翻訳ルール",
+                format!("{}から日本語への翻訳を行います", source_lang.name()),
+                "用語集（以下の訳語を優先してください）",
+                "出力",
+                "翻訳結果のみを出力してください",
+            ),
+            Language::English => (
+                "Translation Rules",
+                format!("Translate from {} to English", source_lang.name()),
+                "Glossary (prefer these translations)",
+                "Output",
+                "Output only the translation",
+            ),
+        };
+
+    let mut prompt = match target_lang {
+        Language::Japanese => format!("あなたは{}です。\n\n", domain.role(target_lang)),
+        Language::English => format!("You are {}.\n\n", domain.role(target_lang)),
+    };
+
+    prompt.push_str(&format!("{}:\n- {}\n", rules_heading, direction));
+    for guideline in domain.guidelines(target_lang) {
+        prompt.push_str(&format!("- {}\n", guideline));
+    }
+
+    let glossary = domain.glossary(source_lang, target_lang);
+    if !glossary.is_empty() {
+        prompt.push_str(&format!("\n{}:\n", glossary_heading));
+        for (term, translation) in glossary {
+            prompt.push_str(&format!("- {}: {}\n", term, translation));
+        }
+    }
 
+    prompt.push_str(&format!("\n{}:\n", output_heading));
+    if let Some(instruction) = formality.instruction(target_lang) {
+        prompt.push_str(&format!("- {}\n", instruction));
+    }
+    prompt.push_str(&format!("- {}\n", output_only));
+
+    prompt
+}
+
+/// Claude CLIをプロンプトモードで実行し、応答本文を返す
+///
+/// # Arguments
+/// * `system_prompt` - システムプロンプト
+/// * `message` - ユーザーメッセージ
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+async fn run_claude_cli(
+    system_prompt: &str,
+    message: &str,
+    cli_path: Option<&str>,
+) -> Result<String, TranslationError> {
     // Claude CLIパスの決定
     let cli_command = cli_path.unwrap_or("claude");
 
-    // システムプロンプトの構築（最適化された3セクション構成）
-    let system_prompt = build_system_prompt(source_lang, target_lang);
-
     // コマンドの構築と実行
     // -pフラグでプロンプトモードを使用し、純粋な翻訳モードにする
     let child = Command::new(cli_command)
         .arg("-p")
         .arg("--system-prompt")
-        .arg(&system_prompt)
+        .arg(system_prompt)
         .arg("--output-format")
         .arg("json")
-        .arg(text)
+        .arg(message)
         .kill_on_drop(true)
         .output();
 
@@ -224,20 +326,362 @@ pub async fn translate_with_claude_cli(
         TranslationError::ApiError(format!("JSON出力のパースに失敗しました: {}", e))
     })?;
 
+    Ok(cli_output.result)
+}
+
+/// Claude CLIで翻訳を実行
+///
+/// # Arguments
+/// * `text` - 翻訳するテキスト
+/// * `source_lang` - 翻訳元言語
+/// * `target_lang` - 翻訳先言語
+/// * `translation_options` - 文体・ドメイン・前後の文脈
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+///
+/// # Returns
+/// 翻訳結果、またはエラー
+pub async fn translate_with_claude_cli(
+    text: &str,
+    source_lang: Language,
+    target_lang: Language,
+    translation_options: &TranslationOptions,
+    cli_path: Option<&str>,
+) -> Result<TranslationResult, TranslationError> {
+    let start = Instant::now();
+
+    // システムプロンプトの構築（最適化された3セクション構成）
+    let system_prompt = build_system_prompt(source_lang, target_lang, translation_options);
+
+    // 前後の文脈がある場合は参考情報として添え、翻訳対象をタグで囲む
+    let message = match translation_options.context() {
+        Some(context) => translation::build_context_message(text, context, target_lang),
+        None => text.to_string(),
+    };
+
+    let result = run_claude_cli(&system_prompt, &message, cli_path).await?;
+
+    // 文脈付きの場合は翻訳対象部分の訳文のみを返す
+    let translated_text = match translation_options.context() {
+        Some(_) => translation::extract_selection_translation(&result),
+        None => result,
+    };
+
     let duration_ms = start.elapsed().as_millis() as u64;
+    let warnings = quality::check_translation(text, &translated_text, source_lang, target_lang);
 
     Ok(TranslationResult {
-        translated_text: cli_output.result,
+        translated_text,
         source_lang,
         target_lang,
         duration_ms,
+        warnings,
+        output_path: OutputPath::Heuristic,
+        reading: None,
+        // リファインメントで同じプロンプトを引き継ぐため、実際に送った内容を返す
+        prompt: Some(TranslationPrompt {
+            system_prompt,
+            message,
+        }),
+    })
+}
+
+/// 要約用のシステムプロンプトを構築（要約言語で記述）
+fn build_summarize_system_prompt(language: Language, length: SummaryLength) -> String {
+    match language {
+        Language::Japanese => format!(
+            r#"あなたは日本語の文章を要約する専門家です。
+
+要約ルール:
+- 与えられたテキストを{}日本語で要約してください
+- 重要な事実・数値・固有名詞を保持してください
+- 原文にない情報を追加しないでください
+
+出力:
+- 要約のみを出力してください
+"#,
+            length.instruction(language)
+        ),
+        Language::English => format!(
+            r#"You are an expert at summarizing English text.
+
+Summarization Rules:
+- Summarize the given text {} in English
+- Keep important facts, numbers and proper nouns
+- Do not add information that is not in the original
+
+Output:
+- Output only the summary
+"#,
+            length.instruction(language)
+        ),
+    }
+}
+
+/// 返信用のシステムプロンプトを構築（返信言語で記述）
+fn build_reply_system_prompt(
+    reply_language: Language,
+    intent: ReplyIntent,
+    explanation_language: Option<Language>,
+) -> String {
+    let output = match (reply_language, explanation_language) {
+        (Language::Japanese, None) => "- 返信のみを出力してください".to_string(),
+        (Language::Japanese, Some(explanation_language)) => format!(
+            "- JSONのみを出力し、\"reply\"に日本語の返信、\"translation\"にその返信の{}訳を入れてください",
+            match explanation_language {
+                Language::Japanese => "日本語",
+                Language::English => "英語",
+            }
+        ),
+        (Language::English, None) => "- Output only the reply".to_string(),
+        (Language::English, Some(explanation_language)) => format!(
+            "- Output only JSON with the English reply in \"reply\" and its {} translation in \"translation\"",
+            explanation_language.name()
+        ),
+    };
+
+    match reply_language {
+        Language::Japanese => format!(
+            r#"あなたはビジネスメールの返信作成の専門家です。
+
+返信ルール:
+- 与えられたメッセージに対して、{}丁寧なビジネスメールの返信を日本語で書いてください
+- メッセージの要点や質問に漏れなく応えてください
+- 日時や金額など不明な事実は作らず、［　］のプレースホルダーにしてください
+
+出力:
+{}
+"#,
+            intent.instruction(reply_language),
+            output
+        ),
+        Language::English => format!(
+            r#"You are an expert at writing business email replies.
+
+Reply Rules:
+- Write a polite business email reply to the given message in English{}
+- Address every point and question in the message
+- Do not invent facts such as dates or amounts; use [placeholders] instead
+
+Output:
+{}
+"#,
+            intent.instruction(reply_language),
+            output
+        ),
+    }
+}
+
+/// Claude CLIで要約を実行
+///
+/// # Arguments
+/// * `text` - 要約するテキスト
+/// * `language` - 要約の言語
+/// * `length` - 要約の長さ・形式
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn summarize_with_claude_cli(
+    text: &str,
+    language: Language,
+    length: SummaryLength,
+    cli_path: Option<&str>,
+) -> Result<SummarizeResult, TranslationError> {
+    let start = Instant::now();
+
+    let system_prompt = build_summarize_system_prompt(language, length);
+    let summary = run_claude_cli(&system_prompt, text, cli_path)
+        .await?
+        .trim()
+        .to_string();
+
+    Ok(SummarizeResult {
+        original_length: text.chars().count(),
+        summary_length: summary.chars().count(),
+        summary,
+        duration_ms: start.elapsed().as_millis() as u64,
     })
 }
 
+/// Claude CLIで返信を生成
+///
+/// 返信言語と説明言語が異なる場合は、返信とその翻訳（説明）をJSONで1回の呼び出しで生成する。
+/// JSONとして解釈できない場合や翻訳が空の場合のみ、返信を別途翻訳して説明とする
+///
+/// # Arguments
+/// * `text` - 返信する元のメッセージ
+/// * `reply_language` - 返信を作成する言語
+/// * `explanation_language` - 説明を作成する言語
+/// * `intent` - 返信の意図
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn generate_reply_with_claude_cli(
+    text: &str,
+    reply_language: Language,
+    explanation_language: Language,
+    intent: ReplyIntent,
+    cli_path: Option<&str>,
+) -> Result<ReplyResult, TranslationError> {
+    let start = Instant::now();
+
+    let bilingual = reply_language != explanation_language;
+    let system_prompt = build_reply_system_prompt(
+        reply_language,
+        intent,
+        bilingual.then_some(explanation_language),
+    );
+    let content = run_claude_cli(&system_prompt, text, cli_path).await?;
+
+    if !bilingual {
+        let reply = content.trim().to_string();
+        return Ok(ReplyResult {
+            explanation: reply.clone(),
+            reply,
+            language: reply_language,
+            duration_ms: start.elapsed().as_millis() as u64,
+        });
+    }
+
+    let (reply, translation) = translation::parse_structured_reply(&content)
+        .unwrap_or_else(|| (content.trim().to_string(), None));
+    let explanation = match translation {
+        Some(translation) => translation,
+        None => {
+            translate_with_claude_cli(
+                &reply,
+                reply_language,
+                explanation_language,
+                &TranslationOptions::default(),
+                cli_path,
+            )
+            .await?
+            .translated_text
+        }
+    };
+
+    Ok(ReplyResult {
+        reply,
+        explanation,
+        language: reply_language,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Claude CLIで単語・慣用句を解説
+///
+/// # Arguments
+/// * `term` - 解説する語句
+/// * `source_lang` - 語句の言語
+/// * `target_lang` - 解説の言語
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn explain_with_claude_cli(
+    term: &str,
+    source_lang: Language,
+    target_lang: Language,
+    cli_path: Option<&str>,
+) -> Result<Explanation, ExplainError> {
+    let start = Instant::now();
+
+    let prompt = explain::build_explain_prompt(term, source_lang, target_lang);
+    let content = run_claude_cli(explain::system_prompt(target_lang), &prompt, cli_path).await?;
+
+    explain::build_explanation(
+        &content,
+        term,
+        source_lang,
+        target_lang,
+        start.elapsed().as_millis() as u64,
+    )
+}
+
+/// Claude CLIで文章を校正
+///
+/// # Arguments
+/// * `text` - 校正する文章
+/// * `language` - 文章の言語
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn proofread_with_claude_cli(
+    text: &str,
+    language: Language,
+    cli_path: Option<&str>,
+) -> Result<ProofreadResult, ProofreadError> {
+    let start = Instant::now();
+
+    let prompt = proofread::build_proofread_prompt(text, language);
+    let content = run_claude_cli(proofread::system_prompt(language), &prompt, cli_path).await?;
+
+    proofread::build_proofread_result(&content, text, language, start.elapsed().as_millis() as u64)
+}
+
+/// Claude CLIで日本語テキストの読み（ふりがな・ローマ字）を生成
+///
+/// # Arguments
+/// * `text` - 読みを付ける日本語テキスト
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn annotate_reading_with_claude_cli(
+    text: &str,
+    cli_path: Option<&str>,
+) -> Result<ReadingAnnotation, ReadingError> {
+    let prompt = reading::build_reading_prompt(text);
+    let content = run_claude_cli(reading::system_prompt(), &prompt, cli_path).await?;
+
+    reading::build_llm_annotation(&content, text)
+}
+
+/// Claude CLIでリファインメントの会話履歴から改訂版の訳文を生成
+///
+/// Claude CLIはストリーミングに対応しないため、完了結果のみを返す
+///
+/// # Arguments
+/// * `session` - 追加指示を含む会話履歴
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn refine_with_claude_cli(
+    session: &RefinementSession,
+    cli_path: Option<&str>,
+) -> Result<RefinementComplete, TranslationError> {
+    let start = Instant::now();
+
+    let prompt = refinement::build_refinement_prompt(session);
+    let content = run_claude_cli(&refinement::system_prompt(session), &prompt, cli_path).await?;
+
+    Ok(translation::build_refinement_complete(
+        session,
+        &content,
+        start.elapsed().as_millis() as u64,
+    ))
+}
+
+/// Claude CLIでメール・チャットのスレッドを要約
+///
+/// # Arguments
+/// * `text` - 要約するスレッド
+/// * `language` - 要約の言語
+/// * `cli_path` - Claude CLIの実行パス（Noneの場合はデフォルトの"claude"を使用）
+pub async fn summarize_thread_with_claude_cli(
+    text: &str,
+    language: Language,
+    cli_path: Option<&str>,
+) -> Result<ThreadSummary, TranslationError> {
+    let start = Instant::now();
+
+    let thread = Thread::parse(text);
+    let prompt = thread::build_thread_prompt(&thread, language);
+    let content = run_claude_cli(thread::system_prompt(language), &prompt, cli_path).await?;
+
+    Ok(thread::build_summary(
+        &content,
+        thread,
+        start.elapsed().as_millis() as u64,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn formality(formality: Formality) -> TranslationOptions {
+        TranslationOptions {
+            formality,
+            ..Default::default()
+        }
+    }
+
     // 注: 実際のClaude CLI実行が必要なため、統合テストとしてマーク
     // 単体テストではモックを使用することを推奨
 
@@ -250,6 +694,7 @@ mod tests {
             "Hello, how are you?",
             Language::English,
             Language::Japanese,
+            &TranslationOptions::default(),
             None,
         )
         .await;
@@ -269,6 +714,7 @@ mod tests {
             "Hello",
             Language::English,
             Language::Japanese,
+            &TranslationOptions::default(),
             Some("/nonexistent/path/to/claude"),
         )
         .await;
@@ -314,7 +760,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_english_to_japanese() {
         // 英→日のシステムプロンプトが3セクション構成になっていることを確認
-        let prompt = build_system_prompt(Language::English, Language::Japanese);
+        let prompt = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
 
         // 役割定義セクションが含まれることを確認
         assert!(
@@ -342,7 +792,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_japanese_to_english() {
         // 日→英のシステムプロンプトが3セクション構成になっていることを確認
-        let prompt = build_system_prompt(Language::Japanese, Language::English);
+        let prompt = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
 
         // 役割定義セクションが含まれることを確認
         assert!(
@@ -370,8 +824,16 @@ mod tests {
     #[test]
     fn test_build_system_prompt_different_for_directions() {
         // 英→日と日→英で異なるプロンプトが生成されることを確認
-        let prompt_en_to_ja = build_system_prompt(Language::English, Language::Japanese);
-        let prompt_ja_to_en = build_system_prompt(Language::Japanese, Language::English);
+        let prompt_en_to_ja = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
+        let prompt_ja_to_en = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
 
         assert_ne!(
             prompt_en_to_ja, prompt_ja_to_en,
@@ -382,7 +844,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_includes_technical_rules() {
         // 技術文書特化型ルールがプロンプトに含まれることを確認（タスク1.2）
-        let prompt = build_system_prompt(Language::English, Language::Japanese);
+        let prompt = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
 
         // プログラミング用語の保持ルール
         assert!(
@@ -420,7 +886,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_technical_rules_english() {
         // 日→英でも技術文書特化型ルールが含まれることを確認
-        let prompt = build_system_prompt(Language::Japanese, Language::English);
+        let prompt = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
 
         // Programming terms preservation
         assert!(
@@ -447,7 +917,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_includes_terminology_dictionary() {
         // 専門用語辞書がプロンプトに含まれることを確認（タスク1.3）
-        let prompt = build_system_prompt(Language::English, Language::Japanese);
+        let prompt = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
 
         // 10種類の基本用語が含まれることを確認
         let required_terms = vec![
@@ -493,7 +967,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_terminology_dictionary_english() {
         // 日→英でも専門用語辞書が含まれることを確認
-        let prompt = build_system_prompt(Language::Japanese, Language::English);
+        let prompt = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
 
         // 10種類の基本用語が含まれることを確認
         let required_terms = vec![
@@ -533,7 +1011,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_includes_context_and_natural_expression_guidelines() {
         // コンテキスト保持と自然な表現ガイドラインが含まれることを確認（タスク1.4）
-        let prompt = build_system_prompt(Language::English, Language::Japanese);
+        let prompt = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
 
         // コンテキスト保持ルール
         assert!(
@@ -581,7 +1063,11 @@ mod tests {
     #[test]
     fn test_build_system_prompt_natural_expression_guidelines_english() {
         // 日→英でもコンテキスト保持と自然な表現ガイドラインが含まれることを確認
-        let prompt = build_system_prompt(Language::Japanese, Language::English);
+        let prompt = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
 
         // Context preservation
         assert!(
@@ -609,11 +1095,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_system_prompt_with_formality() {
+        // 日本語: 既定の「です・ます調」の指示を置き換える
+        let prompt = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &formality(Formality::Plain),
+        );
+        assert!(prompt.contains("「である調」（常体）で翻訳してください。"));
+        assert!(!prompt.contains("「です・ます調」の丁寧語を優先してください"));
+
+        let prompt = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &formality(Formality::Casual),
+        );
+        assert!(prompt.contains("くだけた口語体"));
+
+        // 英語: ガイドラインの末尾に追加する
+        let prompt = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &formality(Formality::Polite),
+        );
+        assert!(prompt.ends_with("- Use a formal, polite tone.\n"));
+
+        let prompt = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
+        assert!(!prompt.contains("tone."));
+    }
+
+    #[test]
+    fn test_build_system_prompt_with_domain() {
+        let legal = TranslationOptions {
+            domain: Domain::Legal,
+            ..Default::default()
+        };
+        let prompt = build_system_prompt(Language::English, Language::Japanese, &legal);
+        assert!(prompt.starts_with("あなたは法務・契約文書を専門とするプロの翻訳者です。"));
+        assert!(prompt.contains("Englishから日本語への翻訳を行います"));
+        assert!(prompt.contains("- Agreement: 本契約"));
+        assert!(prompt.ends_with("- 翻訳結果のみを出力してください\n"));
+        // 技術文書向けの内容は含まない
+        assert!(!prompt.contains("プログラミング"));
+
+        let chat = TranslationOptions {
+            formality: Formality::Casual,
+            domain: Domain::Chat,
+            ..Default::default()
+        };
+        let prompt = build_system_prompt(Language::Japanese, Language::English, &chat);
+        assert!(prompt.starts_with("You are a translator who renders chat messages naturally."));
+        assert!(prompt.contains("- お疲れ様です: Hi"));
+        assert!(prompt.contains("Output:\n- Use a casual, conversational tone.\n"));
+    }
+
     #[test]
     fn test_system_prompt_token_limit() {
         // プロンプト長が2000トークン以内であることを確認（タスク1.5）
-        let prompt_en_to_ja = build_system_prompt(Language::English, Language::Japanese);
-        let prompt_ja_to_en = build_system_prompt(Language::Japanese, Language::English);
+        let prompt_en_to_ja = build_system_prompt(
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
+        let prompt_ja_to_en = build_system_prompt(
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
 
         // トークン数近似計算（英語: 4文字/トークン、日本語: 2文字/トークン）
         let estimate_en_to_ja = prompt_en_to_ja.chars().filter(|c| c.is_ascii()).count() / 4
@@ -638,4 +1191,41 @@ mod tests {
         println!("英→日プロンプト推定トークン数: {}", estimate_en_to_ja);
         println!("日→英プロンプト推定トークン数: {}", estimate_ja_to_en);
     }
+
+    #[test]
+    fn test_build_summarize_system_prompt() {
+        let prompt = build_summarize_system_prompt(Language::Japanese, SummaryLength::default());
+        assert!(prompt.contains("- 与えられたテキストを3文以内で日本語で要約してください\n"));
+        assert!(prompt.ends_with("- 要約のみを出力してください\n"));
+
+        let prompt = build_summarize_system_prompt(Language::English, SummaryLength::Bullets);
+        assert!(prompt.contains("Summarize the given text as 3-5 bullet points"));
+        assert!(prompt.ends_with("- Output only the summary\n"));
+    }
+
+    #[test]
+    fn test_build_reply_system_prompt() {
+        let prompt = build_reply_system_prompt(Language::English, ReplyIntent::Accept, None);
+        assert!(prompt.contains(
+            "- Write a polite business email reply to the given message in English, accepting the request or proposal\n"
+        ));
+        assert!(prompt.ends_with("- Output only the reply\n"));
+
+        let prompt = build_reply_system_prompt(Language::Japanese, ReplyIntent::General, None);
+        assert!(prompt.contains("メッセージに対して、丁寧なビジネスメールの返信を日本語で"));
+
+        // 対訳付きの場合は返信と翻訳をJSONで出力させる
+        let prompt = build_reply_system_prompt(
+            Language::English,
+            ReplyIntent::General,
+            Some(Language::Japanese),
+        );
+        assert!(prompt.contains("\"reply\"") && prompt.contains("Japanese translation"));
+        let prompt = build_reply_system_prompt(
+            Language::Japanese,
+            ReplyIntent::General,
+            Some(Language::English),
+        );
+        assert!(prompt.contains("その返信の英語訳"));
+    }
 }
@@ -1,7 +1,12 @@
 //! クリップボード管理サービス
 //!
 //! クリップボードの読み取り・書き込みと、元のクリップボード内容の保存・復元を提供
+//!
+//! 保存・復元はテキストだけでなくHTML、RTF、画像、ファイルリストなど全形式を対象とする。
+//! macOSではNSPasteboardの全アイテム・全タイプをそのまま保存し、
+//! その他のプラットフォームではarboardで取得可能な形式を保存する
 
+use crate::services::translation::{TranslationContext, TranslationError};
 use serde::Serialize;
 use thiserror::Error;
 
@@ -18,6 +23,11 @@ pub enum ClipboardError {
     #[allow(dead_code)]
     #[error("クリップボードにテキストがありません")]
     NoText,
+    #[error("テキストが選択されていません")]
+    NoSelection,
+    #[allow(dead_code)]
+    #[error("この機能は現在のプラットフォームでは利用できません")]
+    UnsupportedPlatform,
 }
 
 impl Serialize for ClipboardError {
@@ -29,14 +39,39 @@ impl Serialize for ClipboardError {
     }
 }
 
+/// 翻訳して置換のエラー
+///
+/// 選択テキストの取得・置き換えと翻訳のどちらで失敗したかを保持する
+#[derive(Debug, Error)]
+pub enum ReplaceError {
+    #[error(transparent)]
+    Clipboard(#[from] ClipboardError),
+    #[error(transparent)]
+    Translation(#[from] TranslationError),
+}
+
+impl Serialize for ReplaceError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 /// クリップボード読み取り結果
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClipboardContent {
     /// テキスト内容
     pub text: String,
+    /// HTML内容（リッチテキストとしてコピーされた場合）
+    pub html: Option<String>,
     /// 読み取り成功かどうか
     pub success: bool,
+    /// 選択範囲の前後の文脈（選択テキスト取得時に取得できた場合）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<TranslationContext>,
 }
 
 impl ClipboardContent {
@@ -44,7 +79,9 @@ impl ClipboardContent {
     pub fn empty() -> Self {
         Self {
             text: String::new(),
+            html: None,
             success: false,
+            context: None,
         }
     }
 
@@ -52,7 +89,293 @@ impl ClipboardContent {
     pub fn from_text(text: String) -> Self {
         Self {
             text,
+            html: None,
             success: true,
+            context: None,
+        }
+    }
+
+    /// HTML内容を設定
+    pub fn with_html(mut self, html: Option<String>) -> Self {
+        self.html = html;
+        self
+    }
+
+    /// 前後の文脈を設定
+    pub fn with_context(mut self, context: Option<TranslationContext>) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+/// HTMLのペーストボードタイプ（macOS）
+#[cfg(target_os = "macos")]
+const PASTEBOARD_TYPE_HTML: &str = "public.html";
+
+/// クリップボード全体のスナップショット
+///
+/// 選択テキスト取得のためのCmd+C送信前に保存し、取得後に復元する
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardSnapshot {
+    /// ペーストボードアイテムごとの（タイプ, データ）の一覧
+    #[cfg(target_os = "macos")]
+    items: Vec<Vec<(String, Vec<u8>)>>,
+    /// テキスト
+    #[cfg(not(target_os = "macos"))]
+    text: Option<String>,
+    /// HTML
+    #[cfg(not(target_os = "macos"))]
+    html: Option<String>,
+    /// 画像
+    #[cfg(not(target_os = "macos"))]
+    image: Option<arboard::ImageData<'static>>,
+    /// ファイルリスト
+    #[cfg(not(target_os = "macos"))]
+    files: Option<Vec<std::path::PathBuf>>,
+}
+
+#[cfg(target_os = "macos")]
+impl ClipboardSnapshot {
+    /// 現在のクリップボード内容をすべての形式で保存する
+    pub fn capture() -> Self {
+        Self {
+            items: macos::read_items(),
+        }
+    }
+
+    /// 保存したクリップボード内容を復元する
+    pub fn restore(&self) -> Result<(), ClipboardError> {
+        macos::write_items(&self.items)
+    }
+
+    /// HTML内容を取得
+    pub fn html(&self) -> Option<String> {
+        self.items
+            .iter()
+            .flatten()
+            .find(|(ty, _)| ty == PASTEBOARD_TYPE_HTML)
+            .map(|(_, data)| String::from_utf8_lossy(data).into_owned())
+    }
+
+    /// スナップショットが空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.items.iter().all(|item| item.is_empty())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+impl ClipboardSnapshot {
+    /// 現在のクリップボード内容を取得可能なすべての形式で保存する
+    pub fn capture() -> Self {
+        let Ok(mut clipboard) = arboard::Clipboard::new() else {
+            return Self::default();
+        };
+
+        Self {
+            text: clipboard.get().text().ok(),
+            html: clipboard.get().html().ok(),
+            image: clipboard.get().image().ok(),
+            files: clipboard
+                .get()
+                .file_list()
+                .ok()
+                .filter(|files| !files.is_empty()),
+        }
+    }
+
+    /// 保存したクリップボード内容を復元する
+    ///
+    /// arboardは複数形式の同時書き込みに対応していないため、
+    /// ファイルリスト、画像、HTML（テキスト付き）、テキストの優先順で復元する
+    pub fn restore(&self) -> Result<(), ClipboardError> {
+        let mut clipboard =
+            arboard::Clipboard::new().map_err(|e| ClipboardError::WriteFailed(e.to_string()))?;
+
+        let result = if let Some(files) = &self.files {
+            clipboard.set().file_list(files)
+        } else if let Some(image) = &self.image {
+            clipboard.set_image(image.clone())
+        } else if let Some(html) = &self.html {
+            clipboard.set_html(html.as_str(), self.text.as_deref())
+        } else if let Some(text) = &self.text {
+            clipboard.set_text(text.as_str())
+        } else {
+            clipboard.clear()
+        };
+
+        result.map_err(|e| ClipboardError::WriteFailed(e.to_string()))
+    }
+
+    /// HTML内容を取得
+    pub fn html(&self) -> Option<String> {
+        self.html.clone()
+    }
+
+    /// スナップショットが空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none() && self.html.is_none() && self.image.is_none() && self.files.is_none()
+    }
+}
+
+/// PRIMARYセレクション（マウスで選択中のテキスト）を読み取る（Linux）
+///
+/// X11/WaylandではテキストをマウスでPRIMARYセレクションに保持するため、
+/// キーストロークを送信せずに選択テキストを直接取得できる
+#[cfg(target_os = "linux")]
+pub fn read_primary_selection() -> Result<ClipboardContent, ClipboardError> {
+    use arboard::{GetExtLinux, LinuxClipboardKind};
+
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| ClipboardError::ReadFailed(e.to_string()))?;
+
+    let text = match clipboard
+        .get()
+        .clipboard(LinuxClipboardKind::Primary)
+        .text()
+    {
+        Ok(text) => text,
+        Err(arboard::Error::ContentNotAvailable) => String::new(),
+        Err(e) => return Err(ClipboardError::ReadFailed(e.to_string())),
+    };
+
+    if text.is_empty() {
+        return Err(ClipboardError::ReadFailed(
+            "選択テキストがありません。テキストを選択してから実行してください。".to_string(),
+        ));
+    }
+
+    let html = clipboard
+        .get()
+        .clipboard(LinuxClipboardKind::Primary)
+        .html()
+        .ok();
+
+    Ok(ClipboardContent::from_text(text).with_html(html))
+}
+
+/// PRIMARYセレクションを読み取れる環境か確認する（Linux）
+#[cfg(target_os = "linux")]
+pub fn is_primary_selection_available() -> bool {
+    arboard::Clipboard::new().is_ok()
+}
+
+/// macOSのNSPasteboardを直接操作する
+#[cfg(target_os = "macos")]
+#[allow(unexpected_cfgs)]
+mod macos {
+    use super::ClipboardError;
+    use objc::runtime::{Object, BOOL, NO};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::{CStr, CString};
+
+    /// NSStringをRustの文字列に変換
+    unsafe fn to_string(ns_string: *mut Object) -> Option<String> {
+        if ns_string.is_null() {
+            return None;
+        }
+        let utf8: *const std::os::raw::c_char = msg_send![ns_string, UTF8String];
+        if utf8.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+    }
+
+    /// Rustの文字列からNSStringを作成（autorelease）
+    unsafe fn to_ns_string(value: &str) -> *mut Object {
+        let c_string = CString::new(value).unwrap_or_default();
+        msg_send![class!(NSString), stringWithUTF8String: c_string.as_ptr()]
+    }
+
+    /// ペーストボードの全アイテムの全タイプのデータを読み取る
+    pub fn read_items() -> Vec<Vec<(String, Vec<u8>)>> {
+        let mut result = Vec::new();
+
+        unsafe {
+            let pool: *mut Object = msg_send![class!(NSAutoreleasePool), new];
+
+            let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+            let items: *mut Object = msg_send![pasteboard, pasteboardItems];
+
+            if !items.is_null() {
+                let item_count: usize = msg_send![items, count];
+                for i in 0..item_count {
+                    let item: *mut Object = msg_send![items, objectAtIndex: i];
+                    let types: *mut Object = msg_send![item, types];
+                    let type_count: usize = msg_send![types, count];
+
+                    let mut entries = Vec::new();
+                    for j in 0..type_count {
+                        let ty: *mut Object = msg_send![types, objectAtIndex: j];
+                        let Some(type_name) = to_string(ty) else {
+                            continue;
+                        };
+
+                        let data: *mut Object = msg_send![item, dataForType: ty];
+                        if data.is_null() {
+                            continue;
+                        }
+
+                        let length: usize = msg_send![data, length];
+                        let bytes: *const u8 = msg_send![data, bytes];
+                        let bytes = if length == 0 || bytes.is_null() {
+                            Vec::new()
+                        } else {
+                            std::slice::from_raw_parts(bytes, length).to_vec()
+                        };
+
+                        entries.push((type_name, bytes));
+                    }
+
+                    result.push(entries);
+                }
+            }
+
+            let _: () = msg_send![pool, drain];
+        }
+
+        result
+    }
+
+    /// ペーストボードの内容を指定したアイテムで置き換える
+    pub fn write_items(items: &[Vec<(String, Vec<u8>)>]) -> Result<(), ClipboardError> {
+        unsafe {
+            let pool: *mut Object = msg_send![class!(NSAutoreleasePool), new];
+
+            let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+            let _: isize = msg_send![pasteboard, clearContents];
+
+            let mut written = true;
+            if !items.is_empty() {
+                let array: *mut Object = msg_send![class!(NSMutableArray), array];
+
+                for entries in items {
+                    let item: *mut Object = msg_send![class!(NSPasteboardItem), new];
+                    for (type_name, bytes) in entries {
+                        let data: *mut Object = msg_send![
+                            class!(NSData),
+                            dataWithBytes: bytes.as_ptr()
+                            length: bytes.len()
+                        ];
+                        let _: BOOL =
+                            msg_send![item, setData: data forType: to_ns_string(type_name)];
+                    }
+                    let _: () = msg_send![array, addObject: item];
+                    let _: () = msg_send![item, release];
+                }
+
+                let result: BOOL = msg_send![pasteboard, writeObjects: array];
+                written = result != NO;
+            }
+
+            let _: () = msg_send![pool, drain];
+
+            if written {
+                Ok(())
+            } else {
+                Err(ClipboardError::WriteFailed(
+                    "クリップボード内容の復元に失敗しました".to_string(),
+                ))
+            }
         }
     }
 }
@@ -89,6 +412,16 @@ mod tests {
         assert_eq!(json, "\"クリップボードが空です\"");
     }
 
+    #[test]
+    fn test_replace_error_serialization() {
+        let err: ReplaceError = ClipboardError::NoSelection.into();
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(json, "\"テキストが選択されていません\"");
+
+        let err: ReplaceError = TranslationError::Timeout.into();
+        assert_eq!(err.to_string(), "翻訳リクエストがタイムアウトしました");
+    }
+
     #[test]
     fn test_clipboard_content_empty() {
         let content = ClipboardContent::empty();
@@ -103,6 +436,25 @@ mod tests {
         assert!(content.success);
     }
 
+    #[test]
+    fn test_clipboard_content_with_html() {
+        let content = ClipboardContent::from_text("Hello".to_string())
+            .with_html(Some("<b>Hello</b>".to_string()));
+        assert_eq!(content.text, "Hello");
+        assert_eq!(content.html.as_deref(), Some("<b>Hello</b>"));
+        assert!(content.success);
+
+        let json = serde_json::to_string(&content).unwrap();
+        assert!(json.contains("\"html\":\"<b>Hello</b>\""));
+    }
+
+    #[test]
+    fn test_clipboard_snapshot_default_is_empty() {
+        let snapshot = ClipboardSnapshot::default();
+        assert!(snapshot.is_empty());
+        assert!(snapshot.html().is_none());
+    }
+
     #[test]
     fn test_clipboard_content_serialization() {
         let content = ClipboardContent::from_text("テスト".to_string());
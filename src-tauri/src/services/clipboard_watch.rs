@@ -0,0 +1,403 @@
+//! クリップボード監視サービス
+//!
+//! クリップボードの変更を検出し、母国語以外のテキストがコピーされた場合に
+//! 自動翻訳するかどうかを判定する。ポーリングループの停止管理、
+//! 変更のデバウンス、パスワード・短い文字列・URLの除外ルールを提供
+
+use crate::services::translation::{Language, TranslationResult};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// ポーリング間隔
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// デバウンス時間（クリップボードの内容がこの時間変化しなければ翻訳する）
+pub const DEBOUNCE: Duration = Duration::from_millis(800);
+
+/// 自動翻訳の対象とする最小文字数
+const MIN_TEXT_LENGTH: usize = 4;
+
+/// 自動翻訳の対象とする最大文字数
+const MAX_TEXT_LENGTH: usize = 5000;
+
+/// 自動翻訳をスキップする理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// 短すぎる
+    TooShort,
+    /// 長すぎる
+    TooLong,
+    /// URLまたはメールアドレス
+    Url,
+    /// パスワードやトークンと思われる文字列
+    Secret,
+    /// 母国語のテキスト
+    NativeLanguage,
+}
+
+/// クリップボード自動翻訳イベント
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardTranslation {
+    /// コピーされたテキスト
+    pub original_text: String,
+    /// 翻訳結果
+    pub result: TranslationResult,
+}
+
+/// クリップボード監視状態
+///
+/// 監視を開始するたびに世代番号を更新し、ポーリングループは自身の世代が
+/// 最新でなくなった時点で終了する
+#[derive(Debug, Default)]
+pub struct ClipboardWatchState {
+    enabled: AtomicBool,
+    generation: AtomicU64,
+}
+
+impl ClipboardWatchState {
+    /// 監視を開始し、新しいポーリングループの世代番号を返す
+    ///
+    /// 既存のポーリングループは次回のポーリング時に終了する
+    pub fn start(&self) -> u64 {
+        self.enabled.store(true, Ordering::SeqCst);
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// 監視を停止する
+    pub fn stop(&self) {
+        self.enabled.store(false, Ordering::SeqCst);
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// 監視中かどうか
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// 指定した世代のポーリングループを継続すべきか
+    pub fn is_current(&self, generation: u64) -> bool {
+        self.is_enabled() && self.generation.load(Ordering::SeqCst) == generation
+    }
+}
+
+/// クリップボードの変更を追跡し、デバウンス後に処理対象のテキストを返す
+#[derive(Debug)]
+pub struct ClipboardChangeTracker {
+    /// 最後に観測したテキスト
+    last_text: Option<String>,
+    /// 最後に内容が変化した時刻
+    changed_at: Option<Instant>,
+    /// 最後に処理したテキスト
+    last_processed: Option<String>,
+    /// デバウンス時間
+    debounce: Duration,
+}
+
+impl ClipboardChangeTracker {
+    /// 監視開始時のクリップボード内容を既処理として追跡を開始
+    pub fn new(initial: Option<String>, debounce: Duration) -> Self {
+        Self {
+            last_text: initial.clone(),
+            changed_at: None,
+            last_processed: initial,
+            debounce,
+        }
+    }
+
+    /// クリップボードの内容を観測する
+    ///
+    /// # Returns
+    /// 内容が変化してからデバウンス時間が経過し、まだ処理していないテキストの場合はSome
+    pub fn observe(&mut self, text: &str, now: Instant) -> Option<String> {
+        if self.last_text.as_deref() != Some(text) {
+            self.last_text = Some(text.to_string());
+            self.changed_at = Some(now);
+            return None;
+        }
+
+        let changed_at = self.changed_at?;
+        if now.duration_since(changed_at) < self.debounce {
+            return None;
+        }
+
+        self.changed_at = None;
+        if self.last_processed.as_deref() == Some(text) {
+            return None;
+        }
+
+        self.last_processed = Some(text.to_string());
+        Some(text.to_string())
+    }
+}
+
+/// URLまたはメールアドレスかどうか
+fn is_url(text: &str) -> bool {
+    if text.contains(char::is_whitespace) {
+        return false;
+    }
+
+    let lower = text.to_lowercase();
+    if lower.starts_with("www.") || lower.starts_with("mailto:") {
+        return true;
+    }
+
+    // scheme://... 形式
+    if let Some(pos) = lower.find("://") {
+        let scheme = &lower[..pos];
+        if !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return true;
+        }
+    }
+
+    // メールアドレス
+    if let Some((local, domain)) = text.split_once('@') {
+        if !local.is_empty() && domain.contains('.') && !domain.starts_with('.') {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// パスワードやAPIトークンと思われる文字列かどうか
+///
+/// 空白を含まない8文字以上のASCII文字列で、英大文字・英小文字・数字・記号のうち
+/// 3種類以上を含むもの、または英字と数字が混在する20文字以上のものを対象とする
+fn is_secret(text: &str) -> bool {
+    let length = text.chars().count();
+    if length < 8 || !text.is_ascii() || text.contains(char::is_whitespace) {
+        return false;
+    }
+
+    let has_lower = text.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = text.chars().any(|c| c.is_ascii_uppercase());
+    let has_digit = text.chars().any(|c| c.is_ascii_digit());
+    let has_symbol = text.chars().any(|c| c.is_ascii_punctuation());
+
+    let classes = [has_lower, has_upper, has_digit, has_symbol]
+        .iter()
+        .filter(|&&b| b)
+        .count();
+
+    classes >= 3 || (length >= 20 && has_digit && (has_lower || has_upper))
+}
+
+/// コピーされたテキストを自動翻訳すべきか判定する
+///
+/// # Arguments
+/// * `text` - コピーされたテキスト
+/// * `native_language` - ユーザーの母国語（このテキストは翻訳しない）
+///
+/// # Returns
+/// 翻訳する場合は翻訳元言語、スキップする場合はその理由
+pub fn evaluate(text: &str, native_language: Language) -> Result<Language, SkipReason> {
+    let trimmed = text.trim();
+    let length = trimmed.chars().count();
+
+    if length < MIN_TEXT_LENGTH {
+        return Err(SkipReason::TooShort);
+    }
+    if length > MAX_TEXT_LENGTH {
+        return Err(SkipReason::TooLong);
+    }
+    if is_url(trimmed) {
+        return Err(SkipReason::Url);
+    }
+    if is_secret(trimmed) {
+        return Err(SkipReason::Secret);
+    }
+
+    let source_lang = Language::detect(trimmed);
+    if source_lang == native_language {
+        return Err(SkipReason::NativeLanguage);
+    }
+
+    Ok(source_lang)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_state_generation() {
+        let state = ClipboardWatchState::default();
+        assert!(!state.is_enabled());
+
+        let first = state.start();
+        assert!(state.is_current(first));
+
+        // 再開始すると古いループは終了対象になる
+        let second = state.start();
+        assert!(!state.is_current(first));
+        assert!(state.is_current(second));
+
+        state.stop();
+        assert!(!state.is_enabled());
+        assert!(!state.is_current(second));
+    }
+
+    #[test]
+    fn test_tracker_ignores_initial_content() {
+        let start = Instant::now();
+        let mut tracker =
+            ClipboardChangeTracker::new(Some("initial".to_string()), Duration::from_millis(100));
+
+        assert_eq!(tracker.observe("initial", start), None);
+        assert_eq!(
+            tracker.observe("initial", start + Duration::from_secs(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tracker_debounce() {
+        let start = Instant::now();
+        let mut tracker = ClipboardChangeTracker::new(None, Duration::from_millis(100));
+
+        // 変化直後は処理しない
+        assert_eq!(tracker.observe("Hello world", start), None);
+        assert_eq!(
+            tracker.observe("Hello world", start + Duration::from_millis(50)),
+            None
+        );
+
+        // デバウンス時間経過後に1回だけ処理する
+        assert_eq!(
+            tracker.observe("Hello world", start + Duration::from_millis(150)),
+            Some("Hello world".to_string())
+        );
+        assert_eq!(
+            tracker.observe("Hello world", start + Duration::from_millis(300)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tracker_restarts_debounce_on_change() {
+        let start = Instant::now();
+        let mut tracker = ClipboardChangeTracker::new(None, Duration::from_millis(100));
+
+        assert_eq!(tracker.observe("first", start), None);
+        assert_eq!(
+            tracker.observe("second", start + Duration::from_millis(90)),
+            None
+        );
+        assert_eq!(
+            tracker.observe("second", start + Duration::from_millis(150)),
+            None
+        );
+        assert_eq!(
+            tracker.observe("second", start + Duration::from_millis(200)),
+            Some("second".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tracker_skips_already_processed_text() {
+        let start = Instant::now();
+        let mut tracker = ClipboardChangeTracker::new(None, Duration::from_millis(100));
+
+        tracker.observe("text", start);
+        assert!(tracker
+            .observe("text", start + Duration::from_millis(100))
+            .is_some());
+
+        // 別の内容を経由して同じテキストに戻っても再処理しない（クリップボード復元など）
+        tracker.observe("other", start + Duration::from_millis(200));
+        tracker.observe("text", start + Duration::from_millis(250));
+        assert_eq!(
+            tracker.observe("text", start + Duration::from_millis(400)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_evaluate_translates_foreign_text() {
+        assert_eq!(
+            evaluate("This is an English sentence.", Language::Japanese),
+            Ok(Language::English)
+        );
+        assert_eq!(
+            evaluate("これは日本語の文章です。", Language::English),
+            Ok(Language::Japanese)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_skips_native_language() {
+        assert_eq!(
+            evaluate("これは日本語の文章です。", Language::Japanese),
+            Err(SkipReason::NativeLanguage)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_skips_short_text() {
+        assert_eq!(
+            evaluate("OK", Language::Japanese),
+            Err(SkipReason::TooShort)
+        );
+        assert_eq!(
+            evaluate("  a  ", Language::Japanese),
+            Err(SkipReason::TooShort)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_skips_long_text() {
+        let text = "word ".repeat(2000);
+        assert_eq!(
+            evaluate(&text, Language::Japanese),
+            Err(SkipReason::TooLong)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_skips_urls() {
+        for url in [
+            "https://example.com/path?q=1",
+            "http://localhost:11434",
+            "www.example.com",
+            "ssh://git@github.com/user/repo.git",
+            "user@example.com",
+            "mailto:user@example.com",
+        ] {
+            assert_eq!(
+                evaluate(url, Language::Japanese),
+                Err(SkipReason::Url),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_skips_secrets() {
+        for secret in [
+            "P@ssw0rd123",
+            "hunter2Secret!",
+            "ghp_a1b2c3d4e5f6g7h8i9j0k1l2",
+            "sk1234567890abcdefghij",
+        ] {
+            assert_eq!(
+                evaluate(secret, Language::Japanese),
+                Err(SkipReason::Secret),
+                "{}",
+                secret
+            );
+        }
+
+        // 通常の英単語は対象外
+        assert_eq!(
+            evaluate("Internationalization", Language::Japanese),
+            Ok(Language::English)
+        );
+    }
+}
@@ -0,0 +1,471 @@
+//! 日本語の活用形の辞書形への変換（語形復元）
+//!
+//! 「食べなかった」→「食べない」→「食べる」のように、語尾の置換規則を繰り返し適用して
+//! 辞書形の候補を列挙する。候補が実在する語かどうかは辞書の品詞で確認する
+
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// 一段動詞
+pub const V1: u8 = 1;
+/// 五段動詞
+pub const V5: u8 = 1 << 1;
+/// カ変動詞（来る）
+pub const VK: u8 = 1 << 2;
+/// サ変動詞（する・〜する）
+pub const VS: u8 = 1 << 3;
+/// 形容詞（い形容詞）
+pub const ADJ_I: u8 = 1 << 4;
+/// て形（「〜ている」「〜てしまう」の途中）
+pub const TE: u8 = 1 << 5;
+
+/// 活用の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Reason {
+    /// 否定（〜ない）
+    Negative,
+    /// 過去（〜た）
+    Past,
+    /// て形（〜て）
+    Te,
+    /// 丁寧（〜ます）
+    Polite,
+    /// 希望（〜たい）
+    Desiderative,
+    /// 可能（〜える・〜られる）
+    Potential,
+    /// 受身（〜れる・〜られる）
+    Passive,
+    /// 使役（〜せる・〜させる）
+    Causative,
+    /// 命令（〜え・〜ろ）
+    Imperative,
+    /// 意志（〜う・〜よう）
+    Volitional,
+    /// 仮定（〜ば・〜たら）
+    Conditional,
+    /// 進行（〜ている）
+    Progressive,
+    /// 完了（〜てしまう・〜ちゃう）
+    Completion,
+    /// 連用（〜く）
+    Adverbial,
+    /// 名詞化（〜さ）
+    Noun,
+    /// 様態（〜そう）
+    Appearance,
+    /// 過度（〜すぎる）
+    Excess,
+}
+
+/// 辞書形の候補
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deinflection {
+    /// 候補の語
+    pub word: String,
+    /// 候補が取り得る品詞（0は元の語で、品詞を問わない）
+    pub word_type: u8,
+    /// 適用した活用（元の語に近い順）
+    pub reasons: Vec<Reason>,
+}
+
+/// 語尾の置換規則
+struct Rule {
+    /// 活用した語尾
+    from: String,
+    /// 辞書形（または途中の形）の語尾
+    to: String,
+    /// 規則を適用できる途中の形の品詞（0は元の語にのみ適用する）
+    type_in: u8,
+    /// 置換後の品詞
+    type_out: u8,
+    reasons: &'static [Reason],
+}
+
+/// 五段動詞の語尾ごとの活用（辞書形, ア段, イ段, エ段, オ段, て形, た形）
+const GODAN_ENDINGS: &[[&str; 7]] = &[
+    ["う", "わ", "い", "え", "お", "って", "った"],
+    ["く", "か", "き", "け", "こ", "いて", "いた"],
+    ["ぐ", "が", "ぎ", "げ", "ご", "いで", "いだ"],
+    ["す", "さ", "し", "せ", "そ", "して", "した"],
+    ["つ", "た", "ち", "て", "と", "って", "った"],
+    ["ぬ", "な", "に", "ね", "の", "んで", "んだ"],
+    ["ぶ", "ば", "び", "べ", "ぼ", "んで", "んだ"],
+    ["む", "ま", "み", "め", "も", "んで", "んだ"],
+    ["る", "ら", "り", "れ", "ろ", "って", "った"],
+];
+
+/// 丁寧形の語尾と活用
+const POLITE_ENDINGS: &[(&str, &[Reason])] = &[
+    ("ます", &[Reason::Polite]),
+    ("ません", &[Reason::Polite, Reason::Negative]),
+    ("ました", &[Reason::Polite, Reason::Past]),
+    (
+        "ませんでした",
+        &[Reason::Polite, Reason::Negative, Reason::Past],
+    ),
+    ("ましょう", &[Reason::Polite, Reason::Volitional]),
+];
+
+/// 候補の最大数（規則の組み合わせによる爆発を防ぐ）
+const MAX_CANDIDATES: usize = 256;
+
+fn rule(from: &str, to: &str, type_in: u8, type_out: u8, reasons: &'static [Reason]) -> Rule {
+    Rule {
+        from: from.to_string(),
+        to: to.to_string(),
+        type_in,
+        type_out,
+        reasons,
+    }
+}
+
+/// 規則の一覧を構築
+fn build_rules() -> Vec<Rule> {
+    let mut rules = Vec::new();
+
+    // 五段動詞
+    for [base, a, i, e, o, te, ta] in GODAN_ENDINGS {
+        rules.push(rule(
+            &format!("{a}ない"),
+            base,
+            ADJ_I,
+            V5,
+            &[Reason::Negative],
+        ));
+        rules.push(rule(ta, base, 0, V5, &[Reason::Past]));
+        rules.push(rule(te, base, TE, V5, &[Reason::Te]));
+        rules.push(rule(
+            &format!("{ta}ら"),
+            base,
+            0,
+            V5,
+            &[Reason::Conditional],
+        ));
+        for (suffix, reasons) in POLITE_ENDINGS {
+            rules.push(rule(&format!("{i}{suffix}"), base, 0, V5, reasons));
+        }
+        rules.push(rule(
+            &format!("{i}たい"),
+            base,
+            ADJ_I,
+            V5,
+            &[Reason::Desiderative],
+        ));
+        rules.push(rule(&format!("{e}る"), base, V1, V5, &[Reason::Potential]));
+        rules.push(rule(&format!("{a}れる"), base, V1, V5, &[Reason::Passive]));
+        rules.push(rule(
+            &format!("{a}せる"),
+            base,
+            V1,
+            V5,
+            &[Reason::Causative],
+        ));
+        rules.push(rule(e, base, 0, V5, &[Reason::Imperative]));
+        rules.push(rule(&format!("{o}う"), base, 0, V5, &[Reason::Volitional]));
+        rules.push(rule(&format!("{e}ば"), base, 0, V5, &[Reason::Conditional]));
+    }
+    // 「行く」のて形・た形は例外
+    for stem in ["行", "い"] {
+        let base = format!("{stem}く");
+        rules.push(rule(&format!("{stem}った"), &base, 0, V5, &[Reason::Past]));
+        rules.push(rule(&format!("{stem}って"), &base, TE, V5, &[Reason::Te]));
+    }
+
+    // 一段動詞
+    rules.push(rule("ない", "る", ADJ_I, V1, &[Reason::Negative]));
+    rules.push(rule("た", "る", 0, V1, &[Reason::Past]));
+    rules.push(rule("て", "る", TE, V1, &[Reason::Te]));
+    rules.push(rule("たら", "る", 0, V1, &[Reason::Conditional]));
+    for (suffix, reasons) in POLITE_ENDINGS {
+        rules.push(rule(suffix, "る", 0, V1, reasons));
+    }
+    rules.push(rule("たい", "る", ADJ_I, V1, &[Reason::Desiderative]));
+    rules.push(rule("られる", "る", V1, V1, &[Reason::Passive]));
+    rules.push(rule("れる", "る", V1, V1, &[Reason::Potential]));
+    rules.push(rule("させる", "る", V1, V1, &[Reason::Causative]));
+    rules.push(rule("ろ", "る", 0, V1, &[Reason::Imperative]));
+    rules.push(rule("よう", "る", 0, V1, &[Reason::Volitional]));
+    rules.push(rule("れば", "る", 0, V1, &[Reason::Conditional]));
+
+    // サ変動詞（「勉強する」は名詞「勉強」として辞書に載っている）
+    rules.push(rule("しない", "する", ADJ_I, VS, &[Reason::Negative]));
+    rules.push(rule("した", "する", 0, VS, &[Reason::Past]));
+    rules.push(rule("して", "する", TE, VS, &[Reason::Te]));
+    rules.push(rule("したら", "する", 0, VS, &[Reason::Conditional]));
+    for (suffix, reasons) in POLITE_ENDINGS {
+        rules.push(rule(&format!("し{suffix}"), "する", 0, VS, reasons));
+    }
+    rules.push(rule("したい", "する", ADJ_I, VS, &[Reason::Desiderative]));
+    rules.push(rule("できる", "する", V1, VS, &[Reason::Potential]));
+    rules.push(rule("される", "する", V1, VS, &[Reason::Passive]));
+    rules.push(rule("させる", "する", V1, VS, &[Reason::Causative]));
+    rules.push(rule("しろ", "する", 0, VS, &[Reason::Imperative]));
+    rules.push(rule("しよう", "する", 0, VS, &[Reason::Volitional]));
+    rules.push(rule("すれば", "する", 0, VS, &[Reason::Conditional]));
+    rules.push(rule("する", "", VS, VS, &[]));
+
+    // カ変動詞
+    for (ko, ki, ku) in [("こ", "き", "く"), ("来", "来", "来")] {
+        let base = format!("{ku}る");
+        rules.push(rule(
+            &format!("{ko}ない"),
+            &base,
+            ADJ_I,
+            VK,
+            &[Reason::Negative],
+        ));
+        rules.push(rule(&format!("{ki}た"), &base, 0, VK, &[Reason::Past]));
+        rules.push(rule(&format!("{ki}て"), &base, TE, VK, &[Reason::Te]));
+        rules.push(rule(
+            &format!("{ki}たら"),
+            &base,
+            0,
+            VK,
+            &[Reason::Conditional],
+        ));
+        for (suffix, reasons) in POLITE_ENDINGS {
+            rules.push(rule(&format!("{ki}{suffix}"), &base, 0, VK, reasons));
+        }
+        rules.push(rule(
+            &format!("{ki}たい"),
+            &base,
+            ADJ_I,
+            VK,
+            &[Reason::Desiderative],
+        ));
+        rules.push(rule(
+            &format!("{ko}られる"),
+            &base,
+            V1,
+            VK,
+            &[Reason::Passive],
+        ));
+        rules.push(rule(
+            &format!("{ko}させる"),
+            &base,
+            V1,
+            VK,
+            &[Reason::Causative],
+        ));
+        rules.push(rule(
+            &format!("{ko}い"),
+            &base,
+            0,
+            VK,
+            &[Reason::Imperative],
+        ));
+        rules.push(rule(
+            &format!("{ko}よう"),
+            &base,
+            0,
+            VK,
+            &[Reason::Volitional],
+        ));
+        rules.push(rule(
+            &format!("{ku}れば"),
+            &base,
+            0,
+            VK,
+            &[Reason::Conditional],
+        ));
+    }
+
+    // 形容詞（「いい」は「よい」の活用を使う）
+    for (stem, base) in [("", "い"), ("よ", "いい")] {
+        let from = |suffix: &str| format!("{stem}{suffix}");
+        rules.push(rule(
+            &from("くない"),
+            base,
+            ADJ_I,
+            ADJ_I,
+            &[Reason::Negative],
+        ));
+        rules.push(rule(&from("かった"), base, 0, ADJ_I, &[Reason::Past]));
+        rules.push(rule(&from("くて"), base, TE, ADJ_I, &[Reason::Te]));
+        rules.push(rule(&from("く"), base, 0, ADJ_I, &[Reason::Adverbial]));
+        rules.push(rule(
+            &from("ければ"),
+            base,
+            0,
+            ADJ_I,
+            &[Reason::Conditional],
+        ));
+        rules.push(rule(
+            &from("かったら"),
+            base,
+            0,
+            ADJ_I,
+            &[Reason::Conditional],
+        ));
+        rules.push(rule(
+            &from("くありません"),
+            base,
+            0,
+            ADJ_I,
+            &[Reason::Polite, Reason::Negative],
+        ));
+    }
+    rules.push(rule("さ", "い", 0, ADJ_I, &[Reason::Noun]));
+    rules.push(rule("そう", "い", 0, ADJ_I, &[Reason::Appearance]));
+    rules.push(rule("すぎる", "い", V1, ADJ_I, &[Reason::Excess]));
+
+    // 補助動詞（て形を経由して元の動詞に戻す）
+    for (te, progressive, short, completion, contracted) in [
+        ("て", "ている", "てる", "てしまう", "ちゃう"),
+        ("で", "でいる", "でる", "でしまう", "じゃう"),
+    ] {
+        rules.push(rule(progressive, te, V1, TE, &[Reason::Progressive]));
+        rules.push(rule(short, te, V1, TE, &[Reason::Progressive]));
+        rules.push(rule(completion, te, V5, TE, &[Reason::Completion]));
+        rules.push(rule(contracted, te, V5, TE, &[Reason::Completion]));
+    }
+
+    rules
+}
+
+fn rules() -> &'static [Rule] {
+    static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+    RULES.get_or_init(build_rules)
+}
+
+/// 語の辞書形の候補を列挙する
+///
+/// 先頭は元の語（品詞を問わない）で、以降は適用した規則が少ない順に並ぶ
+pub fn deinflect(word: &str) -> Vec<Deinflection> {
+    let mut candidates = vec![Deinflection {
+        word: word.to_string(),
+        word_type: 0,
+        reasons: Vec::new(),
+    }];
+
+    let mut i = 0;
+    while i < candidates.len() && candidates.len() < MAX_CANDIDATES {
+        let current = candidates[i].clone();
+        i += 1;
+
+        for rule in rules() {
+            if current.word_type != 0 && current.word_type & rule.type_in == 0 {
+                continue;
+            }
+            let Some(stem) = current.word.strip_suffix(rule.from.as_str()) else {
+                continue;
+            };
+            if stem.is_empty() && rule.to.is_empty() {
+                continue;
+            }
+
+            let word = format!("{stem}{}", rule.to);
+            if candidates
+                .iter()
+                .any(|c| c.word == word && c.word_type == rule.type_out)
+            {
+                continue;
+            }
+
+            let mut reasons = current.reasons.clone();
+            reasons.extend_from_slice(rule.reasons);
+            candidates.push(Deinflection {
+                word,
+                word_type: rule.type_out,
+                reasons,
+            });
+        }
+    }
+
+    candidates
+}
+
+/// 辞書の品詞（JMdictの品詞コード）が候補の品詞に合うかどうか
+pub fn matches_part_of_speech(word_type: u8, part_of_speech: &str) -> bool {
+    if word_type == 0 {
+        return true;
+    }
+    let pos_type = if part_of_speech.starts_with("v1") {
+        V1
+    } else if part_of_speech.starts_with("v5") {
+        V5
+    } else if part_of_speech.starts_with("vk") {
+        VK
+    } else if part_of_speech.starts_with("vs") {
+        VS
+    } else if part_of_speech.starts_with("adj-i") {
+        ADJ_I
+    } else {
+        0
+    };
+    word_type & pos_type != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 指定した品詞の辞書形の候補に含まれる場合、その活用を返す
+    fn find(word: &str, base: &str, word_type: u8) -> Option<Vec<Reason>> {
+        deinflect(word)
+            .into_iter()
+            .find(|c| c.word == base && c.word_type & word_type != 0)
+            .map(|c| c.reasons)
+    }
+
+    #[test]
+    fn test_deinflect_ichidan() {
+        assert_eq!(find("食べた", "食べる", V1), Some(vec![Reason::Past]));
+        assert_eq!(
+            find("食べなかった", "食べる", V1),
+            Some(vec![Reason::Past, Reason::Negative])
+        );
+        assert_eq!(
+            find("食べています", "食べる", V1),
+            Some(vec![Reason::Polite, Reason::Progressive, Reason::Te])
+        );
+    }
+
+    #[test]
+    fn test_deinflect_godan() {
+        assert_eq!(find("書いた", "書く", V5), Some(vec![Reason::Past]));
+        assert_eq!(find("読んで", "読む", V5), Some(vec![Reason::Te]));
+        assert_eq!(find("行った", "行く", V5), Some(vec![Reason::Past]));
+        assert_eq!(
+            find("書かせられた", "書く", V5),
+            Some(vec![Reason::Past, Reason::Passive, Reason::Causative])
+        );
+        assert_eq!(
+            find("話したくない", "話す", V5),
+            Some(vec![Reason::Negative, Reason::Desiderative])
+        );
+    }
+
+    #[test]
+    fn test_deinflect_irregular_and_adjective() {
+        assert_eq!(
+            find("勉強しました", "勉強", VS),
+            Some(vec![Reason::Polite, Reason::Past])
+        );
+        assert_eq!(find("来ない", "来る", VK), Some(vec![Reason::Negative]));
+        assert_eq!(find("高かった", "高い", ADJ_I), Some(vec![Reason::Past]));
+        assert_eq!(
+            find("よくない", "いい", ADJ_I),
+            Some(vec![Reason::Negative])
+        );
+    }
+
+    #[test]
+    fn test_deinflect_keeps_original_first() {
+        let candidates = deinflect("猫");
+        assert_eq!(candidates[0].word, "猫");
+        assert_eq!(candidates[0].word_type, 0);
+    }
+
+    #[test]
+    fn test_matches_part_of_speech() {
+        assert!(matches_part_of_speech(V5, "v5k-s"));
+        assert!(matches_part_of_speech(ADJ_I, "adj-ix"));
+        assert!(matches_part_of_speech(VS, "vs"));
+        assert!(matches_part_of_speech(0, "n"));
+        assert!(!matches_part_of_speech(V1, "n"));
+        assert!(!matches_part_of_speech(V1, "v5r"));
+    }
+}
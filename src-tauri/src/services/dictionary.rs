@@ -0,0 +1,712 @@
+//! オフライン辞書サービス
+//!
+//! ユーザーが用意したJMdict（XML）またはEDICT形式の辞書ファイルを取り込み、
+//! アプリのデータディレクトリにタブ区切りの索引として保存する。
+//! 索引は最初の検索時にバックグラウンドのスレッドで読み込み、以降はメモリ上で検索する。
+//! 1語だけを選択した場合は、LLMを呼び出さずに索引から即座に語義を返す。
+//! 日本語の活用形は辞書形に戻してから検索する
+
+use crate::services::deinflect::{self, Reason};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// 索引ファイル名（アプリのデータディレクトリに保存）
+pub const INDEX_FILE_NAME: &str = "dictionary.tsv";
+
+/// 索引ファイルの先頭行（形式のバージョン）
+const INDEX_HEADER: &str = "#dictionary-index v1";
+
+/// 索引内のリストの区切り文字
+const LIST_SEPARATOR: char = '\u{1f}';
+
+/// 1語とみなす最大文字数
+const MAX_TOKEN_CHARS: usize = 24;
+
+/// 検索結果の最大件数
+const MAX_MATCHES: usize = 10;
+
+/// 語義
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sense {
+    /// 品詞（JMdictの品詞コード。例: "n", "v5k", "adj-i"）
+    pub part_of_speech: Vec<String>,
+    /// 英語の語義
+    pub glosses: Vec<String>,
+}
+
+/// 辞書の見出し
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictionaryEntry {
+    /// 漢字表記
+    pub kanji: Vec<String>,
+    /// 読み（かな）
+    pub readings: Vec<String>,
+    /// 語義
+    pub senses: Vec<Sense>,
+}
+
+/// 検索で見つかった見出し
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictionaryMatch {
+    pub entry: DictionaryEntry,
+    /// 検索に使用した辞書形
+    pub base_form: String,
+    /// 辞書形に戻すために適用した活用（活用していない場合は空）
+    pub reasons: Vec<Reason>,
+}
+
+/// 辞書の状態
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictionaryStatus {
+    /// 辞書を読み込み済みかどうか
+    pub loaded: bool,
+    /// 見出し数
+    pub entry_count: usize,
+}
+
+impl DictionaryStatus {
+    pub fn of(dictionary: Option<&Dictionary>) -> Self {
+        Self {
+            loaded: dictionary.is_some(),
+            entry_count: dictionary.map_or(0, Dictionary::len),
+        }
+    }
+}
+
+/// 辞書エラー
+#[derive(Debug, Error)]
+pub enum DictionaryError {
+    #[error("辞書ファイルの読み込みに失敗しました: {0}")]
+    ReadFailed(String),
+    #[error("辞書ファイルの形式に対応していません（JMdictのXMLまたはEDICT形式のUTF-8ファイルを指定してください。EUC-JPのEDICTはUTF-8に変換してから取り込んでください）")]
+    UnsupportedFormat,
+    #[error("辞書の索引の保存に失敗しました: {0}")]
+    WriteFailed(String),
+    #[error("辞書の索引が壊れています。辞書ファイルを取り込み直してください（{0}行目）")]
+    InvalidIndex(usize),
+}
+
+impl Serialize for DictionaryError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 索引を読み込んだ辞書
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    entries: Vec<DictionaryEntry>,
+    /// 漢字表記・読み → 見出しの位置
+    headwords: HashMap<String, Vec<usize>>,
+    /// 正規化した英語の語義 → 見出しの位置
+    glosses: HashMap<String, Vec<usize>>,
+}
+
+impl Dictionary {
+    /// 見出しの一覧から辞書を構築する
+    pub fn new(entries: Vec<DictionaryEntry>) -> Self {
+        let mut headwords: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut glosses: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, entry) in entries.iter().enumerate() {
+            for word in entry.kanji.iter().chain(&entry.readings) {
+                push_unique(headwords.entry(word.clone()).or_default(), index);
+            }
+            for gloss in entry.senses.iter().flat_map(|sense| &sense.glosses) {
+                push_unique(glosses.entry(normalize_gloss(gloss)).or_default(), index);
+            }
+        }
+
+        Self {
+            entries,
+            headwords,
+            glosses,
+        }
+    }
+
+    /// 辞書ファイル（JMdict・EDICT）を取り込んで索引を保存する
+    ///
+    /// UTF-8のファイルのみ対応する（従来のEDICTのEUC-JPファイルは形式エラーとする）
+    pub fn import(source: &Path, index: &Path) -> Result<Self, DictionaryError> {
+        let bytes =
+            std::fs::read(source).map_err(|e| DictionaryError::ReadFailed(e.to_string()))?;
+        let content = String::from_utf8(bytes).map_err(|_| DictionaryError::UnsupportedFormat)?;
+
+        let entries = if content.contains("<entry>") {
+            parse_jmdict(&content)
+        } else {
+            parse_edict(&content)
+        };
+        if entries.is_empty() {
+            return Err(DictionaryError::UnsupportedFormat);
+        }
+
+        if let Some(dir) = index.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| DictionaryError::WriteFailed(e.to_string()))?;
+        }
+        std::fs::write(index, write_index(&entries))
+            .map_err(|e| DictionaryError::WriteFailed(e.to_string()))?;
+
+        Ok(Self::new(entries))
+    }
+
+    /// 保存済みの索引を読み込む
+    pub fn load(index: &Path) -> Result<Self, DictionaryError> {
+        let content = std::fs::read_to_string(index)
+            .map_err(|e| DictionaryError::ReadFailed(e.to_string()))?;
+        Ok(Self::new(read_index(&content)?))
+    }
+
+    /// 見出し数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 見出しがないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 語を検索する
+    ///
+    /// 日本語の語は活用形を辞書形に戻して見出しを検索し、英語の語は語義を検索する
+    pub fn lookup(&self, word: &str) -> Vec<DictionaryMatch> {
+        let word = word.trim();
+        let mut found: Vec<usize> = Vec::new();
+        let mut matches = Vec::new();
+
+        if word.chars().any(is_japanese_char) {
+            for candidate in deinflect::deinflect(word) {
+                let Some(indices) = self.headwords.get(&candidate.word) else {
+                    continue;
+                };
+                for &index in indices {
+                    let entry = &self.entries[index];
+                    let matches_type = entry.senses.iter().any(|sense| {
+                        sense
+                            .part_of_speech
+                            .iter()
+                            .any(|pos| deinflect::matches_part_of_speech(candidate.word_type, pos))
+                    });
+                    if matches_type && !found.contains(&index) {
+                        found.push(index);
+                        matches.push(DictionaryMatch {
+                            entry: entry.clone(),
+                            base_form: candidate.word.clone(),
+                            reasons: candidate.reasons.clone(),
+                        });
+                    }
+                }
+            }
+        } else if let Some(indices) = self.glosses.get(&normalize_gloss(word)) {
+            matches.extend(indices.iter().map(|&index| DictionaryMatch {
+                entry: self.entries[index].clone(),
+                base_form: word.to_string(),
+                reasons: Vec::new(),
+            }));
+        }
+
+        matches.truncate(MAX_MATCHES);
+        matches
+    }
+}
+
+/// 読み込んだ辞書の保存先（Tauriの状態として管理する）
+#[derive(Debug, Default)]
+pub struct DictionaryState {
+    dictionary: Mutex<Option<Arc<Dictionary>>>,
+}
+
+impl DictionaryState {
+    /// 読み込み済みの辞書を返す。未読み込みで索引があれば読み込む
+    ///
+    /// 索引の読み込みは非同期ランタイムを止めないよう`spawn_blocking`で行い、
+    /// 読み込み中はロックを保持しない
+    pub async fn get_or_load(
+        &self,
+        index: &Path,
+    ) -> Result<Option<Arc<Dictionary>>, DictionaryError> {
+        if let Some(dictionary) = self.get() {
+            return Ok(Some(dictionary));
+        }
+        if !index.exists() {
+            return Ok(None);
+        }
+
+        let path = index.to_path_buf();
+        let loaded = tokio::task::spawn_blocking(move || Dictionary::load(&path))
+            .await
+            .map_err(|e| DictionaryError::ReadFailed(e.to_string()))??;
+
+        // 読み込み中に取り込み・読み込みが完了していれば、そちらを優先する
+        let mut dictionary = self.dictionary.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(Some(
+            dictionary.get_or_insert_with(|| Arc::new(loaded)).clone(),
+        ))
+    }
+
+    /// 読み込み済みの辞書を返す
+    pub fn get(&self) -> Option<Arc<Dictionary>> {
+        self.dictionary
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 辞書を置き換える
+    pub fn set(&self, dictionary: Dictionary) {
+        *self.dictionary.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(dictionary));
+    }
+}
+
+/// 1語だけの選択かどうか（空白・句読点を含まない短い語）
+pub fn is_single_token(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && text.chars().count() <= MAX_TOKEN_CHARS
+        && text
+            .chars()
+            .all(|c| c.is_alphabetic() || is_japanese_char(c) || matches!(c, '-' | '\''))
+}
+
+/// ひらがな・カタカナ・漢字（長音符・繰り返し記号を含む）かどうか
+pub(crate) fn is_japanese_char(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}' // ひらがな
+        | '\u{30A0}'..='\u{30FF}' // カタカナ
+        | '\u{4E00}'..='\u{9FFF}' // CJK統合漢字
+        | '\u{3400}'..='\u{4DBF}' // CJK統合漢字拡張A
+        | '々'
+    )
+}
+
+fn push_unique(indices: &mut Vec<usize>, index: usize) {
+    if indices.last() != Some(&index) {
+        indices.push(index);
+    }
+}
+
+/// 英語の語義を検索用に正規化する（小文字化・先頭の"to "を除去）
+fn normalize_gloss(gloss: &str) -> String {
+    let gloss = gloss.trim().to_lowercase();
+    gloss
+        .strip_prefix("to ")
+        .map(str::to_string)
+        .unwrap_or(gloss)
+}
+
+/// XMLの文字参照・定義済み実体参照を戻す
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// 要素の内容をすべて取り出す（`<gloss xml:lang="ger">`のような属性付きの開始タグにも対応）
+fn elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut found = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after_name = &rest[start + open.len()..];
+        // `<sense>`を探しているときに`<senses>`のような別の要素に一致させない
+        if !after_name.starts_with(['>', ' ']) {
+            rest = after_name;
+            continue;
+        }
+        let Some(tag_end) = after_name.find('>') else {
+            break;
+        };
+        let attributes = &after_name[..tag_end];
+        let body = &after_name[tag_end + 1..];
+        let Some(end) = body.find(&close) else {
+            break;
+        };
+        found.push((attributes, &body[..end]));
+        rest = &body[end + close.len()..];
+    }
+
+    found
+}
+
+/// JMdictのXMLを解析する
+///
+/// 品詞は実体参照（`&v5k;`）の名前を品詞コードとして使用する。
+/// 品詞を省略した語義は、直前の語義の品詞を引き継ぐ（JMdictの仕様）
+pub fn parse_jmdict(xml: &str) -> Vec<DictionaryEntry> {
+    elements(xml, "entry")
+        .into_iter()
+        .filter_map(|(_, entry)| {
+            let kanji: Vec<String> = elements(entry, "keb")
+                .into_iter()
+                .map(|(_, text)| unescape_xml(text.trim()))
+                .collect();
+            let readings: Vec<String> = elements(entry, "reb")
+                .into_iter()
+                .map(|(_, text)| unescape_xml(text.trim()))
+                .collect();
+
+            let mut part_of_speech: Vec<String> = Vec::new();
+            let senses: Vec<Sense> = elements(entry, "sense")
+                .into_iter()
+                .filter_map(|(_, sense)| {
+                    let pos: Vec<String> = elements(sense, "pos")
+                        .into_iter()
+                        .map(|(_, text)| text.trim().trim_matches(['&', ';']).to_string())
+                        .collect();
+                    if !pos.is_empty() {
+                        part_of_speech = pos;
+                    }
+                    let glosses: Vec<String> = elements(sense, "gloss")
+                        .into_iter()
+                        .filter(|(attributes, _)| {
+                            !attributes.contains("xml:lang") || attributes.contains("\"eng\"")
+                        })
+                        .map(|(_, text)| unescape_xml(text.trim()))
+                        .collect();
+                    (!glosses.is_empty()).then(|| Sense {
+                        part_of_speech: part_of_speech.clone(),
+                        glosses,
+                    })
+                })
+                .collect();
+
+            (!readings.is_empty() && !senses.is_empty()).then_some(DictionaryEntry {
+                kanji,
+                readings,
+                senses,
+            })
+        })
+        .collect()
+}
+
+/// EDICT（EDICT2）形式を解析する
+///
+/// 例: `食べる;喰べる [たべる] /(v1,vt) (1) to eat/(2) to live on/EntL1358280X/`
+pub fn parse_edict(text: &str) -> Vec<DictionaryEntry> {
+    text.lines().filter_map(parse_edict_line).collect()
+}
+
+fn parse_edict_line(line: &str) -> Option<DictionaryEntry> {
+    let (head, body) = line.split_once(" /")?;
+    // 先頭行（"　？？？ /EDICT.../"）は見出しではない
+    if head.contains('？') {
+        return None;
+    }
+    let (kanji, readings) = match head.split_once('[') {
+        Some((kanji, readings)) => (kanji.trim(), readings.trim().trim_end_matches(']')),
+        // かなのみの語は見出しが読みになる
+        None => ("", head.trim()),
+    };
+    let split_words = |words: &str| -> Vec<String> {
+        words
+            .split(';')
+            // "(P)"などの付加情報を除去
+            .map(|word| {
+                word.split('(')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            })
+            .filter(|word| !word.is_empty())
+            .collect()
+    };
+
+    let mut senses: Vec<Sense> = Vec::new();
+    let mut part_of_speech: Vec<String> = Vec::new();
+    for field in body.split('/').map(str::trim) {
+        if field.is_empty() || field.starts_with("EntL") || field == "(P)" {
+            continue;
+        }
+
+        // 先頭の"(v1,vt)"や"(1)"を読み取る（"(n) (uk)"のように分かれた品詞はまとめる）
+        let mut gloss = field;
+        let mut new_sense = senses.is_empty();
+        let mut field_pos: Vec<String> = Vec::new();
+        while let Some(rest) = gloss.strip_prefix('(') {
+            let Some((tag, after)) = rest.split_once(')') else {
+                break;
+            };
+            if tag.chars().all(|c| c.is_ascii_digit()) {
+                new_sense = true;
+            } else if tag
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-,".contains(c))
+            {
+                field_pos.extend(tag.split(',').map(str::to_string));
+            } else {
+                break;
+            }
+            gloss = after.trim_start();
+        }
+        if !field_pos.is_empty() {
+            part_of_speech = field_pos;
+        }
+        if gloss.is_empty() {
+            continue;
+        }
+
+        match senses.last_mut() {
+            Some(sense) if !new_sense => sense.glosses.push(gloss.to_string()),
+            _ => senses.push(Sense {
+                part_of_speech: part_of_speech.clone(),
+                glosses: vec![gloss.to_string()],
+            }),
+        }
+    }
+
+    let readings = split_words(readings);
+    (!readings.is_empty() && !senses.is_empty()).then(|| DictionaryEntry {
+        kanji: split_words(kanji),
+        readings,
+        senses,
+    })
+}
+
+/// 索引を書き出す
+///
+/// 1行に1見出しで、タブ区切りで漢字表記・読み・（品詞, 語義）の繰り返しを並べる。
+/// 各フィールド内のリストは単位区切り文字（U+001F）で区切る
+fn write_index(entries: &[DictionaryEntry]) -> String {
+    let join = |items: &[String]| {
+        items
+            .iter()
+            .map(|item| item.replace(['\t', '\n', LIST_SEPARATOR], " "))
+            .collect::<Vec<_>>()
+            .join(&LIST_SEPARATOR.to_string())
+    };
+
+    let mut output = String::from(INDEX_HEADER);
+    output.push('\n');
+    for entry in entries {
+        output.push_str(&join(&entry.kanji));
+        output.push('\t');
+        output.push_str(&join(&entry.readings));
+        for sense in &entry.senses {
+            output.push('\t');
+            output.push_str(&join(&sense.part_of_speech));
+            output.push('\t');
+            output.push_str(&join(&sense.glosses));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// 索引を読み込む
+fn read_index(content: &str) -> Result<Vec<DictionaryEntry>, DictionaryError> {
+    let mut lines = content.lines();
+    if lines.next() != Some(INDEX_HEADER) {
+        return Err(DictionaryError::InvalidIndex(1));
+    }
+    let split = |field: &str| -> Vec<String> {
+        field
+            .split(LIST_SEPARATOR)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+
+    lines
+        .enumerate()
+        .map(|(number, line)| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 4 || !fields.len().is_multiple_of(2) {
+                return Err(DictionaryError::InvalidIndex(number + 2));
+            }
+            Ok(DictionaryEntry {
+                kanji: split(fields[0]),
+                readings: split(fields[1]),
+                senses: fields[2..]
+                    .chunks(2)
+                    .map(|sense| Sense {
+                        part_of_speech: split(sense[0]),
+                        glosses: split(sense[1]),
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JMDICT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE JMdict [
+<!ENTITY v1 "Ichidan verb">
+]>
+<JMdict>
+<entry>
+<ent_seq>1358280</ent_seq>
+<k_ele><keb>食べる</keb></k_ele>
+<k_ele><keb>喰べる</keb></k_ele>
+<r_ele><reb>たべる</reb></r_ele>
+<sense>
+<pos>&v1;</pos>
+<pos>&vt;</pos>
+<gloss>to eat</gloss>
+<gloss xml:lang="ger">essen</gloss>
+</sense>
+<sense>
+<gloss>to live on (e.g. a salary)</gloss>
+</sense>
+</entry>
+<entry>
+<ent_seq>1000000</ent_seq>
+<r_ele><reb>ちょっと</reb></r_ele>
+<sense>
+<pos>&adv;</pos>
+<gloss>just a minute &amp; a little</gloss>
+</sense>
+</entry>
+</JMdict>"#;
+
+    const EDICT: &str = "　？？？ /,EDICT,JMdict/\n\
+                         書く [かく] /(v5k,vt) (1) to write/(2) to draw/EntL1199800X/\n\
+                         高い [たかい] /(adj-i) (1) high/tall/(2) expensive/EntL1584120X/\n\
+                         勉強(P) [べんきょう] /(n,vs) study/(P)/EntL1512080X/\n";
+
+    #[test]
+    fn test_parse_jmdict() {
+        let entries = parse_jmdict(JMDICT);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kanji, vec!["食べる", "喰べる"]);
+        assert_eq!(entries[0].readings, vec!["たべる"]);
+        assert_eq!(
+            entries[0].senses,
+            vec![
+                Sense {
+                    part_of_speech: vec!["v1".to_string(), "vt".to_string()],
+                    glosses: vec!["to eat".to_string()],
+                },
+                // 品詞を省略した語義は直前の品詞を引き継ぐ
+                Sense {
+                    part_of_speech: vec!["v1".to_string(), "vt".to_string()],
+                    glosses: vec!["to live on (e.g. a salary)".to_string()],
+                },
+            ]
+        );
+        assert!(entries[1].kanji.is_empty());
+        assert_eq!(
+            entries[1].senses[0].glosses,
+            vec!["just a minute & a little"]
+        );
+    }
+
+    #[test]
+    fn test_parse_edict() {
+        let entries = parse_edict(EDICT);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].kanji, vec!["書く"]);
+        assert_eq!(entries[0].senses.len(), 2);
+        assert_eq!(entries[0].senses[1].part_of_speech, vec!["v5k", "vt"]);
+        assert_eq!(entries[0].senses[1].glosses, vec!["to draw"]);
+        assert_eq!(entries[1].senses[0].glosses, vec!["high", "tall"]);
+        assert_eq!(entries[2].kanji, vec!["勉強"]);
+        assert_eq!(entries[2].senses[0].part_of_speech, vec!["n", "vs"]);
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        let entries = parse_jmdict(JMDICT);
+        let index = write_index(&entries);
+        assert_eq!(read_index(&index).unwrap(), entries);
+
+        assert!(matches!(
+            read_index("not an index"),
+            Err(DictionaryError::InvalidIndex(1))
+        ));
+    }
+
+    #[test]
+    fn test_lookup_with_deinflection() {
+        let mut entries = parse_jmdict(JMDICT);
+        entries.extend(parse_edict(EDICT));
+        let dictionary = Dictionary::new(entries);
+
+        let matches = dictionary.lookup("食べなかった");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].base_form, "食べる");
+        assert_eq!(matches[0].reasons, vec![Reason::Past, Reason::Negative]);
+
+        let matches = dictionary.lookup("勉強しています");
+        assert_eq!(matches[0].base_form, "勉強");
+
+        assert_eq!(dictionary.lookup("高くて")[0].base_form, "高い");
+        assert_eq!(dictionary.lookup("たべる")[0].reasons, vec![]);
+        assert!(dictionary.lookup("猫").is_empty());
+    }
+
+    #[test]
+    fn test_lookup_english() {
+        let dictionary = Dictionary::new(parse_edict(EDICT));
+        let matches = dictionary.lookup("Write");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].entry.readings, vec!["かく"]);
+        assert!(dictionary.lookup("expensive")[0].entry.kanji == vec!["高い"]);
+    }
+
+    #[tokio::test]
+    async fn test_import_and_load() {
+        let dir = std::env::temp_dir().join(format!("dictionary-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("edict.txt");
+        let index = dir.join("index").join(INDEX_FILE_NAME);
+        std::fs::write(&source, EDICT).unwrap();
+
+        let imported = Dictionary::import(&source, &index).unwrap();
+        assert_eq!(imported.len(), 3);
+
+        let state = DictionaryState::default();
+        let loaded = state.get_or_load(&index).await.unwrap().unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded.lookup("書いた")[0].base_form, "書く");
+
+        std::fs::write(&source, "plain text").unwrap();
+        assert!(matches!(
+            Dictionary::import(&source, &index),
+            Err(DictionaryError::UnsupportedFormat)
+        ));
+
+        // EUC-JPの「辞書」
+        std::fs::write(
+            &source,
+            b"\xBC\xAD\xBD\xF1 [\xA4\xB8\xA4\xB7\xA4\xE7] /(n) dictionary/\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            Dictionary::import(&source, &index),
+            Err(DictionaryError::UnsupportedFormat)
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_is_single_token() {
+        assert!(is_single_token("食べなかった"));
+        assert!(is_single_token(" serendipity "));
+        assert!(is_single_token("well-known"));
+        assert!(!is_single_token("two words"));
+        assert!(!is_single_token("文です。"));
+        assert!(!is_single_token(""));
+    }
+}
@@ -0,0 +1,572 @@
+//! ファイル翻訳サービス
+//!
+//! Markdown・テキスト・字幕（SRT/WebVTT）ファイルを解析し、構造やタイミングを保持したまま
+//! テキスト部分のみを翻訳して、元のファイルと同じ場所に翻訳版を書き出す
+
+use crate::services::rich_text::{self, Part, RichDocument, RichTextFormat};
+use crate::services::translation::{Language, TranslationError};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// 対応するファイル形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DocumentFormat {
+    /// Markdown（.md, .markdown）
+    Markdown,
+    /// プレーンテキスト（.txt）
+    PlainText,
+    /// SubRip字幕（.srt）
+    Srt,
+    /// WebVTT字幕（.vtt）
+    Vtt,
+}
+
+impl DocumentFormat {
+    /// ファイルの拡張子から形式を判定
+    pub fn from_path(path: &Path) -> Result<Self, DocumentError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "md" | "markdown" => Ok(DocumentFormat::Markdown),
+            "txt" => Ok(DocumentFormat::PlainText),
+            "srt" => Ok(DocumentFormat::Srt),
+            "vtt" => Ok(DocumentFormat::Vtt),
+            _ => Err(DocumentError::UnsupportedFormat(extension)),
+        }
+    }
+}
+
+/// ファイル翻訳の進捗
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTranslationProgress {
+    /// 翻訳中のファイルパス
+    pub path: String,
+    /// 翻訳済みのセグメント数
+    pub completed: usize,
+    /// セグメントの総数
+    pub total: usize,
+    /// 今回翻訳されたセグメント
+    pub segments: Vec<String>,
+}
+
+/// ファイル翻訳結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTranslationResult {
+    /// 元のファイルパス
+    pub input_path: String,
+    /// 翻訳版のファイルパス
+    pub output_path: String,
+    /// ファイル形式
+    pub format: DocumentFormat,
+    /// 翻訳したセグメント数
+    pub segment_count: usize,
+    /// 翻訳元言語
+    pub source_lang: Language,
+    /// 翻訳先言語
+    pub target_lang: Language,
+    /// 翻訳にかかった時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// ファイル翻訳エラー
+#[derive(Debug, Error)]
+pub enum DocumentError {
+    #[error("対応していないファイル形式です: {0}")]
+    UnsupportedFormat(String),
+    #[error("ファイルの読み込みに失敗しました: {0}")]
+    ReadFailed(String),
+    #[error("ファイルの書き込みに失敗しました: {0}")]
+    WriteFailed(String),
+    #[error("{0}")]
+    TranslationFailed(#[from] TranslationError),
+}
+
+impl Serialize for DocumentError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 改行を除いた行本体と改行部分に分割
+fn split_line_ending(line: &str) -> (&str, &str) {
+    let body = line.trim_end_matches(['\n', '\r']);
+    (body, &line[body.len()..])
+}
+
+/// Markdownの先頭にあるフロントマター（`---`で囲まれたYAML）を分離する
+fn split_front_matter(content: &str) -> (&str, &str) {
+    let mut lines = content.split_inclusive('\n');
+    let Some(first) = lines.next() else {
+        return ("", content);
+    };
+    if split_line_ending(first).0 != "---" {
+        return ("", content);
+    }
+
+    let mut end = first.len();
+    for line in lines {
+        end += line.len();
+        let (body, _) = split_line_ending(line);
+        if body == "---" || body == "..." {
+            return content.split_at(end);
+        }
+    }
+
+    // 閉じられていない場合はフロントマターとして扱わない
+    ("", content)
+}
+
+/// 空行区切りの段落を1つのテキストとして追加
+///
+/// 複数行の段落・キューは翻訳後も元の行数で改行し直す
+fn flush_paragraph(parts: &mut Vec<Part>, lines: &mut Vec<&str>, newline: &str) {
+    if lines.is_empty() {
+        return;
+    }
+    rich_text::push_lines(parts, lines, newline);
+    rich_text::push_raw(parts, newline);
+    lines.clear();
+}
+
+/// プレーンテキストを段落ごとに分割
+fn parse_plain_text(content: &str, parts: &mut Vec<Part>) {
+    let mut paragraph = Vec::new();
+    let mut newline = "";
+
+    for line in content.split_inclusive('\n') {
+        let (body, line_ending) = split_line_ending(line);
+        if body.trim().is_empty() {
+            flush_paragraph(parts, &mut paragraph, newline);
+            rich_text::push_raw(parts, line);
+        } else {
+            paragraph.push(body);
+            newline = line_ending;
+        }
+    }
+
+    flush_paragraph(parts, &mut paragraph, newline);
+}
+
+/// 字幕（SRT/WebVTT）を解析し、番号・タイミング行を保持してキューのテキストを分割
+///
+/// タイミング行（`-->`を含む行）の後から空行までをキューのテキストとして扱う。
+/// WebVTTのヘッダーやNOTE・STYLEブロックはタイミング行を含まないためそのまま残る
+fn parse_subtitles(content: &str, parts: &mut Vec<Part>) {
+    let mut cue_text = Vec::new();
+    let mut in_cue = false;
+    let mut newline = "";
+
+    for line in content.split_inclusive('\n') {
+        let (body, line_ending) = split_line_ending(line);
+
+        if body.trim().is_empty() {
+            flush_paragraph(parts, &mut cue_text, newline);
+            in_cue = false;
+            rich_text::push_raw(parts, line);
+        } else if in_cue {
+            cue_text.push(body);
+            newline = line_ending;
+        } else {
+            in_cue = body.contains("-->");
+            rich_text::push_raw(parts, line);
+        }
+    }
+
+    flush_paragraph(parts, &mut cue_text, newline);
+}
+
+/// ファイルの内容を解析する
+pub fn parse_document(content: &str, format: DocumentFormat) -> RichDocument {
+    let mut parts = Vec::new();
+
+    match format {
+        DocumentFormat::Markdown => {
+            let (front_matter, body) = split_front_matter(content);
+            rich_text::push_raw(&mut parts, front_matter);
+            rich_text::parse_markdown(body, &mut parts);
+        }
+        DocumentFormat::PlainText => parse_plain_text(content, &mut parts),
+        DocumentFormat::Srt | DocumentFormat::Vtt => parse_subtitles(content, &mut parts),
+    }
+
+    RichDocument::from_parts(RichTextFormat::Markdown, parts)
+}
+
+/// 翻訳版の出力パスを取得（例: `README.md` → `README.ja.md`）
+pub fn output_path(path: &Path, target_lang: Language) -> PathBuf {
+    numbered_output_path(path, target_lang, 1)
+}
+
+/// 番号付きの出力パスを取得（例: 2番目は`README.ja-2.md`）
+fn numbered_output_path(path: &Path, target_lang: Language, number: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let suffix = if number > 1 {
+        format!("{}-{}", target_lang.code(), number)
+    } else {
+        target_lang.code().to_string()
+    };
+
+    let file_name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, suffix, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, suffix),
+    };
+
+    path.with_file_name(file_name)
+}
+
+/// 翻訳版を書き出し、書き出したパスを返す
+///
+/// 上書きしない場合は既存のファイルと重ならない番号付きの名前を選ぶ
+async fn write_output(
+    path: &Path,
+    target_lang: Language,
+    content: &str,
+    overwrite: bool,
+) -> Result<PathBuf, DocumentError> {
+    let write_failed = |e: std::io::Error| DocumentError::WriteFailed(e.to_string());
+
+    if overwrite {
+        let output = output_path(path, target_lang);
+        tokio::fs::write(&output, content)
+            .await
+            .map_err(write_failed)?;
+        return Ok(output);
+    }
+
+    let mut number = 1;
+    loop {
+        let output = numbered_output_path(path, target_lang, number);
+        // 存在しない場合のみ作成し、確認から書き込みまでの間に作られたファイルも上書きしない
+        match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&output)
+            .await
+        {
+            Ok(_) => {
+                tokio::fs::write(&output, content)
+                    .await
+                    .map_err(write_failed)?;
+                return Ok(output);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => number += 1,
+            Err(e) => return Err(write_failed(e)),
+        }
+    }
+}
+
+/// ファイルを翻訳し、翻訳版を元のファイルと同じディレクトリに書き出す
+///
+/// # Arguments
+/// * `path` - 翻訳するファイルのパス
+/// * `overwrite` - 既存の翻訳版を上書きするか（falseの場合は`README.ja-2.md`のように別名で書き出す）
+/// * `translate` - テキストを翻訳する関数（プロバイダーの翻訳処理）
+/// * `on_progress` - セグメントの翻訳が進むたびに呼ばれる関数
+pub async fn translate_file<F, Fut, P>(
+    path: &Path,
+    source_lang: Language,
+    target_lang: Language,
+    overwrite: bool,
+    translate: F,
+    mut on_progress: P,
+) -> Result<FileTranslationResult, DocumentError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+    P: FnMut(FileTranslationProgress),
+{
+    let start = std::time::Instant::now();
+
+    let format = DocumentFormat::from_path(path)?;
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| DocumentError::ReadFailed(e.to_string()))?;
+
+    let document = parse_document(&content, format);
+    let segments = document.segments();
+    let total = segments.len();
+    let path_str = path.to_string_lossy().into_owned();
+
+    let translations = rich_text::translate_segments_with_progress(
+        &segments,
+        rich_text::DEFAULT_BATCH_CHARS,
+        translate,
+        |completed, batch| {
+            on_progress(FileTranslationProgress {
+                path: path_str.clone(),
+                completed,
+                total,
+                segments: batch.to_vec(),
+            })
+        },
+    )
+    .await?;
+
+    let output = write_output(
+        path,
+        target_lang,
+        &document.reassemble(&translations),
+        overwrite,
+    )
+    .await?;
+
+    Ok(FileTranslationResult {
+        input_path: path_str,
+        output_path: output.to_string_lossy().into_owned(),
+        format,
+        segment_count: total,
+        source_lang,
+        target_lang,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テキストを大文字にする疑似翻訳
+    fn upper(document: &RichDocument) -> String {
+        let translations: Vec<String> = document
+            .segments()
+            .iter()
+            .map(|s| s.to_uppercase())
+            .collect();
+        document.reassemble(&translations)
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            DocumentFormat::from_path(Path::new("README.md")).unwrap(),
+            DocumentFormat::Markdown
+        );
+        assert_eq!(
+            DocumentFormat::from_path(Path::new("notes.TXT")).unwrap(),
+            DocumentFormat::PlainText
+        );
+        assert_eq!(
+            DocumentFormat::from_path(Path::new("movie.srt")).unwrap(),
+            DocumentFormat::Srt
+        );
+        assert_eq!(
+            DocumentFormat::from_path(Path::new("movie.vtt")).unwrap(),
+            DocumentFormat::Vtt
+        );
+
+        let err = DocumentFormat::from_path(Path::new("image.png")).unwrap_err();
+        assert_eq!(err.to_string(), "対応していないファイル形式です: png");
+    }
+
+    #[test]
+    fn test_output_path() {
+        assert_eq!(
+            output_path(Path::new("/docs/README.md"), Language::Japanese),
+            PathBuf::from("/docs/README.ja.md")
+        );
+        assert_eq!(
+            output_path(Path::new("movie.srt"), Language::English),
+            PathBuf::from("movie.en.srt")
+        );
+        assert_eq!(
+            numbered_output_path(Path::new("/docs/README.md"), Language::Japanese, 2),
+            PathBuf::from("/docs/README.ja-2.md")
+        );
+    }
+
+    #[test]
+    fn test_markdown_keeps_front_matter_and_code() {
+        let content = "---\ntitle: Hello\n---\n# Intro\n\n```sh\necho hello\n```\nSome text\n";
+        let document = parse_document(content, DocumentFormat::Markdown);
+
+        assert_eq!(document.segments(), vec!["Intro", "Some text"]);
+        assert_eq!(
+            upper(&document),
+            "---\ntitle: Hello\n---\n# INTRO\n\n```sh\necho hello\n```\nSOME TEXT\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_without_closing_front_matter() {
+        let document = parse_document("---\ntitle\n", DocumentFormat::Markdown);
+        assert_eq!(document.segments(), vec!["title"]);
+    }
+
+    #[test]
+    fn test_plain_text_paragraphs() {
+        let content = "First line\nsecond line\n\nNext paragraph\r\n";
+        let document = parse_document(content, DocumentFormat::PlainText);
+
+        assert_eq!(
+            document.segments(),
+            vec!["First line second line", "Next paragraph"]
+        );
+        assert_eq!(
+            upper(&document),
+            "FIRST LINE\nSECOND LINE\n\nNEXT PARAGRAPH\r\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_keeps_timings() {
+        let content = "1\n00:00:01,000 --> 00:00:02,500\nHello,\nhow are you?\n\n2\n00:00:03,000 --> 00:00:04,000\nFine.\n";
+        let document = parse_document(content, DocumentFormat::Srt);
+
+        assert_eq!(document.segments(), vec!["Hello, how are you?", "Fine."]);
+        assert_eq!(
+            upper(&document),
+            "1\n00:00:01,000 --> 00:00:02,500\nHELLO,\nHOW ARE YOU?\n\n2\n00:00:03,000 --> 00:00:04,000\nFINE.\n"
+        );
+    }
+
+    #[test]
+    fn test_srt_keeps_cue_line_breaks() {
+        let content = "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello,\r\nhow are you?\r\n";
+        let document = parse_document(content, DocumentFormat::Srt);
+
+        // 訳文を元のキューと同じ2行に分割する（句読点の直後を優先）
+        let translated = document.reassemble(&["こんにちは、お元気ですか？".to_string()]);
+        assert_eq!(
+            translated,
+            "1\r\n00:00:01,000 --> 00:00:02,500\r\nこんにちは、\r\nお元気ですか？\r\n"
+        );
+
+        // 訳文が短い場合は空行を作らず行数を減らす
+        for short in ["やあ", "Hi!"] {
+            let translated = document.reassemble(&[short.to_string()]);
+            assert_eq!(
+                translated,
+                format!("1\r\n00:00:01,000 --> 00:00:02,500\r\n{short}\r\n")
+            );
+        }
+    }
+
+    #[test]
+    fn test_vtt_keeps_header_and_notes() {
+        let content = "WEBVTT\n\nNOTE This is a comment\n\nintro\n00:01.000 --> 00:04.000 align:start\nGood morning\n";
+        let document = parse_document(content, DocumentFormat::Vtt);
+
+        assert_eq!(document.segments(), vec!["Good morning"]);
+        assert_eq!(
+            upper(&document),
+            "WEBVTT\n\nNOTE This is a comment\n\nintro\n00:01.000 --> 00:04.000 align:start\nGOOD MORNING\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_file_writes_copy_and_reports_progress() {
+        let dir = std::env::temp_dir().join(format!("honnyaku-document-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("sample.srt");
+        std::fs::write(&input, "1\n00:00:01,000 --> 00:00:02,000\nHello\n").unwrap();
+
+        let mut progress = Vec::new();
+        let result = translate_file(
+            &input,
+            Language::English,
+            Language::Japanese,
+            false,
+            |text| async move { Ok(text.replace("Hello", "こんにちは")) },
+            |p| progress.push(p),
+        )
+        .await
+        .unwrap();
+
+        let output = std::fs::read_to_string(dir.join("sample.ja.srt")).unwrap();
+        assert_eq!(output, "1\n00:00:01,000 --> 00:00:02,000\nこんにちは\n");
+        assert_eq!(result.segment_count, 1);
+        assert_eq!(result.format, DocumentFormat::Srt);
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].completed, 1);
+        assert_eq!(progress[0].total, 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_translate_file_does_not_overwrite_existing_output() {
+        let dir = std::env::temp_dir().join(format!(
+            "honnyaku-document-overwrite-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("notes.txt");
+        std::fs::write(&input, "Hello\n").unwrap();
+        std::fs::write(dir.join("notes.ja.txt"), "既存の訳\n").unwrap();
+
+        let translate = |text: String| async move { Ok(text.replace("Hello", "こんにちは")) };
+
+        // 既存の翻訳版は残し、別名で書き出す
+        let result = translate_file(
+            &input,
+            Language::English,
+            Language::Japanese,
+            false,
+            translate,
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            PathBuf::from(&result.output_path),
+            dir.join("notes.ja-2.txt")
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes.ja.txt")).unwrap(),
+            "既存の訳\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes.ja-2.txt")).unwrap(),
+            "こんにちは\n"
+        );
+
+        // 上書きを指定した場合は既存の翻訳版を置き換える
+        let result = translate_file(
+            &input,
+            Language::English,
+            Language::Japanese,
+            true,
+            translate,
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(PathBuf::from(&result.output_path), dir.join("notes.ja.txt"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes.ja.txt")).unwrap(),
+            "こんにちは\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_translate_file_missing() {
+        let result = translate_file(
+            Path::new("/nonexistent/honnyaku.md"),
+            Language::English,
+            Language::Japanese,
+            false,
+            |text| async move { Ok(text) },
+            |_| {},
+        )
+        .await;
+
+        assert!(matches!(result, Err(DocumentError::ReadFailed(_))));
+    }
+}
@@ -0,0 +1,309 @@
+//! 翻訳ドメインプリセット
+//!
+//! 技術文書・法務文書・マーケティングコピー・チャットなど、翻訳対象の分野ごとに
+//! プロンプトの方針、既定の用語集、サンプリングパラメータを提供する。
+//! Ollama・Claude CLIの両プロバイダーで共通に使用する
+
+use crate::services::translation::Language;
+use serde::{Deserialize, Serialize};
+
+/// 用語集の項目（翻訳元の用語, 訳語）
+pub type GlossaryEntry = (&'static str, &'static str);
+
+/// 翻訳ドメイン
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Domain {
+    /// 技術文書（従来の既定）
+    #[default]
+    Technical,
+    /// 法務・契約文書
+    Legal,
+    /// マーケティング・広告コピー
+    Marketing,
+    /// チャット・カジュアルな会話
+    Chat,
+}
+
+const LEGAL_EN_JA: &[GlossaryEntry] = &[
+    ("Agreement", "本契約"),
+    ("party", "当事者"),
+    ("liability", "責任"),
+    ("indemnify", "補償する"),
+    ("termination", "解除"),
+    ("confidential information", "秘密情報"),
+    ("governing law", "準拠法"),
+];
+
+const LEGAL_JA_EN: &[GlossaryEntry] = &[
+    ("本契約", "this Agreement"),
+    ("甲", "Party A"),
+    ("乙", "Party B"),
+    ("損害賠償", "damages"),
+    ("秘密情報", "Confidential Information"),
+    ("準拠法", "governing law"),
+];
+
+const MARKETING_EN_JA: &[GlossaryEntry] = &[
+    ("free trial", "無料トライアル"),
+    ("limited time", "期間限定"),
+    ("sign up", "今すぐ登録"),
+    ("free shipping", "送料無料"),
+];
+
+const MARKETING_JA_EN: &[GlossaryEntry] = &[
+    ("無料トライアル", "free trial"),
+    ("期間限定", "limited time"),
+    ("今すぐ登録", "sign up now"),
+    ("送料無料", "free shipping"),
+];
+
+const CHAT_EN_JA: &[GlossaryEntry] = &[
+    ("LGTM", "問題なさそうです"),
+    ("FYI", "参考までに"),
+    ("ASAP", "至急"),
+    ("btw", "ところで"),
+];
+
+const CHAT_JA_EN: &[GlossaryEntry] = &[
+    ("お疲れ様です", "Hi"),
+    ("よろしくお願いします", "Thanks"),
+    ("承知しました", "Understood"),
+    ("了解です", "Got it"),
+];
+
+impl Domain {
+    /// 翻訳者の役割（翻訳先言語で記述）
+    pub fn role(&self, target_lang: Language) -> &'static str {
+        match (self, target_lang) {
+            (Domain::Technical, Language::Japanese) => "プロフェッショナルな技術翻訳者",
+            (Domain::Technical, Language::English) => "a professional technical translator",
+            (Domain::Legal, Language::Japanese) => "法務・契約文書を専門とするプロの翻訳者",
+            (Domain::Legal, Language::English) => {
+                "a professional legal translator specializing in contracts"
+            }
+            (Domain::Marketing, Language::Japanese) => {
+                "マーケティング・広告コピーを専門とするプロの翻訳者"
+            }
+            (Domain::Marketing, Language::English) => {
+                "a professional translator specializing in marketing copy"
+            }
+            (Domain::Chat, Language::Japanese) => {
+                "チャットやメッセージのやり取りを自然に訳す翻訳者"
+            }
+            (Domain::Chat, Language::English) => "a translator who renders chat messages naturally",
+        }
+    }
+
+    /// 翻訳方針（翻訳先言語で記述）
+    pub fn guidelines(&self, target_lang: Language) -> &'static [&'static str] {
+        match (self, target_lang) {
+            (Domain::Technical, Language::Japanese) => &[
+                "技術文書に特化した翻訳を提供します",
+                "コード・識別子・コマンドは原文のまま保持してください",
+            ],
+            (Domain::Technical, Language::English) => &[
+                "Provide technical document-focused translations",
+                "Preserve code, identifiers and commands as-is",
+            ],
+            (Domain::Legal, Language::Japanese) => &[
+                "条文の構造・番号・定義語を原文どおりに保持してください",
+                "意訳せず、権利・義務・条件の意味を正確に訳してください",
+                "法律文書にふさわしい正式な表現を用い、曖昧な表現を避けてください",
+                "定義語は文書全体で同じ訳語を使用してください",
+            ],
+            (Domain::Legal, Language::English) => &[
+                "Preserve clause structure, numbering and defined terms exactly",
+                "Do not paraphrase; render rights, obligations and conditions precisely",
+                "Use formal legal register and avoid ambiguous wording",
+                "Use the same translation for each defined term throughout the document",
+            ],
+            (Domain::Marketing, Language::Japanese) => &[
+                "直訳にこだわらず、読み手の心に響く自然なコピーにしてください",
+                "原文の訴求ポイント・ニュアンス・勢いを保ってください",
+                "ブランド名・商品名は原文のまま保持してください",
+                "見出しやキャッチコピーは簡潔にまとめてください",
+            ],
+            (Domain::Marketing, Language::English) => &[
+                "Favor natural, persuasive copy over literal translation",
+                "Keep the selling points, nuance and energy of the original",
+                "Preserve brand and product names as-is",
+                "Keep headlines and taglines short and punchy",
+            ],
+            (Domain::Chat, Language::Japanese) => &[
+                "チャットのメッセージとして自然な、短く読みやすい表現にしてください",
+                "絵文字・顔文字・メンション（@ユーザー名）・URLはそのまま保持してください",
+                "原文の感情やトーンを保ち、堅苦しい表現を避けてください",
+                "略語やスラングは意味が伝わる自然な表現に訳してください",
+            ],
+            (Domain::Chat, Language::English) => &[
+                "Keep it natural, short and easy to read as a chat message",
+                "Preserve emoji, emoticons, @mentions and URLs as-is",
+                "Keep the emotion and tone of the original and avoid stiff wording",
+                "Render abbreviations and slang with natural equivalents",
+            ],
+        }
+    }
+
+    /// 汎用LLM向けの短い指示（翻訳先言語で記述）
+    ///
+    /// 技術文書は従来のシンプルなプロンプトを維持するためNone
+    pub fn instruction(&self, target_lang: Language) -> Option<&'static str> {
+        match (self, target_lang) {
+            (Domain::Technical, _) => None,
+            (Domain::Legal, Language::Japanese) => {
+                Some("法律・契約文書として、条文の構造と定義語を保ち正確に翻訳してください。")
+            }
+            (Domain::Legal, Language::English) => Some(
+                "This is a legal document: translate precisely, keeping clause structure and defined terms.",
+            ),
+            (Domain::Marketing, Language::Japanese) => {
+                Some("マーケティング用のコピーとして、訴求力のある自然な日本語に翻訳してください。")
+            }
+            (Domain::Marketing, Language::English) => {
+                Some("This is marketing copy: make it natural and persuasive for native readers.")
+            }
+            (Domain::Chat, Language::Japanese) => {
+                Some("チャットのメッセージとして、自然で短い口語的な日本語に翻訳してください。")
+            }
+            (Domain::Chat, Language::English) => Some(
+                "This is a chat message: keep it natural, short and conversational, keeping emoji and @mentions.",
+            ),
+        }
+    }
+
+    /// 既定の用語集
+    ///
+    /// 技術用語はClaude CLIのプロンプトに専用の辞書があるため空
+    pub fn glossary(
+        &self,
+        source_lang: Language,
+        target_lang: Language,
+    ) -> &'static [GlossaryEntry] {
+        match (self, source_lang, target_lang) {
+            (Domain::Legal, Language::English, Language::Japanese) => LEGAL_EN_JA,
+            (Domain::Legal, Language::Japanese, Language::English) => LEGAL_JA_EN,
+            (Domain::Marketing, Language::English, Language::Japanese) => MARKETING_EN_JA,
+            (Domain::Marketing, Language::Japanese, Language::English) => MARKETING_JA_EN,
+            (Domain::Chat, Language::English, Language::Japanese) => CHAT_EN_JA,
+            (Domain::Chat, Language::Japanese, Language::English) => CHAT_JA_EN,
+            _ => &[],
+        }
+    }
+
+    /// テキストに含まれる用語のみに絞った用語集
+    ///
+    /// 小さなモデルのプロンプトを短く保つために使用する（英語は大文字小文字を区別しない）
+    pub fn glossary_for(
+        &self,
+        text: &str,
+        source_lang: Language,
+        target_lang: Language,
+    ) -> Vec<GlossaryEntry> {
+        let lower = text.to_lowercase();
+        self.glossary(source_lang, target_lang)
+            .iter()
+            .filter(|(term, _)| lower.contains(&term.to_lowercase()))
+            .copied()
+            .collect()
+    }
+
+    /// サンプリングパラメータをドメインに合わせて調整する
+    ///
+    /// 法務は正確さを優先して温度を下げ、マーケティング・チャットは表現の自然さを優先して上げる
+    pub fn apply_sampling(&self, options: &mut serde_json::Value) {
+        let Some(temperature) = options["temperature"].as_f64() else {
+            return;
+        };
+
+        let adjusted = match self {
+            Domain::Technical => temperature,
+            Domain::Legal => temperature.min(0.1),
+            Domain::Marketing => temperature + 0.3,
+            Domain::Chat => temperature + 0.2,
+        };
+        options["temperature"] = serde_json::json!(adjusted);
+    }
+}
+
+/// 用語集をプロンプト用の1行に整形（例: "Agreement → 本契約, party → 当事者"）
+pub fn format_glossary(entries: &[GlossaryEntry]) -> String {
+    entries
+        .iter()
+        .map(|(term, translation)| format!("{} → {}", term, translation))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_domain() {
+        assert_eq!(Domain::default(), Domain::Technical);
+        assert_eq!(Domain::Technical.instruction(Language::Japanese), None);
+    }
+
+    #[test]
+    fn test_domain_serialization() {
+        assert_eq!(
+            serde_json::to_string(&Domain::Marketing).unwrap(),
+            "\"marketing\""
+        );
+        let domain: Domain = serde_json::from_str("\"chat\"").unwrap();
+        assert_eq!(domain, Domain::Chat);
+    }
+
+    #[test]
+    fn test_instruction_in_target_language() {
+        let ja = Domain::Legal.instruction(Language::Japanese).unwrap();
+        assert!(ja.contains("契約"));
+        let en = Domain::Chat.instruction(Language::English).unwrap();
+        assert!(en.contains("chat message"));
+    }
+
+    #[test]
+    fn test_glossary_for_filters_terms_in_text() {
+        let entries = Domain::Legal.glossary_for(
+            "The PARTY shall keep all Confidential Information secret.",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(
+            entries,
+            vec![
+                ("party", "当事者"),
+                ("confidential information", "秘密情報")
+            ]
+        );
+
+        assert!(Domain::Technical
+            .glossary_for("API framework", Language::English, Language::Japanese)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_format_glossary() {
+        assert_eq!(
+            format_glossary(&[("甲", "Party A"), ("乙", "Party B")]),
+            "甲 → Party A, 乙 → Party B"
+        );
+        assert_eq!(format_glossary(&[]), "");
+    }
+
+    #[test]
+    fn test_apply_sampling() {
+        let temperature = |domain: Domain| {
+            let mut options = serde_json::json!({ "temperature": 0.2, "top_p": 0.9 });
+            domain.apply_sampling(&mut options);
+            assert_eq!(options["top_p"], 0.9);
+            options["temperature"].as_f64().unwrap()
+        };
+
+        assert_eq!(temperature(Domain::Technical), 0.2);
+        assert_eq!(temperature(Domain::Legal), 0.1);
+        assert!((temperature(Domain::Marketing) - 0.5).abs() < 1e-9);
+        assert!((temperature(Domain::Chat) - 0.4).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,314 @@
+//! 単語・慣用句の解説サービス
+//!
+//! 選択テキストが単語や短いフレーズの場合、全文翻訳よりも辞書形式の解説が役に立つ。
+//! 読み（日本語の場合はふりがな・ローマ字）・品詞・意味・例文・ニュアンスを
+//! JSON形式の出力で生成し、構造化データとして返す
+
+use crate::services::translation::{parse_json_object, Language, TranslationError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// 短い入力とみなす最大文字数
+pub const MAX_EXPLAIN_CHARS: usize = 30;
+
+/// 短い入力とみなす最大単語数（空白区切りの言語）
+pub const MAX_EXPLAIN_WORDS: usize = 4;
+
+/// 読み（日本語の語句のみ）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reading {
+    /// ふりがな（ひらがな）
+    pub furigana: String,
+    /// ローマ字（ヘボン式）
+    pub romaji: String,
+}
+
+/// 例文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExampleSentence {
+    /// 語句の言語での例文
+    pub sentence: String,
+    /// 例文の訳
+    pub translation: String,
+}
+
+/// 単語・慣用句の解説
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Explanation {
+    /// 解説した語句
+    pub term: String,
+    /// 読み（日本語の語句のみ）
+    pub reading: Option<Reading>,
+    /// 品詞
+    pub part_of_speech: String,
+    /// 意味（解説言語）
+    pub meanings: Vec<String>,
+    /// 例文
+    pub examples: Vec<ExampleSentence>,
+    /// ニュアンス・使い分けの補足
+    pub nuance: Option<String>,
+    /// 語句の言語
+    pub source_lang: Language,
+    /// 解説の言語
+    pub target_lang: Language,
+    /// 処理時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// モデルが出力する解説（構造化出力）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StructuredExplanation {
+    #[serde(default)]
+    furigana: String,
+    #[serde(default)]
+    romaji: String,
+    #[serde(default)]
+    part_of_speech: String,
+    #[serde(default)]
+    meanings: Vec<String>,
+    #[serde(default)]
+    examples: Vec<ExampleSentence>,
+    #[serde(default)]
+    nuance: String,
+}
+
+/// 解説エラー
+#[derive(Debug, Error)]
+pub enum ExplainError {
+    #[error("解説できるのは単語・短いフレーズのみです（{MAX_EXPLAIN_CHARS}文字・{MAX_EXPLAIN_WORDS}単語以内）")]
+    InputTooLong,
+    #[error("解説結果を解釈できませんでした")]
+    InvalidResponse,
+    #[error("{0}")]
+    TranslationFailed(#[from] TranslationError),
+}
+
+impl Serialize for ExplainError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 単語・短いフレーズかどうかを判定する
+///
+/// 1行で、文字数と単語数が上限以内であり、文末の句点で終わらないものを短い入力とみなす
+pub fn is_short_input(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && !text.contains('\n')
+        && text.chars().count() <= MAX_EXPLAIN_CHARS
+        && text.split_whitespace().count() <= MAX_EXPLAIN_WORDS
+        && !text.ends_with(['。', '.'])
+}
+
+/// 解説用のシステムプロンプト（解説言語で記述）
+pub fn system_prompt(target_lang: Language) -> &'static str {
+    match target_lang {
+        Language::Japanese => {
+            "あなたは辞書編集の専門家です。必ず日本語で、指定されたJSON形式でのみ出力してください。"
+        }
+        Language::English => {
+            "You are an expert lexicographer. You MUST respond in English and output only the requested JSON."
+        }
+    }
+}
+
+/// 解説用のプロンプトを構築
+///
+/// 語句が日本語の場合はふりがな・ローマ字も出力させる
+pub fn build_explain_prompt(term: &str, source_lang: Language, target_lang: Language) -> String {
+    let term = term.trim();
+    match (source_lang, target_lang) {
+        (Language::Japanese, Language::Japanese) => format!(
+            "次の日本語の語句を辞書のように解説してください。\n\
+             JSONで出力し、\"furigana\"にひらがなの読み、\"romaji\"にヘボン式ローマ字、\"partOfSpeech\"に品詞、\"meanings\"に意味の一覧、\"examples\"に{{\"sentence\": 日本語の例文, \"translation\": 例文の英訳}}を2つ、\"nuance\"にニュアンスや使い分けの補足を入れてください。\n\n語句: {}",
+            term
+        ),
+        (Language::English, Language::Japanese) => format!(
+            "次の英語の語句を辞書のように日本語で解説してください。\n\
+             JSONで出力し、\"partOfSpeech\"に品詞、\"meanings\"に日本語での意味の一覧、\"examples\"に{{\"sentence\": 英語の例文, \"translation\": 例文の日本語訳}}を2つ、\"nuance\"にニュアンスや使い分けの補足を入れてください。\n\n語句: {}",
+            term
+        ),
+        (Language::Japanese, Language::English) => format!(
+            "Explain the following Japanese term in English like a dictionary entry.\n\
+             Output JSON with the hiragana reading in \"furigana\", the Hepburn romanization in \"romaji\", the part of speech in \"partOfSpeech\", the list of English meanings in \"meanings\", two {{\"sentence\": Japanese example sentence, \"translation\": English translation}} in \"examples\", and notes on nuance and usage in \"nuance\".\n\nTerm: {}",
+            term
+        ),
+        (Language::English, Language::English) => format!(
+            "Explain the following English term like a dictionary entry.\n\
+             Output JSON with the part of speech in \"partOfSpeech\", the list of meanings in \"meanings\", two {{\"sentence\": example sentence, \"translation\": Japanese translation}} in \"examples\", and notes on nuance and usage in \"nuance\".\n\nTerm: {}",
+            term
+        ),
+    }
+}
+
+/// 解説の構造化出力スキーマ（Ollamaの`format`パラメータ）
+pub fn explanation_schema(source_lang: Language) -> serde_json::Value {
+    let mut schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "partOfSpeech": { "type": "string" },
+            "meanings": { "type": "array", "items": { "type": "string" } },
+            "examples": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "sentence": { "type": "string" },
+                        "translation": { "type": "string" }
+                    },
+                    "required": ["sentence", "translation"]
+                }
+            },
+            "nuance": { "type": "string" }
+        },
+        "required": ["partOfSpeech", "meanings", "examples", "nuance"]
+    });
+
+    if source_lang == Language::Japanese {
+        schema["properties"]["furigana"] = serde_json::json!({ "type": "string" });
+        schema["properties"]["romaji"] = serde_json::json!({ "type": "string" });
+        schema["required"] = serde_json::json!([
+            "furigana",
+            "romaji",
+            "partOfSpeech",
+            "meanings",
+            "examples",
+            "nuance"
+        ]);
+    }
+
+    schema
+}
+
+/// モデルの出力から解説を組み立てる
+///
+/// JSONとして解釈できない場合や意味が含まれない場合はエラー
+pub fn build_explanation(
+    content: &str,
+    term: &str,
+    source_lang: Language,
+    target_lang: Language,
+    duration_ms: u64,
+) -> Result<Explanation, ExplainError> {
+    let parsed: StructuredExplanation =
+        parse_json_object(content).ok_or(ExplainError::InvalidResponse)?;
+
+    let meanings: Vec<String> = parsed
+        .meanings
+        .into_iter()
+        .map(|meaning| meaning.trim().to_string())
+        .filter(|meaning| !meaning.is_empty())
+        .collect();
+    if meanings.is_empty() {
+        return Err(ExplainError::InvalidResponse);
+    }
+
+    let reading =
+        (source_lang == Language::Japanese && !parsed.furigana.trim().is_empty()).then(|| {
+            Reading {
+                furigana: parsed.furigana.trim().to_string(),
+                romaji: parsed.romaji.trim().to_string(),
+            }
+        });
+    let nuance = parsed.nuance.trim();
+
+    Ok(Explanation {
+        term: term.trim().to_string(),
+        reading,
+        part_of_speech: parsed.part_of_speech.trim().to_string(),
+        meanings,
+        examples: parsed
+            .examples
+            .into_iter()
+            .filter(|example| !example.sentence.trim().is_empty())
+            .collect(),
+        nuance: (!nuance.is_empty()).then(|| nuance.to_string()),
+        source_lang,
+        target_lang,
+        duration_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_short_input() {
+        assert!(is_short_input("serendipity"));
+        assert!(is_short_input("break a leg"));
+        assert!(is_short_input("猫の手も借りたい"));
+        assert!(is_short_input("  お疲れ様  "));
+
+        assert!(!is_short_input(""));
+        assert!(!is_short_input("This is a complete sentence."));
+        assert!(!is_short_input("これは文です。"));
+        assert!(!is_short_input("one two three four five"));
+        assert!(!is_short_input("line one\nline two"));
+        assert!(!is_short_input(&"あ".repeat(MAX_EXPLAIN_CHARS + 1)));
+    }
+
+    #[test]
+    fn test_explanation_schema() {
+        let schema = explanation_schema(Language::Japanese);
+        assert!(schema["properties"]["furigana"].is_object());
+        assert_eq!(schema["required"].as_array().unwrap().len(), 6);
+
+        let schema = explanation_schema(Language::English);
+        assert!(schema["properties"]["furigana"].is_null());
+    }
+
+    #[test]
+    fn test_build_explanation() {
+        let content = r#"```json
+{"furigana": "ねこのてもかりたい", "romaji": "neko no te mo karitai", "partOfSpeech": "慣用句",
+ "meanings": ["Extremely busy", " "], "examples": [{"sentence": "年末は猫の手も借りたい。", "translation": "The year-end is so busy."}],
+ "nuance": "Used humorously."}
+```"#;
+
+        let explanation = build_explanation(
+            content,
+            "猫の手も借りたい",
+            Language::Japanese,
+            Language::English,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            explanation.reading,
+            Some(Reading {
+                furigana: "ねこのてもかりたい".to_string(),
+                romaji: "neko no te mo karitai".to_string(),
+            })
+        );
+        assert_eq!(explanation.meanings, vec!["Extremely busy"]);
+        assert_eq!(explanation.examples.len(), 1);
+        assert_eq!(explanation.nuance.as_deref(), Some("Used humorously."));
+    }
+
+    #[test]
+    fn test_build_explanation_invalid() {
+        assert!(matches!(
+            build_explanation("not json", "word", Language::English, Language::Japanese, 0),
+            Err(ExplainError::InvalidResponse)
+        ));
+        assert!(matches!(
+            build_explanation(
+                r#"{"partOfSpeech": "noun", "meanings": []}"#,
+                "word",
+                Language::English,
+                Language::Japanese,
+                0
+            ),
+            Err(ExplainError::InvalidResponse)
+        ));
+    }
+}
@@ -0,0 +1,1825 @@
+//! ローカライズファイル翻訳サービス
+//!
+//! JSON（i18nバンドル）・gettext .po・XLIFF 1.2/2.0を解析し、未翻訳または原文が変更された
+//! エントリのみを翻訳する。ICU・printf形式のプレースホルダーは翻訳対象から保護し、
+//! 機械翻訳したエントリは要レビュー（fuzzy・needs-review）として記録する
+
+use crate::services::rich_text;
+use crate::services::translation::{Language, TranslationError};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::Range;
+use std::path::Path;
+use thiserror::Error;
+
+/// 対応するローカライズファイル形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalizationFormat {
+    /// ネストしたJSONのi18nバンドル（en.json, ja.json）
+    Json,
+    /// gettextのカタログ（.po）
+    Po,
+    /// XLIFF 1.2/2.0（.xlf, .xliff）
+    Xliff,
+}
+
+impl LocalizationFormat {
+    /// ファイルの拡張子から形式を判定
+    pub fn from_path(path: &Path) -> Result<Self, LocalizationError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "json" => Ok(LocalizationFormat::Json),
+            "po" => Ok(LocalizationFormat::Po),
+            "xlf" | "xliff" => Ok(LocalizationFormat::Xliff),
+            _ => Err(LocalizationError::UnsupportedFormat(extension)),
+        }
+    }
+}
+
+/// ローカライズファイル翻訳結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizationResult {
+    /// 更新したファイルパス
+    pub path: String,
+    /// ファイル形式
+    pub format: LocalizationFormat,
+    /// エントリの総数
+    pub total_entries: usize,
+    /// 機械翻訳したエントリ（要レビュー）
+    pub translated_ids: Vec<String>,
+    /// プレースホルダーが一致しなかったため翻訳を見送ったエントリ
+    pub failed_ids: Vec<String>,
+    /// 翻訳元言語
+    pub source_lang: Language,
+    /// 翻訳先言語
+    pub target_lang: Language,
+    /// 翻訳にかかった時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// ローカライズファイル翻訳エラー
+#[derive(Debug, Error)]
+pub enum LocalizationError {
+    #[error("対応していないファイル形式です: {0}")]
+    UnsupportedFormat(String),
+    #[error("ファイルの読み込みに失敗しました: {0}")]
+    ReadFailed(String),
+    #[error("ファイルの書き込みに失敗しました: {0}")]
+    WriteFailed(String),
+    #[error("ファイルの解析に失敗しました: {0}")]
+    ParseFailed(String),
+    #[error("JSONの翻訳には翻訳元ファイルの指定が必要です")]
+    SourceRequired,
+    #[error("{0}")]
+    TranslationFailed(#[from] TranslationError),
+}
+
+impl Serialize for LocalizationError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 翻訳後のファイル内容と翻訳したエントリ
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalizedContent {
+    content: String,
+    total_entries: usize,
+    translated_ids: Vec<String>,
+    failed_ids: Vec<String>,
+}
+
+// ============================================================================
+// プレースホルダーの保護
+// ============================================================================
+
+/// メッセージの構成要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MessagePart {
+    /// 翻訳対象のテキスト
+    Text(String),
+    /// そのまま残す部分（出力形式でエスケープ済み）
+    Placeholder(String),
+}
+
+fn push_message_text(parts: &mut Vec<MessagePart>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(MessagePart::Text(last)) = parts.last_mut() {
+        last.push_str(text);
+    } else {
+        parts.push(MessagePart::Text(text.to_string()));
+    }
+}
+
+fn push_placeholder(parts: &mut Vec<MessagePart>, raw: &str) {
+    if raw.is_empty() {
+        return;
+    }
+    if let Some(MessagePart::Placeholder(last)) = parts.last_mut() {
+        last.push_str(raw);
+    } else {
+        parts.push(MessagePart::Placeholder(raw.to_string()));
+    }
+}
+
+/// `{`から対応する`}`までの長さを返す
+fn matching_brace_len(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// printf形式のプレースホルダー（`%s`, `%1$d`, `%(name)s`, `%{name}`, `%%`）の長さを返す
+fn printf_len(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut i = 1;
+
+    match bytes.get(i)? {
+        b'%' => return Some(2),
+        b'{' => return s.find('}').map(|end| end + 1),
+        b'(' => i = s.find(')')? + 1,
+        _ => {
+            // 位置指定（%1$s）
+            let digits = bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if digits > 0 && bytes.get(i + digits) == Some(&b'$') {
+                i += digits + 1;
+            }
+        }
+    }
+
+    // フラグ・幅・精度・長さ修飾子（「50% off」を誤検出しないよう空白フラグは扱わない）
+    while matches!(bytes.get(i), Some(b'-' | b'+' | b'0' | b'#' | b'\'')) {
+        i += 1;
+    }
+    while matches!(bytes.get(i), Some(b) if b.is_ascii_digit() || *b == b'*') {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        while matches!(bytes.get(i), Some(b) if b.is_ascii_digit() || *b == b'*') {
+            i += 1;
+        }
+    }
+    while matches!(
+        bytes.get(i),
+        Some(b'h' | b'l' | b'L' | b'q' | b'j' | b'z' | b't')
+    ) {
+        i += 1;
+    }
+
+    match bytes.get(i)? {
+        b'd' | b'i' | b'o' | b'u' | b'x' | b'X' | b'e' | b'E' | b'f' | b'F' | b'g' | b'G'
+        | b'a' | b'A' | b'c' | b's' | b'p' | b'@' => Some(i + 1),
+        _ => None,
+    }
+}
+
+/// ICUの引数（`{name}`, `{count, plural, one {...} other {...}}`）を分割
+///
+/// plural・selectの各選択肢のメッセージは翻訳対象とし、構文部分のみを保護する
+fn parse_icu_argument(argument: &str, parts: &mut Vec<MessagePart>) {
+    let inner = &argument[1..argument.len() - 1];
+    let mut fields = inner.splitn(3, ',');
+    let _name = fields.next();
+    let kind = fields.next().map(str::trim);
+
+    match (kind, fields.next()) {
+        (Some(kind @ ("plural" | "select" | "selectordinal")), Some(options)) => {
+            let header_len = 1 + inner.len() - options.len();
+            push_placeholder(parts, &argument[..header_len]);
+            parse_icu_options(options, kind != "select", parts);
+            push_placeholder(parts, "}");
+        }
+        _ => push_placeholder(parts, argument),
+    }
+}
+
+/// ICUの選択肢（`one {...} other {...}`）を分割
+fn parse_icu_options(options: &str, plural: bool, parts: &mut Vec<MessagePart>) {
+    let mut rest = options;
+
+    while let Some(brace) = rest.find('{') {
+        push_placeholder(parts, &rest[..=brace]);
+        let Some(len) = matching_brace_len(&rest[brace..]) else {
+            push_placeholder(parts, &rest[brace + 1..]);
+            return;
+        };
+        parse_message(&rest[brace + 1..brace + len - 1], plural, parts);
+        push_placeholder(parts, "}");
+        rest = &rest[brace + len..];
+    }
+
+    push_placeholder(parts, rest);
+}
+
+/// メッセージをテキストとプレースホルダーに分割
+///
+/// ICU引数、printf形式、HTMLタグをプレースホルダーとして扱う。
+/// pluralの選択肢内では`#`（数値）もプレースホルダーとする
+fn parse_message(text: &str, in_plural: bool, parts: &mut Vec<MessagePart>) {
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let rest = &text[pos..];
+        let c = rest.chars().next().unwrap_or_default();
+
+        let placeholder_len = match c {
+            '{' => matching_brace_len(rest),
+            '%' => printf_len(rest),
+            '<' => rich_text::markup_len(rest),
+            '#' if in_plural => Some(1),
+            _ => None,
+        };
+
+        match placeholder_len {
+            Some(len) => {
+                push_message_text(parts, &text[text_start..pos]);
+                if c == '{' {
+                    parse_icu_argument(&rest[..len], parts);
+                } else {
+                    push_placeholder(parts, &rest[..len]);
+                }
+                pos += len;
+                text_start = pos;
+            }
+            None => pos += c.len_utf8(),
+        }
+    }
+
+    push_message_text(parts, &text[text_start..]);
+}
+
+/// テキスト中のICU引数・printf形式のプレースホルダーを出現順に列挙する（品質チェック用）
+pub(crate) fn find_placeholders(text: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let rest = &text[pos..];
+        let c = rest.chars().next().unwrap_or_default();
+
+        let len = match c {
+            '{' => matching_brace_len(rest),
+            '%' => printf_len(rest),
+            _ => None,
+        };
+
+        match len {
+            Some(len) => {
+                placeholders.push(rest[..len].to_string());
+                pos += len;
+            }
+            None => pos += c.len_utf8(),
+        }
+    }
+
+    placeholders
+}
+
+/// プレースホルダーを番号付きトークンに置き換えたメッセージ
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MaskedMessage {
+    /// 翻訳に渡すテキスト（例: `You have ⟪0⟫ messages`）
+    text: String,
+    /// トークンに対応するプレースホルダー
+    placeholders: Vec<String>,
+    /// 翻訳が必要なテキストを含むか
+    translatable: bool,
+}
+
+impl MaskedMessage {
+    fn new(parts: &[MessagePart]) -> Self {
+        let mut text = String::new();
+        let mut placeholders = Vec::new();
+        let mut translatable = false;
+
+        for part in parts {
+            match part {
+                MessagePart::Text(t) => {
+                    translatable |= t.chars().any(char::is_alphabetic);
+                    text.push_str(t);
+                }
+                MessagePart::Placeholder(p) => {
+                    text.push_str(&format!("⟪{}⟫", placeholders.len()));
+                    placeholders.push(p.clone());
+                }
+            }
+        }
+
+        Self {
+            text,
+            placeholders,
+            translatable,
+        }
+    }
+
+    /// プレーンテキストのメッセージから作成
+    fn from_text(text: &str) -> Self {
+        let mut parts = Vec::new();
+        parse_message(text, false, &mut parts);
+        Self::new(&parts)
+    }
+
+    /// 翻訳結果のトークンをプレースホルダーに戻す
+    ///
+    /// トークンの欠落・重複がある場合はNone
+    fn unmask(&self, translated: &str, escape: fn(&str) -> String) -> Option<String> {
+        let mut output = String::new();
+        let mut used = vec![0usize; self.placeholders.len()];
+        let mut rest = translated;
+
+        while let Some(start) = rest.find('⟪') {
+            output.push_str(&escape(&rest[..start]));
+            let token = &rest[start + '⟪'.len_utf8()..];
+            let end = token.find('⟫')?;
+            let index: usize = token[..end].trim().parse().ok()?;
+            output.push_str(self.placeholders.get(index)?);
+            *used.get_mut(index)? += 1;
+            rest = &token[end + '⟫'.len_utf8()..];
+        }
+        output.push_str(&escape(rest));
+
+        used.iter().all(|&count| count == 1).then_some(output)
+    }
+}
+
+/// エスケープしない（JSON・.poは書き出し時にエスケープする）
+fn no_escape(text: &str) -> String {
+    text.to_string()
+}
+
+/// XMLのテキストとしてエスケープ
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// メッセージをまとめて翻訳し、プレースホルダーを復元する
+///
+/// 翻訳が不要なメッセージはそのまま、プレースホルダーが一致しない翻訳はNoneを返す
+async fn translate_messages<F, Fut>(
+    messages: &[MaskedMessage],
+    escape: fn(&str) -> String,
+    translate: F,
+) -> Result<Vec<Option<String>>, TranslationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    let segments: Vec<String> = messages
+        .iter()
+        .filter(|m| m.translatable)
+        .map(|m| m.text.clone())
+        .collect();
+    let translated =
+        rich_text::translate_segments(&segments, rich_text::DEFAULT_BATCH_CHARS, translate).await?;
+    let mut translated = translated.into_iter();
+
+    Ok(messages
+        .iter()
+        .map(|message| {
+            let text = if message.translatable {
+                translated.next().unwrap_or_default()
+            } else {
+                message.text.clone()
+            };
+            message.unmask(&text, escape)
+        })
+        .collect())
+}
+
+// ============================================================================
+// JSON
+// ============================================================================
+
+/// キー順を保持するJSONの値
+///
+/// バンドルを翻訳元のキー順で書き出すため、オブジェクトを`IndexMap`で保持する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(IndexMap<String, JsonValue>),
+}
+
+impl JsonValue {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn as_object(&self) -> Option<&IndexMap<String, JsonValue>> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+/// パスをJSON Pointer（RFC 6901）に変換する（例: `["app", "a/b"]` → `/app/a~1b`）
+///
+/// キーに`.`や`/`を含む場合も、ネストしたキーと区別できるようにエスケープする
+fn json_pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|key| format!("/{}", key.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// JSONのパス上の値を取得（オブジェクトのキーまたは配列の添字）
+fn json_lookup<'a>(value: &'a JsonValue, path: &[String]) -> Option<&'a JsonValue> {
+    path.iter().try_fold(value, |current, key| match current {
+        JsonValue::Object(map) => map.get(key),
+        JsonValue::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// 文字列の値をパスとともに列挙
+fn collect_json_strings(
+    value: &JsonValue,
+    path: &mut Vec<String>,
+    out: &mut Vec<(Vec<String>, String)>,
+) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map {
+                path.push(key.clone());
+                collect_json_strings(child, path, out);
+                path.pop();
+            }
+        }
+        JsonValue::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                path.push(i.to_string());
+                collect_json_strings(child, path, out);
+                path.pop();
+            }
+        }
+        JsonValue::String(text) => out.push((path.clone(), text.clone())),
+        _ => {}
+    }
+}
+
+/// 翻訳元の構造とキー順に合わせて翻訳先を再構築する
+///
+/// 翻訳元にないキーは翻訳先の末尾に残す
+fn merge_json(
+    source: &JsonValue,
+    target: Option<&JsonValue>,
+    path: &mut Vec<String>,
+    translations: &HashMap<Vec<String>, String>,
+) -> Option<JsonValue> {
+    match source {
+        JsonValue::Object(map) => {
+            let target_map = target.and_then(JsonValue::as_object);
+            let mut result = IndexMap::new();
+
+            for (key, child) in map {
+                path.push(key.clone());
+                let target_child = target_map.and_then(|t| t.get(key));
+                if let Some(merged) = merge_json(child, target_child, path, translations) {
+                    result.insert(key.clone(), merged);
+                }
+                path.pop();
+            }
+
+            for (key, value) in target_map.into_iter().flatten() {
+                if !result.contains_key(key) {
+                    result.insert(key.clone(), value.clone());
+                }
+            }
+
+            Some(JsonValue::Object(result))
+        }
+        JsonValue::Array(items) => {
+            let target_items = target.and_then(JsonValue::as_array);
+            let merged = items
+                .iter()
+                .enumerate()
+                .map(|(i, child)| {
+                    path.push(i.to_string());
+                    let target_child = target_items.and_then(|t| t.get(i));
+                    let merged = merge_json(child, target_child, path, translations)
+                        .unwrap_or_else(|| child.clone());
+                    path.pop();
+                    merged
+                })
+                .collect();
+            Some(JsonValue::Array(merged))
+        }
+        JsonValue::String(_) => translations
+            .get(path)
+            .map(|text| JsonValue::String(text.clone()))
+            .or_else(|| target.cloned()),
+        _ => Some(target.cloned().unwrap_or_else(|| source.clone())),
+    }
+}
+
+/// ファイルのインデントを検出（デフォルトは2スペース）
+fn detect_indent(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            line.chars()
+                .take_while(|&c| c == ' ' || c == '\t')
+                .collect::<String>()
+        })
+        .find(|indent| !indent.is_empty())
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+/// JSONを指定したインデントで文字列化
+fn to_json_string(value: &JsonValue, indent: &str) -> Result<String, LocalizationError> {
+    let mut buffer = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    value
+        .serialize(&mut serializer)
+        .map_err(|e| LocalizationError::ParseFailed(e.to_string()))?;
+
+    let mut output =
+        String::from_utf8(buffer).map_err(|e| LocalizationError::ParseFailed(e.to_string()))?;
+    output.push('\n');
+    Ok(output)
+}
+
+/// JSONのi18nバンドルを翻訳する
+///
+/// 翻訳先にないキー、空の値、前回の翻訳元（`previous_source`）から原文が変わったキーのみを翻訳する
+async fn translate_json<F, Fut>(
+    source: &str,
+    target: Option<&str>,
+    previous_source: Option<&str>,
+    translate: F,
+) -> Result<LocalizedContent, LocalizationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    let parse = |content: &str| {
+        serde_json::from_str::<JsonValue>(content)
+            .map_err(|e| LocalizationError::ParseFailed(e.to_string()))
+    };
+    let source_value = parse(source)?;
+    let target_value = target.map(parse).transpose()?;
+    let previous_value = previous_source.map(parse).transpose()?;
+
+    let mut entries = Vec::new();
+    collect_json_strings(&source_value, &mut Vec::new(), &mut entries);
+    let total_entries = entries.len();
+
+    let pending: Vec<(Vec<String>, String)> = entries
+        .into_iter()
+        .filter(|(path, text)| {
+            let current = target_value
+                .as_ref()
+                .and_then(|t| json_lookup(t, path))
+                .and_then(JsonValue::as_str);
+            let previous = previous_value
+                .as_ref()
+                .and_then(|p| json_lookup(p, path))
+                .and_then(JsonValue::as_str);
+
+            match current {
+                None => true,
+                Some(current) if current.trim().is_empty() => true,
+                Some(_) => previous.is_some_and(|previous| previous != text),
+            }
+        })
+        .collect();
+
+    let messages: Vec<MaskedMessage> = pending
+        .iter()
+        .map(|(_, text)| MaskedMessage::from_text(text))
+        .collect();
+    let results = translate_messages(&messages, no_escape, translate).await?;
+
+    let mut translations = HashMap::new();
+    let mut translated_ids = Vec::new();
+    let mut failed_ids = Vec::new();
+    for ((path, _), result) in pending.into_iter().zip(results) {
+        let id = json_pointer(&path);
+        match result {
+            Some(text) => {
+                translated_ids.push(id);
+                translations.insert(path, text);
+            }
+            None => failed_ids.push(id),
+        }
+    }
+
+    let merged = merge_json(
+        &source_value,
+        target_value.as_ref(),
+        &mut Vec::new(),
+        &translations,
+    )
+    .unwrap_or(JsonValue::Null);
+
+    Ok(LocalizedContent {
+        content: to_json_string(&merged, &detect_indent(target.unwrap_or(source)))?,
+        total_entries,
+        translated_ids,
+        failed_ids,
+    })
+}
+
+// ============================================================================
+// gettext .po
+// ============================================================================
+
+/// .poのエントリ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct PoEntry {
+    /// 翻訳者コメント・抽出コメント・参照（`#`, `#.`, `#:`）
+    comments: Vec<String>,
+    /// フラグ（`#, fuzzy, c-format`）
+    flags: Vec<String>,
+    /// 変更前の原文（`#| msgid`）
+    previous: Vec<String>,
+    msgctxt: Option<String>,
+    msgid: String,
+    msgid_plural: Option<String>,
+    msgstr: Vec<String>,
+}
+
+impl PoEntry {
+    fn id(&self) -> String {
+        match &self.msgctxt {
+            Some(context) => format!("[{}] {}", context, self.msgid),
+            None => self.msgid.clone(),
+        }
+    }
+
+    fn is_fuzzy(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "fuzzy")
+    }
+
+    /// 未翻訳、またはmsgmergeで原文の変更が検出されたエントリか
+    fn needs_translation(&self) -> bool {
+        self.msgstr.iter().all(|s| s.is_empty()) || (self.is_fuzzy() && !self.previous.is_empty())
+    }
+}
+
+/// .poの構成要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PoBlock {
+    /// 空行・廃止エントリなどそのまま残す部分
+    Raw(String),
+    /// エントリ（元のテキストと解析結果）
+    Entry { raw: String, entry: PoEntry },
+}
+
+/// .poの文字列リテラルをデコード
+fn unquote_po(literal: &str) -> String {
+    let inner = literal
+        .trim()
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or_default();
+
+    let mut output = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('r') => output.push('\r'),
+            Some(other) => output.push(other),
+            None => {}
+        }
+    }
+    output
+}
+
+/// .poの文字列リテラルとしてエスケープ
+fn quote_po(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r");
+    format!("\"{}\"", escaped)
+}
+
+/// エントリのブロックを解析する（msgidがない、または廃止エントリの場合はNone）
+fn parse_po_entry(block: &str) -> Option<PoEntry> {
+    /// 継続行の追加先
+    enum Field {
+        Context,
+        Id,
+        Plural,
+        Str(usize),
+    }
+
+    let mut entry = PoEntry::default();
+    let mut field: Option<Field> = None;
+    let mut has_msgid = false;
+
+    for line in block.lines() {
+        let line = line.trim_end();
+
+        if line.starts_with("#~") {
+            return None;
+        } else if let Some(flags) = line.strip_prefix("#,") {
+            entry.flags.extend(
+                flags
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .map(str::to_string),
+            );
+        } else if line.starts_with("#|") {
+            entry.previous.push(line.to_string());
+        } else if line.starts_with('#') {
+            entry.comments.push(line.to_string());
+        } else if line.starts_with('"') {
+            let value = unquote_po(line);
+            match field {
+                Some(Field::Context) => entry
+                    .msgctxt
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Id) => entry.msgid.push_str(&value),
+                Some(Field::Plural) => entry
+                    .msgid_plural
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Str(i)) => entry.msgstr[i].push_str(&value),
+                None => {}
+            }
+        } else if let Some((keyword, literal)) = line.split_once(' ') {
+            let value = unquote_po(literal);
+            field = match keyword {
+                "msgctxt" => {
+                    entry.msgctxt = Some(value);
+                    Some(Field::Context)
+                }
+                "msgid" => {
+                    has_msgid = true;
+                    entry.msgid = value;
+                    Some(Field::Id)
+                }
+                "msgid_plural" => {
+                    entry.msgid_plural = Some(value);
+                    Some(Field::Plural)
+                }
+                "msgstr" => {
+                    entry.msgstr = vec![value];
+                    Some(Field::Str(0))
+                }
+                _ => {
+                    let index = keyword
+                        .strip_prefix("msgstr[")
+                        .and_then(|s| s.strip_suffix(']'))
+                        .and_then(|s| s.parse::<usize>().ok())?;
+                    if entry.msgstr.len() <= index {
+                        entry.msgstr.resize(index + 1, String::new());
+                    }
+                    entry.msgstr[index] = value;
+                    Some(Field::Str(index))
+                }
+            };
+        }
+    }
+
+    has_msgid.then_some(entry)
+}
+
+/// .poを空行区切りのブロックに分割して解析
+fn parse_po(content: &str) -> Vec<PoBlock> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+
+    let flush = |current: &mut String, blocks: &mut Vec<PoBlock>| {
+        if current.is_empty() {
+            return;
+        }
+        let raw = std::mem::take(current);
+        match parse_po_entry(&raw) {
+            Some(entry) => blocks.push(PoBlock::Entry { raw, entry }),
+            None => blocks.push(PoBlock::Raw(raw)),
+        }
+    };
+
+    for line in content.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            flush(&mut current, &mut blocks);
+            blocks.push(PoBlock::Raw(line.to_string()));
+        } else {
+            current.push_str(line);
+        }
+    }
+    flush(&mut current, &mut blocks);
+
+    blocks
+}
+
+/// ヘッダーのPlural-Formsから複数形の数を取得（デフォルトは2）
+fn po_plural_count(blocks: &[PoBlock]) -> usize {
+    blocks
+        .iter()
+        .find_map(|block| match block {
+            PoBlock::Entry { entry, .. } if entry.msgid.is_empty() && entry.msgctxt.is_none() => {
+                entry.msgstr.first().cloned()
+            }
+            _ => None,
+        })
+        .and_then(|header| {
+            let start = header.find("nplurals=")? + "nplurals=".len();
+            let digits: String = header[start..]
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits.parse().ok()
+        })
+        .unwrap_or(2)
+}
+
+/// 文字列フィールドを書き出す（改行を含む場合は複数行形式）
+fn write_po_field(output: &mut String, keyword: &str, value: &str) {
+    let lines: Vec<&str> = value.split_inclusive('\n').collect();
+    if lines.len() > 1 {
+        output.push_str(&format!("{} \"\"\n", keyword));
+        for line in lines {
+            output.push_str(&quote_po(line));
+            output.push('\n');
+        }
+    } else {
+        output.push_str(&format!("{} {}\n", keyword, quote_po(value)));
+    }
+}
+
+/// エントリを書き出す
+fn write_po_entry(entry: &PoEntry) -> String {
+    let mut output = String::new();
+
+    for comment in &entry.comments {
+        output.push_str(comment);
+        output.push('\n');
+    }
+    if !entry.flags.is_empty() {
+        output.push_str(&format!("#, {}\n", entry.flags.join(", ")));
+    }
+    for previous in &entry.previous {
+        output.push_str(previous);
+        output.push('\n');
+    }
+    if let Some(context) = &entry.msgctxt {
+        write_po_field(&mut output, "msgctxt", context);
+    }
+    write_po_field(&mut output, "msgid", &entry.msgid);
+
+    match &entry.msgid_plural {
+        Some(plural) => {
+            write_po_field(&mut output, "msgid_plural", plural);
+            for (i, msgstr) in entry.msgstr.iter().enumerate() {
+                write_po_field(&mut output, &format!("msgstr[{}]", i), msgstr);
+            }
+        }
+        None => write_po_field(
+            &mut output,
+            "msgstr",
+            entry.msgstr.first().map(String::as_str).unwrap_or_default(),
+        ),
+    }
+
+    output
+}
+
+/// gettextの.poを翻訳する
+///
+/// 未翻訳のエントリと、msgmergeで原文の変更が検出された（`#|`付きのfuzzy）エントリのみを翻訳し、
+/// 翻訳したエントリにはfuzzyフラグを付ける
+async fn translate_po<F, Fut>(
+    content: &str,
+    translate: F,
+) -> Result<LocalizedContent, LocalizationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    let mut blocks = parse_po(content);
+    let plural_count = po_plural_count(&blocks).max(1);
+
+    // 翻訳が必要なエントリの位置と、翻訳するメッセージ（単数形・複数形）
+    let mut pending: Vec<usize> = Vec::new();
+    let mut messages: Vec<MaskedMessage> = Vec::new();
+    let mut total_entries = 0;
+
+    for (i, block) in blocks.iter().enumerate() {
+        let PoBlock::Entry { entry, .. } = block else {
+            continue;
+        };
+        if entry.msgid.is_empty() {
+            continue;
+        }
+        total_entries += 1;
+        if !entry.needs_translation() {
+            continue;
+        }
+
+        pending.push(i);
+        messages.push(MaskedMessage::from_text(&entry.msgid));
+        if let Some(plural) = &entry.msgid_plural {
+            messages.push(MaskedMessage::from_text(plural));
+        }
+    }
+
+    let mut results = translate_messages(&messages, no_escape, translate)
+        .await?
+        .into_iter();
+
+    let mut translated_ids = Vec::new();
+    let mut failed_ids = Vec::new();
+
+    for i in pending {
+        let PoBlock::Entry { entry, .. } = &blocks[i] else {
+            continue;
+        };
+        let mut entry = entry.clone();
+
+        let singular = results.next().flatten();
+        let msgstr = if entry.msgid_plural.is_some() {
+            let plural = results.next().flatten();
+            singular.zip(plural).map(|(singular, plural)| {
+                if plural_count == 1 {
+                    vec![plural]
+                } else {
+                    std::iter::once(singular)
+                        .chain(std::iter::repeat_n(plural, plural_count - 1))
+                        .collect()
+                }
+            })
+        } else {
+            singular.map(|s| vec![s])
+        };
+
+        let Some(msgstr) = msgstr else {
+            failed_ids.push(entry.id());
+            continue;
+        };
+
+        entry.msgstr = msgstr;
+        entry.previous.clear();
+        if !entry.is_fuzzy() {
+            entry.flags.insert(0, "fuzzy".to_string());
+        }
+        translated_ids.push(entry.id());
+        blocks[i] = PoBlock::Entry {
+            raw: write_po_entry(&entry),
+            entry,
+        };
+    }
+
+    let content = blocks
+        .iter()
+        .map(|block| match block {
+            PoBlock::Raw(raw) | PoBlock::Entry { raw, .. } => raw.as_str(),
+        })
+        .collect();
+
+    Ok(LocalizedContent {
+        content,
+        total_entries,
+        translated_ids,
+        failed_ids,
+    })
+}
+
+// ============================================================================
+// XLIFF
+// ============================================================================
+
+/// 開始タグを検索し、(開始位置, 終了位置)を返す
+fn find_start_tag(content: &str, name: &str, from: usize) -> Option<Range<usize>> {
+    let pattern = format!("<{}", name);
+    let mut search = from;
+
+    while let Some(offset) = content[search..].find(&pattern) {
+        let start = search + offset;
+        let after = content[start + pattern.len()..].chars().next();
+        if matches!(after, Some(c) if c.is_whitespace() || c == '>' || c == '/') {
+            let len = rich_text::markup_len(&content[start..])?;
+            return Some(start..start + len);
+        }
+        search = start + pattern.len();
+    }
+
+    None
+}
+
+/// 属性値の範囲を取得
+fn attribute_range(tag: &str, name: &str) -> Option<Range<usize>> {
+    let pattern = format!("{}=", name);
+    let mut search = 0;
+
+    while let Some(offset) = tag[search..].find(&pattern) {
+        let start = search + offset;
+        let preceded_by_space = tag[..start].ends_with(char::is_whitespace);
+        let quote = tag[start + pattern.len()..].chars().next();
+
+        if let (true, Some(quote @ ('"' | '\''))) = (preceded_by_space, quote) {
+            let value_start = start + pattern.len() + 1;
+            let value_end = value_start + tag[value_start..].find(quote)?;
+            return Some(value_start..value_end);
+        }
+        search = start + pattern.len();
+    }
+
+    None
+}
+
+/// 属性値を取得
+fn get_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    attribute_range(tag, name).map(|range| &tag[range])
+}
+
+/// 属性値を設定（存在しない場合は追加）
+fn set_attribute(tag: &str, name: &str, value: &str) -> String {
+    if let Some(range) = attribute_range(tag, name) {
+        let mut output = tag.to_string();
+        output.replace_range(range, value);
+        return output;
+    }
+
+    let end = if tag.ends_with("/>") {
+        tag.len() - 2
+    } else {
+        tag.len() - 1
+    };
+    let head = tag[..end].trim_end();
+    format!("{} {}=\"{}\"{}", head, name, value, &tag[end..])
+}
+
+/// XLIFFの翻訳単位（1.2のtrans-unit、2.0のsegment）
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct XliffUnit {
+    /// 要素全体の範囲
+    range: Range<usize>,
+    id: String,
+    /// 以下は要素内の相対位置
+    start_tag: Range<usize>,
+    source: Range<usize>,
+    source_content: Range<usize>,
+    /// targetの(開始タグ, 要素全体)。存在しない場合はNone
+    target: Option<(Range<usize>, Range<usize>)>,
+    target_content: Option<Range<usize>>,
+    translate: bool,
+}
+
+/// 翻訳単位を列挙する
+fn parse_xliff_units(content: &str, version2: bool) -> Vec<XliffUnit> {
+    let unit_name = if version2 { "segment" } else { "trans-unit" };
+    let close = format!("</{}>", unit_name);
+    let mut units = Vec::new();
+    let mut search = 0;
+
+    while let Some(start_tag) = find_start_tag(content, unit_name, search) {
+        let start = start_tag.start;
+        let Some(end) = content[start_tag.end..]
+            .find(&close)
+            .map(|offset| start_tag.end + offset + close.len())
+        else {
+            break;
+        };
+        search = end;
+        let element = &content[start..end];
+        let tag = &content[start_tag.clone()];
+
+        // 2.0ではidとtranslate属性は親のunit要素にある
+        let unit_tag = if version2 {
+            content[..start]
+                .rfind("<unit")
+                .and_then(|pos| find_start_tag(content, "unit", pos))
+                .map(|range| &content[range])
+                .unwrap_or_default()
+        } else {
+            tag
+        };
+
+        let mut id = get_attribute(unit_tag, "id")
+            .unwrap_or_default()
+            .to_string();
+        if version2 {
+            if let Some(segment_id) = get_attribute(tag, "id") {
+                id = format!("{}/{}", id, segment_id);
+            }
+        }
+
+        let Some(source_tag) = find_start_tag(element, "source", 0) else {
+            continue;
+        };
+        let Some(source_end) = element[source_tag.end..].find("</source>") else {
+            continue;
+        };
+        let source_content = source_tag.end..source_tag.end + source_end;
+        let source = source_tag.start..source_content.end + "</source>".len();
+
+        let (target, target_content) = match find_start_tag(element, "target", source.end) {
+            Some(target_tag) if element[target_tag.clone()].ends_with("/>") => {
+                (Some((target_tag.clone(), target_tag)), None)
+            }
+            Some(target_tag) => match element[target_tag.end..].find("</target>") {
+                Some(offset) => {
+                    let content_range = target_tag.end..target_tag.end + offset;
+                    let full = target_tag.start..content_range.end + "</target>".len();
+                    (Some((target_tag, full)), Some(content_range))
+                }
+                None => (None, None),
+            },
+            None => (None, None),
+        };
+
+        units.push(XliffUnit {
+            range: start..end,
+            id,
+            start_tag: 0..start_tag.len(),
+            source,
+            source_content,
+            target,
+            target_content,
+            translate: get_attribute(unit_tag, "translate") != Some("no"),
+        });
+    }
+
+    units
+}
+
+/// 未翻訳、または翻訳が必要な状態の翻訳単位か
+fn xliff_needs_translation(element: &str, unit: &XliffUnit, version2: bool) -> bool {
+    if !unit.translate {
+        return false;
+    }
+
+    let target_empty = unit
+        .target_content
+        .as_ref()
+        .is_none_or(|range| element[range.clone()].trim().is_empty());
+    if target_empty {
+        return true;
+    }
+
+    if version2 {
+        get_attribute(&element[unit.start_tag.clone()], "state") == Some("initial")
+    } else {
+        unit.target.as_ref().is_some_and(|(tag, _)| {
+            matches!(
+                get_attribute(&element[tag.clone()], "state"),
+                Some("new" | "needs-translation")
+            )
+        })
+    }
+}
+
+/// XLIFFのsource要素の内容をメッセージに変換
+///
+/// インライン要素（`<g>`, `<x/>`, `<ph>`, `<pc>`など）はプレースホルダーとして保護する
+fn xliff_message(source: &str) -> MaskedMessage {
+    let mut parts = Vec::new();
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        if let Some(len) = rich_text::markup_len(rest) {
+            push_placeholder(&mut parts, &rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(i, c)| c == '<' && rich_text::markup_len(&rest[i..]).is_some())
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+
+        let mut text_parts = Vec::new();
+        parse_message(
+            &rich_text::decode_entities(&rest[..end]),
+            false,
+            &mut text_parts,
+        );
+        for part in text_parts {
+            match part {
+                MessagePart::Text(text) => push_message_text(&mut parts, &text),
+                MessagePart::Placeholder(raw) => push_placeholder(&mut parts, &escape_xml(&raw)),
+            }
+        }
+        rest = &rest[end..];
+    }
+
+    MaskedMessage::new(&parts)
+}
+
+/// 翻訳単位に翻訳結果を書き込んだ要素を作成
+fn write_xliff_unit(element: &str, unit: &XliffUnit, translated: &str, version2: bool) -> String {
+    let mut output = element.to_string();
+
+    let target_tag = |existing: Option<&str>| {
+        let tag = existing
+            .map(|tag| match tag.strip_suffix("/>") {
+                Some(head) => format!("{}>", head.trim_end()),
+                None => tag.to_string(),
+            })
+            .unwrap_or_else(|| "<target>".to_string());
+        if version2 {
+            tag
+        } else {
+            let tag = set_attribute(&tag, "state", "needs-review-translation");
+            set_attribute(&tag, "state-qualifier", "mt-suggestion")
+        }
+    };
+
+    match &unit.target {
+        Some((tag, full)) => {
+            let new_target = format!(
+                "{}{}</target>",
+                target_tag(Some(&element[tag.clone()])),
+                translated
+            );
+            output.replace_range(full.clone(), &new_target);
+        }
+        None => {
+            // sourceと同じインデントでtargetを追加
+            let indent_start = element[..unit.source.start]
+                .trim_end_matches([' ', '\t'])
+                .trim_end_matches(['\n', '\r'])
+                .len();
+            let indent = &element[indent_start..unit.source.start];
+            let new_target = format!("{}{}{}</target>", indent, target_tag(None), translated);
+            output.insert_str(unit.source.end, &new_target);
+        }
+    }
+
+    if version2 {
+        let tag = set_attribute(&element[unit.start_tag.clone()], "state", "translated");
+        output.replace_range(unit.start_tag.clone(), &tag);
+    }
+
+    output
+}
+
+/// XLIFF 1.2/2.0を翻訳する
+///
+/// targetがない、空、または未翻訳の状態の翻訳単位のみを翻訳する。翻訳したtargetは
+/// 1.2では`state="needs-review-translation"`、2.0では`state="translated"`（未レビュー）とする
+async fn translate_xliff<F, Fut>(
+    content: &str,
+    translate: F,
+) -> Result<LocalizedContent, LocalizationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    let version2 = content.contains("urn:oasis:names:tc:xliff:document:2.0");
+    let units = parse_xliff_units(content, version2);
+
+    let pending: Vec<&XliffUnit> = units
+        .iter()
+        .filter(|unit| xliff_needs_translation(&content[unit.range.clone()], unit, version2))
+        .collect();
+    let messages: Vec<MaskedMessage> = pending
+        .iter()
+        .map(|unit| {
+            let element = &content[unit.range.clone()];
+            xliff_message(&element[unit.source_content.clone()])
+        })
+        .collect();
+    let results = translate_messages(&messages, escape_xml, translate).await?;
+
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    let mut translated_ids = Vec::new();
+    let mut failed_ids = Vec::new();
+
+    for (unit, result) in pending.into_iter().zip(results) {
+        let Some(translated) = result else {
+            failed_ids.push(unit.id.clone());
+            continue;
+        };
+
+        let element = &content[unit.range.clone()];
+        output.push_str(&content[last..unit.range.start]);
+        output.push_str(&write_xliff_unit(element, unit, &translated, version2));
+        last = unit.range.end;
+        translated_ids.push(unit.id.clone());
+    }
+    output.push_str(&content[last..]);
+
+    Ok(LocalizedContent {
+        content: output,
+        total_entries: units.len(),
+        translated_ids,
+        failed_ids,
+    })
+}
+
+// ============================================================================
+// ファイル翻訳
+// ============================================================================
+
+fn read_file(path: &Path) -> Result<String, LocalizationError> {
+    std::fs::read_to_string(path).map_err(|e| LocalizationError::ReadFailed(e.to_string()))
+}
+
+/// ローカライズファイルを翻訳して更新する
+///
+/// # Arguments
+/// * `path` - 更新するファイル（ja.json, ja.po, messages.xlfなど）
+/// * `source_path` - JSONの場合の翻訳元バンドル（en.jsonなど）
+/// * `previous_source_path` - JSONの場合の前回翻訳時の翻訳元バンドル（変更検出用）
+/// * `translate` - テキストを翻訳する関数（プロバイダーの翻訳処理）
+pub async fn translate_localization_file<F, Fut>(
+    path: &Path,
+    source_path: Option<&Path>,
+    previous_source_path: Option<&Path>,
+    source_lang: Language,
+    target_lang: Language,
+    translate: F,
+) -> Result<LocalizationResult, LocalizationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    let start = std::time::Instant::now();
+    let format = LocalizationFormat::from_path(path)?;
+
+    let localized = match format {
+        LocalizationFormat::Json => {
+            let source = read_file(source_path.ok_or(LocalizationError::SourceRequired)?)?;
+            // 翻訳先のバンドルはまだ存在しない場合がある
+            let target = path.exists().then(|| read_file(path)).transpose()?;
+            let previous = previous_source_path.map(read_file).transpose()?;
+            translate_json(&source, target.as_deref(), previous.as_deref(), translate).await?
+        }
+        LocalizationFormat::Po => translate_po(&read_file(path)?, translate).await?,
+        LocalizationFormat::Xliff => translate_xliff(&read_file(path)?, translate).await?,
+    };
+
+    std::fs::write(path, &localized.content)
+        .map_err(|e| LocalizationError::WriteFailed(e.to_string()))?;
+
+    Ok(LocalizationResult {
+        path: path.to_string_lossy().into_owned(),
+        format,
+        total_entries: localized.total_entries,
+        translated_ids: localized.translated_ids,
+        failed_ids: localized.failed_ids,
+        source_lang,
+        target_lang,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// トークンとマーカーを保持したまま大文字にする疑似翻訳
+    async fn upper(text: String) -> Result<String, TranslationError> {
+        Ok(text.to_uppercase())
+    }
+
+    fn placeholders(text: &str) -> Vec<String> {
+        MaskedMessage::from_text(text).placeholders
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            LocalizationFormat::from_path(Path::new("locales/ja.json")).unwrap(),
+            LocalizationFormat::Json
+        );
+        assert_eq!(
+            LocalizationFormat::from_path(Path::new("ja.po")).unwrap(),
+            LocalizationFormat::Po
+        );
+        assert_eq!(
+            LocalizationFormat::from_path(Path::new("messages.xlf")).unwrap(),
+            LocalizationFormat::Xliff
+        );
+        assert!(LocalizationFormat::from_path(Path::new("strings.yaml")).is_err());
+    }
+
+    #[test]
+    fn test_printf_placeholders() {
+        assert_eq!(
+            placeholders("%s has %d items (%1$s, %(name)s, %{count}, %.2f, 100%%)"),
+            vec!["%s", "%d", "%1$s", "%(name)s", "%{count}", "%.2f", "%%"]
+        );
+        // 変換指定子がない%はテキスト
+        assert!(placeholders("50% off").is_empty());
+    }
+
+    #[test]
+    fn test_icu_and_template_placeholders() {
+        assert_eq!(
+            placeholders("Hello {name}, you have {{count}} <b>new</b> items"),
+            vec!["{name}", "{{count}}", "<b>", "</b>"]
+        );
+    }
+
+    #[test]
+    fn test_icu_plural_masking() {
+        let message = MaskedMessage::from_text(
+            "You have {count, plural, one {# message} other {# messages}}",
+        );
+
+        assert_eq!(message.text, "You have ⟪0⟫ message⟪1⟫ messages⟪2⟫");
+        assert_eq!(
+            message.placeholders,
+            vec!["{count, plural, one {#", "} other {#", "}}"]
+        );
+        assert_eq!(
+            message.unmask("⟪0⟫件のメッセージ⟪1⟫件のメッセージ⟪2⟫があります", no_escape),
+            Some(
+                "{count, plural, one {#件のメッセージ} other {#件のメッセージ}}があります"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_unmask_rejects_missing_or_duplicated_tokens() {
+        let message = MaskedMessage::from_text("Hello {name}");
+        assert_eq!(message.text, "Hello ⟪0⟫");
+        assert_eq!(
+            message.unmask("こんにちは、⟪0⟫さん", no_escape),
+            Some("こんにちは、{name}さん".to_string())
+        );
+        assert_eq!(message.unmask("こんにちは", no_escape), None);
+        assert_eq!(message.unmask("⟪0⟫ ⟪0⟫", no_escape), None);
+        assert_eq!(message.unmask("⟪5⟫", no_escape), None);
+    }
+
+    #[test]
+    fn test_find_placeholders() {
+        assert_eq!(
+            find_placeholders("%s%s {name} {count, plural, one {# item} other {# items}} <b>"),
+            vec![
+                "%s",
+                "%s",
+                "{name}",
+                "{count, plural, one {# item} other {# items}}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_placeholder_only_message_is_not_translatable() {
+        assert!(!MaskedMessage::from_text("{count}").translatable);
+        assert!(!MaskedMessage::from_text("%s: %d").translatable);
+        assert!(MaskedMessage::from_text("Hello").translatable);
+    }
+
+    #[tokio::test]
+    async fn test_translate_json_only_missing_and_changed() {
+        let source = r#"{
+    "app": {
+        "title": "Honnyaku",
+        "greeting": "Hello {name}",
+        "changed": "New text"
+    },
+    "count": "%d items",
+    "empty": "Empty",
+    "list": ["One", "Two"]
+}"#;
+        let target = r#"{
+    "app": {
+        "title": "翻訳",
+        "changed": "古いテキスト"
+    },
+    "empty": "",
+    "obsolete": "残す"
+}"#;
+        let previous = r#"{"app": {"title": "Honnyaku", "changed": "Old text"}}"#;
+
+        let result = translate_json(source, Some(target), Some(previous), upper)
+            .await
+            .unwrap();
+
+        assert_eq!(result.total_entries, 7);
+        assert_eq!(
+            result.translated_ids,
+            vec![
+                "/app/greeting",
+                "/app/changed",
+                "/count",
+                "/empty",
+                "/list/0",
+                "/list/1"
+            ]
+        );
+        assert!(result.failed_ids.is_empty());
+
+        // 翻訳元のキー順・インデント（4スペース）で書き出される
+        assert_eq!(
+            result.content,
+            r#"{
+    "app": {
+        "title": "翻訳",
+        "greeting": "HELLO {name}",
+        "changed": "NEW TEXT"
+    },
+    "count": "%d ITEMS",
+    "empty": "EMPTY",
+    "list": [
+        "ONE",
+        "TWO"
+    ],
+    "obsolete": "残す"
+}
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_json_skips_entries_with_broken_placeholders() {
+        let source = r#"{"greeting": "Hello {name}", "title": "Title"}"#;
+        let result = translate_json(source, None, None, |text: String| async move {
+            Ok(text.replace("⟪0⟫", "").to_uppercase())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.failed_ids, vec!["/greeting"]);
+        assert_eq!(result.translated_ids, vec!["/title"]);
+        assert!(!result.content.contains("greeting"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_json_ids_distinguish_dotted_keys() {
+        let source = r#"{"a.b": "Dotted", "a": {"b": "Nested"}, "x/y~z": "Slash"}"#;
+        let result = translate_json(source, None, None, upper).await.unwrap();
+
+        assert_eq!(result.translated_ids, vec!["/a.b", "/a/b", "/x~1y~0z"]);
+        assert_eq!(
+            result.content,
+            "{\n  \"a.b\": \"DOTTED\",\n  \"a\": {\n    \"b\": \"NESTED\"\n  },\n  \"x/y~z\": \"SLASH\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_po_entry() {
+        let block = "#: src/main.rs:10\n#, fuzzy, c-format\n#| msgid \"Old\"\nmsgctxt \"menu\"\nmsgid \"\"\n\"Open \"\n\"file\"\nmsgstr \"開く\"\n";
+        let entry = parse_po_entry(block).unwrap();
+
+        assert_eq!(entry.comments, vec!["#: src/main.rs:10"]);
+        assert_eq!(entry.flags, vec!["fuzzy", "c-format"]);
+        assert_eq!(entry.previous, vec!["#| msgid \"Old\""]);
+        assert_eq!(entry.msgctxt, Some("menu".to_string()));
+        assert_eq!(entry.msgid, "Open file");
+        assert_eq!(entry.msgstr, vec!["開く"]);
+        assert!(entry.needs_translation());
+        assert_eq!(entry.id(), "[menu] Open file");
+
+        // 廃止エントリは解析しない
+        assert_eq!(
+            parse_po_entry("#~ msgid \"Old\"\n#~ msgstr \"古い\"\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_po_quote_roundtrip() {
+        let text = "Line \"one\"\n\tTab\\";
+        assert_eq!(unquote_po(&quote_po(text)), text);
+    }
+
+    #[tokio::test]
+    async fn test_translate_po() {
+        let content = r#"msgid ""
+msgstr ""
+"Language: ja\n"
+"Plural-Forms: nplurals=1; plural=0;\n"
+
+#: src/app.rs:1
+msgid "Open"
+msgstr "開く"
+
+#, c-format
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] ""
+
+#, fuzzy
+#| msgid "Save file"
+msgid "Save files"
+msgstr "ファイルを保存"
+
+msgid "Quit"
+msgstr ""
+"#;
+
+        let result = translate_po(content, upper).await.unwrap();
+
+        assert_eq!(result.total_entries, 4);
+        assert_eq!(result.translated_ids, vec!["%d file", "Save files", "Quit"]);
+        assert_eq!(
+            result.content,
+            r#"msgid ""
+msgstr ""
+"Language: ja\n"
+"Plural-Forms: nplurals=1; plural=0;\n"
+
+#: src/app.rs:1
+msgid "Open"
+msgstr "開く"
+
+#, fuzzy, c-format
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] "%d FILES"
+
+#, fuzzy
+msgid "Save files"
+msgstr "SAVE FILES"
+
+#, fuzzy
+msgid "Quit"
+msgstr "QUIT"
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_po_plural_forms() {
+        let content = "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural=(n != 1);\\n\"\n\nmsgid \"%d file\"\nmsgid_plural \"%d files\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"\n";
+
+        let result = translate_po(content, upper).await.unwrap();
+        assert!(result
+            .content
+            .ends_with("msgstr[0] \"%d FILE\"\nmsgstr[1] \"%d FILES\"\n"));
+    }
+
+    #[test]
+    fn test_attributes() {
+        let tag = r#"<target xml:lang="ja" state="new">"#;
+        assert_eq!(get_attribute(tag, "state"), Some("new"));
+        assert_eq!(get_attribute(tag, "lang"), None);
+        assert_eq!(
+            set_attribute(tag, "state", "translated"),
+            r#"<target xml:lang="ja" state="translated">"#
+        );
+        assert_eq!(
+            set_attribute("<segment>", "state", "translated"),
+            r#"<segment state="translated">"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_xliff_12() {
+        let content = r#"<xliff version="1.2">
+  <file source-language="en" target-language="ja">
+    <body>
+      <trans-unit id="greeting">
+        <source>Hello <g id="1">world</g> &amp; {name}</source>
+      </trans-unit>
+      <trans-unit id="done">
+        <source>Done</source>
+        <target state="translated">完了</target>
+      </trans-unit>
+      <trans-unit id="pending">
+        <source>Pending</source>
+        <target xml:lang="ja" state="new">Pending</target>
+      </trans-unit>
+      <trans-unit id="code" translate="no">
+        <source>Code</source>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>"#;
+
+        let result = translate_xliff(content, upper).await.unwrap();
+
+        assert_eq!(result.total_entries, 4);
+        assert_eq!(result.translated_ids, vec!["greeting", "pending"]);
+        assert!(result.content.contains(
+            "<source>Hello <g id=\"1\">world</g> &amp; {name}</source>\n        <target state=\"needs-review-translation\" state-qualifier=\"mt-suggestion\">HELLO <g id=\"1\">WORLD</g> &amp; {name}</target>"
+        ));
+        assert!(result
+            .content
+            .contains("<target state=\"translated\">完了</target>"));
+        assert!(result.content.contains(
+            "<target xml:lang=\"ja\" state=\"needs-review-translation\" state-qualifier=\"mt-suggestion\">PENDING</target>"
+        ));
+        assert!(!result.content.contains("CODE"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_xliff_20() {
+        let content = r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="ja">
+  <file id="f1">
+    <unit id="u1">
+      <segment id="s1">
+        <source>Save <pc id="1">now</pc></source>
+      </segment>
+    </unit>
+    <unit id="u2">
+      <segment state="final">
+        <source>Cancel</source>
+        <target>キャンセル</target>
+      </segment>
+    </unit>
+  </file>
+</xliff>"#;
+
+        let result = translate_xliff(content, upper).await.unwrap();
+
+        assert_eq!(result.total_entries, 2);
+        assert_eq!(result.translated_ids, vec!["u1/s1"]);
+        assert!(result.content.contains(
+            "<segment id=\"s1\" state=\"translated\">\n        <source>Save <pc id=\"1\">now</pc></source>\n        <target>SAVE <pc id=\"1\">NOW</pc></target>"
+        ));
+        assert!(result.content.contains("<target>キャンセル</target>"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_localization_file_requires_source_for_json() {
+        let result = translate_localization_file(
+            Path::new("/nonexistent/ja.json"),
+            None,
+            None,
+            Language::English,
+            Language::Japanese,
+            upper,
+        )
+        .await;
+
+        assert!(matches!(result, Err(LocalizationError::SourceRequired)));
+    }
+
+    #[tokio::test]
+    async fn test_translate_localization_file_creates_json_bundle() {
+        let dir =
+            std::env::temp_dir().join(format!("honnyaku-localization-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("en.json");
+        let target = dir.join("ja.json");
+        std::fs::write(&source, "{\n  \"title\": \"Title\"\n}\n").unwrap();
+
+        let result = translate_localization_file(
+            &target,
+            Some(&source),
+            None,
+            Language::English,
+            Language::Japanese,
+            upper,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.translated_ids, vec!["/title"]);
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "{\n  \"title\": \"TITLE\"\n}\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,7 +3,22 @@
 //! バックエンドのビジネスロジックを提供するサービス群
 
 pub mod clipboard;
+pub mod clipboard_watch;
+pub mod deinflect;
+pub mod dictionary;
+pub mod document;
+pub mod domain;
+pub mod explain;
+pub mod localization;
 pub mod permissions;
+pub mod proofread;
+pub mod quality;
+pub mod reading;
+pub mod refinement;
+pub mod rich_text;
+pub mod selection_context;
 pub mod settings;
 pub mod shortcut;
+pub mod thread;
 pub mod translation;
+pub mod window;
@@ -1,6 +1,9 @@
 //! 権限管理サービス
 //!
 //! macOSのアクセシビリティ権限の確認と管理を提供
+//!
+//! Linuxではアクセシビリティ権限の概念がないため、選択テキストの取得に必要な
+//! ディスプレイサーバー（X11/Wayland）への接続可否を権限状態として扱う
 
 use serde::Serialize;
 
@@ -23,6 +26,31 @@ impl Default for PermissionStatus {
     }
 }
 
+/// Linuxでの選択テキスト取得可否から権限状態を判定する
+///
+/// # Arguments
+/// * `x11_display` - DISPLAY環境変数の値
+/// * `wayland_display` - WAYLAND_DISPLAY環境変数の値
+/// * `selection_available` - PRIMARYセレクションへの接続に成功したか
+///
+/// # Returns
+/// ディスプレイサーバーに接続でき、セレクションを読み取れる場合は許可済み。
+/// Linuxには権限ダイアログがないため、権限リクエストは常に不要とする
+pub fn linux_permission_status(
+    x11_display: Option<&str>,
+    wayland_display: Option<&str>,
+    selection_available: bool,
+) -> PermissionStatus {
+    let has_display = [x11_display, wayland_display]
+        .iter()
+        .any(|d| d.is_some_and(|v| !v.is_empty()));
+
+    PermissionStatus {
+        accessibility_granted: has_display && selection_available,
+        needs_permission_request: false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +72,31 @@ mod tests {
         assert!(json.contains("\"accessibilityGranted\":true"));
         assert!(json.contains("\"needsPermissionRequest\":false"));
     }
+
+    #[test]
+    fn test_linux_permission_status_granted() {
+        let status = linux_permission_status(Some(":0"), None, true);
+        assert!(status.accessibility_granted);
+        assert!(!status.needs_permission_request);
+
+        let status = linux_permission_status(None, Some("wayland-0"), true);
+        assert!(status.accessibility_granted);
+    }
+
+    #[test]
+    fn test_linux_permission_status_no_display() {
+        // ディスプレイサーバーがない場合（SSH接続やヘッドレス環境）
+        let status = linux_permission_status(None, None, false);
+        assert!(!status.accessibility_granted);
+        assert!(!status.needs_permission_request);
+
+        let status = linux_permission_status(Some(""), None, true);
+        assert!(!status.accessibility_granted);
+    }
+
+    #[test]
+    fn test_linux_permission_status_selection_unavailable() {
+        let status = linux_permission_status(Some(":0"), None, false);
+        assert!(!status.accessibility_granted);
+    }
 }
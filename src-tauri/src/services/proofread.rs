@@ -0,0 +1,286 @@
+//! 文法・文体の校正サービス
+//!
+//! 自分で書いた英語・日本語の文章を翻訳せずに校正する。
+//! 校正後のテキストと、修正箇所（原文上の範囲・修正前・修正後・理由）の一覧を
+//! JSON形式の出力で生成し、UIで差分を表示できる構造化データとして返す
+
+use crate::services::translation::{parse_json_object, Language, TranslationError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// 原文上の範囲（UTF-16のコード単位でのオフセット。フロントエンドの文字列と一致する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSpan {
+    /// 開始位置
+    pub start: usize,
+    /// 終了位置（この位置を含まない）
+    pub end: usize,
+}
+
+/// 修正箇所
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofreadEdit {
+    /// 原文上の範囲
+    pub span: EditSpan,
+    /// 修正前の文字列
+    pub original: String,
+    /// 修正後の文字列
+    pub replacement: String,
+    /// 修正の理由（日本語）
+    pub reason: String,
+}
+
+/// 校正結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofreadResult {
+    /// 校正前のテキスト
+    pub original_text: String,
+    /// 校正後のテキスト
+    pub corrected_text: String,
+    /// 修正箇所（原文上の出現順）
+    pub edits: Vec<ProofreadEdit>,
+    /// 文章の言語
+    pub language: Language,
+    /// 処理時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// モデルが出力する修正箇所（構造化出力）
+#[derive(Debug, Deserialize)]
+struct StructuredEdit {
+    #[serde(default)]
+    original: String,
+    #[serde(default)]
+    replacement: String,
+    #[serde(default)]
+    reason: String,
+}
+
+/// モデルが出力する校正結果（構造化出力）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StructuredProofread {
+    #[serde(default)]
+    corrected_text: String,
+    #[serde(default)]
+    edits: Vec<StructuredEdit>,
+}
+
+/// 校正エラー
+#[derive(Debug, Error)]
+pub enum ProofreadError {
+    #[error("校正するテキストが空です")]
+    EmptyInput,
+    #[error("校正結果を解釈できませんでした")]
+    InvalidResponse,
+    #[error("{0}")]
+    TranslationFailed(#[from] TranslationError),
+}
+
+impl Serialize for ProofreadError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 校正用のシステムプロンプト（文章の言語で記述）
+pub fn system_prompt(language: Language) -> &'static str {
+    match language {
+        Language::Japanese => {
+            "あなたは日本語の校正者です。文章の意味や書き手の意図を変えずに、誤字脱字・文法・表記の揺れ・不自然な表現を修正してください。指定されたJSON形式でのみ出力してください。"
+        }
+        Language::English => {
+            "You are a professional English proofreader. Correct grammar, spelling, punctuation, word choice, and awkward phrasing without changing the meaning or the writer's intent. Output only the requested JSON."
+        }
+    }
+}
+
+/// 校正用のプロンプトを構築
+///
+/// 修正の理由は文章の言語によらず日本語で出力させる
+pub fn build_proofread_prompt(text: &str, language: Language) -> String {
+    match language {
+        Language::Japanese => format!(
+            "次の日本語の文章を校正してください。\n\
+             JSONで出力し、\"correctedText\"に校正後の文章全体、\"edits\"に修正箇所ごとの{{\"original\": 原文中の修正前の文字列, \"replacement\": 修正後の文字列, \"reason\": 修正の理由}}を原文の出現順に入れてください。\
+             \"original\"は原文の文字列をそのまま抜き出し、修正がない場合は\"edits\"を空にしてください。\n\n文章:\n{}",
+            text
+        ),
+        Language::English => format!(
+            "Proofread the following English text.\n\
+             Output JSON with the full corrected text in \"correctedText\" and, in \"edits\", one {{\"original\": exact substring of the original text, \"replacement\": corrected string, \"reason\": reason for the change}} per change in order of appearance.\
+             Copy \"original\" verbatim from the text, write each \"reason\" in Japanese, and leave \"edits\" empty if no changes are needed.\n\nText:\n{}",
+            text
+        ),
+    }
+}
+
+/// 校正の構造化出力スキーマ（Ollamaの`format`パラメータ）
+pub fn proofread_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "correctedText": { "type": "string" },
+            "edits": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "original": { "type": "string" },
+                        "replacement": { "type": "string" },
+                        "reason": { "type": "string" }
+                    },
+                    "required": ["original", "replacement", "reason"]
+                }
+            }
+        },
+        "required": ["correctedText", "edits"]
+    })
+}
+
+/// バイトオフセットをUTF-16のオフセットに変換する
+fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
+}
+
+/// 修正箇所を原文上で探し、範囲を求める
+///
+/// 直前の修正箇所の後ろから順に探し、原文に見つからない修正箇所や変更のない修正箇所は除く
+fn locate_edits(text: &str, edits: Vec<StructuredEdit>) -> Vec<ProofreadEdit> {
+    let mut located = Vec::new();
+    let mut search = 0;
+
+    for edit in edits {
+        if edit.original.is_empty() || edit.original == edit.replacement {
+            continue;
+        }
+        let Some(offset) = text[search..].find(&edit.original) else {
+            continue;
+        };
+        let start = search + offset;
+        let end = start + edit.original.len();
+        search = end;
+
+        located.push(ProofreadEdit {
+            span: EditSpan {
+                start: utf16_offset(text, start),
+                end: utf16_offset(text, end),
+            },
+            original: edit.original,
+            replacement: edit.replacement,
+            reason: edit.reason.trim().to_string(),
+        });
+    }
+
+    located
+}
+
+/// 原文に修正箇所を適用する
+fn apply_edits(text: &str, edits: &[ProofreadEdit]) -> String {
+    let utf16: Vec<u16> = text.encode_utf16().collect();
+    let mut output = String::new();
+    let mut position = 0;
+    for edit in edits {
+        output.push_str(&String::from_utf16_lossy(&utf16[position..edit.span.start]));
+        output.push_str(&edit.replacement);
+        position = edit.span.end;
+    }
+    output.push_str(&String::from_utf16_lossy(&utf16[position..]));
+    output
+}
+
+/// モデルの出力から校正結果を組み立てる
+///
+/// 校正後のテキストが出力されなかった場合は、原文に修正箇所を適用して求める
+pub fn build_proofread_result(
+    content: &str,
+    text: &str,
+    language: Language,
+    duration_ms: u64,
+) -> Result<ProofreadResult, ProofreadError> {
+    let parsed: StructuredProofread =
+        parse_json_object(content).ok_or(ProofreadError::InvalidResponse)?;
+
+    let edits = locate_edits(text, parsed.edits);
+    let corrected_text = match parsed.corrected_text.trim() {
+        "" => apply_edits(text, &edits),
+        corrected => corrected.to_string(),
+    };
+
+    Ok(ProofreadResult {
+        original_text: text.to_string(),
+        corrected_text,
+        edits,
+        language,
+        duration_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(original: &str, replacement: &str) -> StructuredEdit {
+        StructuredEdit {
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+            reason: "理由".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_locate_edits() {
+        let text = "He go to school. He go home.";
+        let edits = locate_edits(
+            text,
+            vec![
+                edit("go", "goes"),
+                edit("not found", "x"),
+                edit("school", "school"),
+                edit("go", "goes"),
+            ],
+        );
+
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[0].span, EditSpan { start: 3, end: 5 });
+        assert_eq!(edits[1].span, EditSpan { start: 20, end: 22 });
+        assert_eq!(
+            apply_edits(text, &edits),
+            "He goes to school. He goes home."
+        );
+    }
+
+    #[test]
+    fn test_locate_edits_utf16() {
+        let text = "😀今日わ晴れです。";
+        let edits = locate_edits(text, vec![edit("わ", "は")]);
+
+        // 絵文字はUTF-16で2コード単位
+        assert_eq!(edits[0].span, EditSpan { start: 4, end: 5 });
+        assert_eq!(apply_edits(text, &edits), "😀今日は晴れです。");
+    }
+
+    #[test]
+    fn test_build_proofread_result() {
+        let content = r#"```json
+{"correctedText": "", "edits": [{"original": "recieve", "replacement": "receive", "reason": "スペルの誤り"}]}
+```"#;
+
+        let result =
+            build_proofread_result(content, "I recieve it.", Language::English, 10).unwrap();
+        assert_eq!(result.corrected_text, "I receive it.");
+        assert_eq!(result.edits.len(), 1);
+        assert_eq!(result.edits[0].reason, "スペルの誤り");
+
+        assert!(matches!(
+            build_proofread_result("not json", "text", Language::English, 0),
+            Err(ProofreadError::InvalidResponse)
+        ));
+    }
+}
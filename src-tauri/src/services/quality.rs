@@ -0,0 +1,596 @@
+//! 翻訳品質チェックサービス
+//!
+//! 翻訳結果を原文と比較し、数値・URL・インラインコード・プレースホルダーの欠落、
+//! 未翻訳、途中で切れた出力、括弧の不一致、行数の違いを警告として検出する。
+//! また、逆翻訳と原文の類似度による意味の整合性チェックを提供
+
+use crate::services::localization;
+use crate::services::translation::{Language, TranslationResult};
+use serde::{Deserialize, Serialize};
+
+/// 未翻訳・途中切れの判定に必要な原文の最小文字数（空白を除く）
+const MIN_CHECK_LENGTH: usize = 10;
+
+/// 対応する括弧の組
+const BRACKET_PAIRS: &[(char, char)] = &[
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('（', '）'),
+    ('「', '」'),
+    ('『', '』'),
+    ('【', '】'),
+    ('《', '》'),
+    ('“', '”'),
+];
+
+/// 警告の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QaWarningKind {
+    /// 数値の不一致
+    Number,
+    /// URLの欠落
+    Url,
+    /// インラインコードの欠落
+    Code,
+    /// プレースホルダーの不一致
+    Placeholder,
+    /// 翻訳されていない
+    Untranslated,
+    /// 出力が途中で切れている
+    Truncated,
+    /// 括弧の対応が崩れている
+    Bracket,
+    /// 行数の不一致
+    LineCount,
+}
+
+/// 品質チェックの警告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaWarning {
+    /// 警告の種類
+    pub kind: QaWarningKind,
+    /// 警告メッセージ
+    pub message: String,
+}
+
+/// 逆翻訳による整合性チェックの結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackTranslationResult {
+    /// 翻訳結果
+    pub translation: TranslationResult,
+    /// 翻訳結果を翻訳元言語に戻した逆翻訳
+    pub back_translation: String,
+    /// 原文と逆翻訳の類似度（0.0 - 1.0）
+    pub similarity: f64,
+}
+
+impl QaWarning {
+    fn new(kind: QaWarningKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// 全角数字・記号を半角に変換
+fn normalize_width(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            '．' => '.',
+            '，' => ',',
+            _ => c,
+        })
+        .collect()
+}
+
+/// `source`にあって`translated`にない要素を返す（重複も考慮）
+fn missing_items(source: &[String], translated: &[String]) -> Vec<String> {
+    let mut remaining: Vec<&String> = translated.iter().collect();
+    let mut missing = Vec::new();
+
+    for item in source {
+        match remaining.iter().position(|t| *t == item) {
+            Some(pos) => {
+                remaining.swap_remove(pos);
+            }
+            None => missing.push(item.clone()),
+        }
+    }
+
+    missing
+}
+
+/// URLを抽出
+fn extract_urls(text: &str) -> Vec<String> {
+    let mut urls = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme))
+        .min()
+    {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| {
+                c.is_whitespace()
+                    || matches!(
+                        c,
+                        ')' | ']' | '>' | '"' | '\'' | '」' | '』' | '）' | '、' | '。' | '`'
+                    )
+            })
+            .unwrap_or(candidate.len());
+        let url = candidate[..end].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        urls.push(url.to_string());
+        rest = &candidate[end..];
+    }
+
+    urls
+}
+
+/// バッククォートで囲まれたインラインコードを抽出
+fn extract_code_spans(text: &str) -> Vec<String> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('`') {
+        let candidate = &rest[start..];
+        let ticks = candidate.chars().take_while(|&c| c == '`').count();
+        let fence = &candidate[..ticks];
+
+        match candidate[ticks..].find(fence) {
+            Some(end) => {
+                let len = ticks + end + ticks;
+                spans.push(candidate[..len].to_string());
+                rest = &candidate[len..];
+            }
+            None => break,
+        }
+    }
+
+    spans
+}
+
+/// URLとインラインコードを除いたテキスト
+fn strip_urls_and_code(text: &str) -> String {
+    let mut stripped = text.to_string();
+    for span in extract_code_spans(text)
+        .into_iter()
+        .chain(extract_urls(text))
+    {
+        stripped = stripped.replacen(&span, " ", 1);
+    }
+    stripped
+}
+
+/// 数値を抽出（桁区切りのカンマは除去して比較する）
+fn extract_numbers(text: &str) -> Vec<String> {
+    let chars: Vec<char> = normalize_width(text).chars().collect();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut number = String::new();
+        while i < chars.len() {
+            let c = chars[i];
+            let next_is_digit = chars.get(i + 1).is_some_and(char::is_ascii_digit);
+            if c.is_ascii_digit() || (c == '.' && next_is_digit) {
+                number.push(c);
+            } else if c != ',' || !next_is_digit {
+                break;
+            }
+            i += 1;
+        }
+        numbers.push(number);
+    }
+
+    numbers
+}
+
+/// 括弧の対応が取れているか
+fn is_balanced(text: &str) -> bool {
+    let mut stack = Vec::new();
+
+    for c in text.chars() {
+        if let Some(&(_, close)) = BRACKET_PAIRS.iter().find(|(open, _)| *open == c) {
+            stack.push(close);
+        } else if BRACKET_PAIRS.iter().any(|(_, close)| *close == c) && stack.pop() != Some(c) {
+            return false;
+        }
+    }
+
+    stack.is_empty()
+}
+
+/// 空白を除いた文字数
+fn content_length(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+/// 空行を除いた行数
+fn line_count(text: &str) -> usize {
+    text.lines().filter(|line| !line.trim().is_empty()).count()
+}
+
+/// 類似度計算用に正規化した文字列（空白・記号を除き、小文字・半角に統一）
+fn normalize_for_similarity(text: &str) -> Vec<char> {
+    normalize_width(text)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 文字n-gramの一覧
+fn char_ngrams(chars: &[char], n: usize) -> Vec<&[char]> {
+    if chars.len() < n {
+        return Vec::new();
+    }
+    chars.windows(n).collect()
+}
+
+/// 原文と逆翻訳の類似度を計算する
+///
+/// 文字n-gram（日本語はバイグラム、英語はトライグラム）の重なりによる
+/// Dice係数を返す。1.0に近いほど意味が保たれている可能性が高い
+pub fn similarity(original: &str, back_translation: &str, language: Language) -> f64 {
+    let n = match language {
+        Language::Japanese => 2,
+        Language::English => 3,
+    };
+
+    let original = normalize_for_similarity(original);
+    let back_translation = normalize_for_similarity(back_translation);
+    let original_ngrams = char_ngrams(&original, n);
+    let back_ngrams = char_ngrams(&back_translation, n);
+
+    // n-gramを作れないほど短い場合は完全一致かどうかで判定
+    if original_ngrams.is_empty() || back_ngrams.is_empty() {
+        return if !original.is_empty() && original == back_translation {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    let mut remaining = back_ngrams.clone();
+    let mut common = 0;
+    for ngram in &original_ngrams {
+        if let Some(pos) = remaining.iter().position(|g| g == ngram) {
+            remaining.swap_remove(pos);
+            common += 1;
+        }
+    }
+
+    (2 * common) as f64 / (original_ngrams.len() + back_ngrams.len()) as f64
+}
+
+/// 原文と訳文の要素の差分を警告として追加
+fn push_mismatch(
+    warnings: &mut Vec<QaWarning>,
+    kind: QaWarningKind,
+    label: &str,
+    source: &[String],
+    translated: &[String],
+) {
+    let missing = missing_items(source, translated);
+    let extra = missing_items(translated, source);
+
+    let mut details = Vec::new();
+    if !missing.is_empty() {
+        details.push(format!("原文のみ: {}", missing.join(", ")));
+    }
+    if !extra.is_empty() {
+        details.push(format!("訳文のみ: {}", extra.join(", ")));
+    }
+
+    if !details.is_empty() {
+        warnings.push(QaWarning::new(
+            kind,
+            format!("{}が一致しません（{}）", label, details.join(" / ")),
+        ));
+    }
+}
+
+/// 翻訳結果の品質をチェックする
+///
+/// # Arguments
+/// * `source` - 原文
+/// * `translated` - 整形済みの訳文（`clean_translation_result`の適用後）
+///
+/// # Returns
+/// 検出した警告の一覧（問題がなければ空）
+pub fn check_translation(
+    source: &str,
+    translated: &str,
+    source_lang: Language,
+    target_lang: Language,
+) -> Vec<QaWarning> {
+    let mut warnings = Vec::new();
+
+    if translated.trim().is_empty() {
+        warnings.push(QaWarning::new(QaWarningKind::Truncated, "訳文が空です"));
+        return warnings;
+    }
+
+    // 原文にあるURL・インラインコードは訳文にもそのまま含まれるべき
+    let missing_urls = missing_items(&extract_urls(source), &extract_urls(translated));
+    if !missing_urls.is_empty() {
+        warnings.push(QaWarning::new(
+            QaWarningKind::Url,
+            format!("URLが訳文にありません: {}", missing_urls.join(", ")),
+        ));
+    }
+
+    let missing_code = missing_items(&extract_code_spans(source), &extract_code_spans(translated));
+    if !missing_code.is_empty() {
+        warnings.push(QaWarning::new(
+            QaWarningKind::Code,
+            format!(
+                "インラインコードが訳文にありません: {}",
+                missing_code.join(", ")
+            ),
+        ));
+    }
+
+    let source_text = strip_urls_and_code(source);
+    let translated_text = strip_urls_and_code(translated);
+
+    push_mismatch(
+        &mut warnings,
+        QaWarningKind::Number,
+        "数値",
+        &extract_numbers(&source_text),
+        &extract_numbers(&translated_text),
+    );
+    push_mismatch(
+        &mut warnings,
+        QaWarningKind::Placeholder,
+        "プレースホルダー",
+        &localization::find_placeholders(&source_text),
+        &localization::find_placeholders(&translated_text),
+    );
+
+    let source_length = content_length(&source_text);
+    if source_lang != target_lang && source_length >= MIN_CHECK_LENGTH {
+        if source.trim() == translated.trim() || Language::detect(&translated_text) == source_lang {
+            warnings.push(QaWarning::new(
+                QaWarningKind::Untranslated,
+                format!("訳文が{}のままです", source_lang.name()),
+            ));
+        }
+
+        // 言語による文字数の違いを考慮し、想定の3割未満なら途中で切れているとみなす
+        let expected_ratio = match (source_lang, target_lang) {
+            (Language::English, Language::Japanese) => 0.5,
+            (Language::Japanese, Language::English) => 2.0,
+            _ => 1.0,
+        };
+        let translated_length = content_length(&translated_text);
+        let ends_mid_sentence = translated
+            .trim_end()
+            .ends_with([',', '、', ':', '：', '(', '（'])
+            && !source
+                .trim_end()
+                .ends_with([',', '、', ':', '：', '(', '（']);
+
+        if (translated_length as f64) < source_length as f64 * expected_ratio * 0.3
+            || ends_mid_sentence
+        {
+            warnings.push(QaWarning::new(
+                QaWarningKind::Truncated,
+                "訳文が途中で切れている可能性があります",
+            ));
+        }
+    }
+
+    if is_balanced(source) && !is_balanced(translated) {
+        warnings.push(QaWarning::new(
+            QaWarningKind::Bracket,
+            "訳文の括弧の対応が取れていません",
+        ));
+    }
+
+    let source_lines = line_count(source);
+    let translated_lines = line_count(translated);
+    if source_lines >= 2 && source_lines != translated_lines {
+        warnings.push(QaWarning::new(
+            QaWarningKind::LineCount,
+            format!(
+                "行数が一致しません（原文: {}行 / 訳文: {}行）",
+                source_lines, translated_lines
+            ),
+        ));
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(warnings: &[QaWarning]) -> Vec<QaWarningKind> {
+        warnings.iter().map(|w| w.kind).collect()
+    }
+
+    #[test]
+    fn test_good_translation_has_no_warnings() {
+        let warnings = check_translation(
+            "The meeting starts at 10:30 in room 5. See https://example.com for details.",
+            "会議は5号室で10:30に始まります。詳細は https://example.com をご覧ください。",
+            Language::English,
+            Language::Japanese,
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_empty_translation() {
+        let warnings = check_translation("Hello", "  ", Language::English, Language::Japanese);
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Truncated]);
+    }
+
+    #[test]
+    fn test_number_mismatch() {
+        let warnings = check_translation(
+            "Order 1,200 units by Friday",
+            "金曜日までに1300個注文してください",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Number]);
+        assert_eq!(
+            warnings[0].message,
+            "数値が一致しません（原文のみ: 1200 / 訳文のみ: 1300）"
+        );
+    }
+
+    #[test]
+    fn test_numbers_normalize_full_width_and_separators() {
+        assert_eq!(extract_numbers("１，０００円と3.5%"), vec!["1000", "3.5"]);
+        assert_eq!(extract_numbers("1, 2"), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_missing_url_and_code() {
+        let warnings = check_translation(
+            "Run `npm install` and open https://example.com/docs.",
+            "npm install を実行してドキュメントを開いてください。",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(
+            kinds(&warnings),
+            vec![QaWarningKind::Url, QaWarningKind::Code]
+        );
+        assert_eq!(
+            warnings[0].message,
+            "URLが訳文にありません: https://example.com/docs"
+        );
+    }
+
+    #[test]
+    fn test_placeholder_mismatch() {
+        let warnings = check_translation(
+            "Hello {name}, you have %d new messages",
+            "こんにちは、新しいメッセージが%d件あります",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Placeholder]);
+        assert_eq!(
+            warnings[0].message,
+            "プレースホルダーが一致しません（原文のみ: {name}）"
+        );
+    }
+
+    #[test]
+    fn test_untranslated() {
+        let warnings = check_translation(
+            "Please review the attached document.",
+            "Please review the attached document.",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Untranslated]);
+
+        // 短いテキストは判定しない
+        let warnings = check_translation("API", "API", Language::English, Language::Japanese);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let warnings = check_translation(
+            "添付の資料を確認して、明日の会議までにコメントをお願いします。",
+            "Please review",
+            Language::Japanese,
+            Language::English,
+        );
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Truncated]);
+
+        let warnings = check_translation(
+            "Please review the attached document before the meeting.",
+            "会議の前に添付の資料を、",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Truncated]);
+    }
+
+    #[test]
+    fn test_bracket_balance() {
+        assert!(is_balanced("関数（function）を呼び出す「例」"));
+        assert!(!is_balanced("関数（function を呼び出す"));
+        assert!(!is_balanced("(]"));
+
+        let warnings = check_translation(
+            "Call the function (see below).",
+            "関数を呼び出します（下記参照。",
+            Language::English,
+            Language::Japanese,
+        );
+        assert_eq!(kinds(&warnings), vec![QaWarningKind::Bracket]);
+    }
+
+    #[test]
+    fn test_line_count() {
+        let warnings = check_translation(
+            "First line\nSecond line\nThird line",
+            "1行目\n2行目と3行目",
+            Language::English,
+            Language::Japanese,
+        );
+        assert!(kinds(&warnings).contains(&QaWarningKind::LineCount));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let text = "明日の会議の資料を送ってください。";
+        assert_eq!(similarity(text, text, Language::Japanese), 1.0);
+
+        let close = similarity(
+            text,
+            "明日の会議の資料を送付してください。",
+            Language::Japanese,
+        );
+        let far = similarity(text, "昨日の夕食はとても美味しかった。", Language::Japanese);
+        assert!(close > 0.6, "{}", close);
+        assert!(far < 0.2, "{}", far);
+        assert!(close > far);
+    }
+
+    #[test]
+    fn test_similarity_ignores_case_and_punctuation() {
+        assert_eq!(
+            similarity(
+                "Please send the report.",
+                "please send the report",
+                Language::English
+            ),
+            1.0
+        );
+        assert_eq!(similarity("OK", "OK", Language::English), 1.0);
+        assert_eq!(similarity("OK", "No", Language::English), 0.0);
+        assert_eq!(similarity("", "", Language::English), 0.0);
+    }
+
+    #[test]
+    fn test_warning_serialization() {
+        let warning = QaWarning::new(QaWarningKind::LineCount, "message");
+        let json = serde_json::to_string(&warning).unwrap();
+        assert_eq!(json, r#"{"kind":"line-count","message":"message"}"#);
+    }
+}
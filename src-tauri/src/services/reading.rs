@@ -0,0 +1,735 @@
+//! 日本語の読み（ふりがな・ローマ字）生成サービス
+//!
+//! 日本語テキストを単語に分割し、漢字にひらがなの読みを付けたルビ付きの区間と
+//! ヘボン式ローマ字（長音を長音符号で表す修正ヘボン式）を生成する。取り込み済みのオフライン辞書があれば辞書で単語に分割し、
+//! 辞書にない漢字がある場合はLLMの構造化出力で単語と読みを取得する。
+//! ローマ字は読みから規則的に変換する
+
+use crate::services::dictionary::{self, Dictionary, DictionaryError};
+use crate::services::translation::{parse_json_object, TranslationError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// 辞書で単語に分割するときの単語の最大文字数
+const MAX_WORD_CHARS: usize = 8;
+
+/// ルビ付きの区間
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RubySegment {
+    /// 表記
+    pub text: String,
+    /// ひらがなの読み（漢字を含む区間のみ）
+    pub reading: Option<String>,
+}
+
+/// 読みの取得元
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadingSource {
+    /// 漢字を含まないため、かなから変換
+    Kana,
+    /// オフライン辞書
+    Dictionary,
+    /// LLMの構造化出力
+    Llm,
+}
+
+/// 読みの注釈
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingAnnotation {
+    /// ルビ付きの区間（つなげると元のテキストになる）
+    pub segments: Vec<RubySegment>,
+    /// 修正ヘボン式ローマ字（単語ごとに空白で区切る）
+    pub romaji: String,
+    /// 読みの取得元
+    pub source: ReadingSource,
+}
+
+/// モデルが出力する単語（構造化出力）
+#[derive(Debug, Deserialize)]
+struct StructuredWord {
+    text: String,
+    #[serde(default)]
+    reading: String,
+}
+
+/// モデルが出力する単語の一覧（構造化出力）
+#[derive(Debug, Deserialize)]
+struct StructuredReading {
+    segments: Vec<StructuredWord>,
+}
+
+/// 読み生成エラー
+#[derive(Debug, Error)]
+pub enum ReadingError {
+    #[error("読みの生成結果を解釈できませんでした")]
+    InvalidResponse,
+    #[error("{0}")]
+    TranslationFailed(#[from] TranslationError),
+    #[error("{0}")]
+    DictionaryFailed(#[from] DictionaryError),
+}
+
+impl Serialize for ReadingError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// 漢字（繰り返し記号を含む）かどうか
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々')
+}
+
+/// 漢字を含むかどうか
+pub fn contains_kanji(text: &str) -> bool {
+    text.chars().any(is_kanji)
+}
+
+/// カタカナをひらがなに変換する（長音符などはそのまま）
+pub fn to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// 2文字の拗音・外来音のローマ字
+fn romaji_digraph(first: char, second: char) -> Option<String> {
+    let prefix = match first {
+        'き' => "ky",
+        'ぎ' => "gy",
+        'し' => "sh",
+        'じ' | 'ぢ' => "j",
+        'ち' => "ch",
+        'に' => "ny",
+        'ひ' => "hy",
+        'び' => "by",
+        'ぴ' => "py",
+        'み' => "my",
+        'り' => "ry",
+        _ => "",
+    };
+    if !prefix.is_empty() {
+        let vowel = match second {
+            'ゃ' => "a",
+            'ゅ' => "u",
+            'ょ' => "o",
+            'ぇ' if matches!(prefix, "sh" | "j" | "ch") => "e",
+            _ => return None,
+        };
+        return Some(format!("{prefix}{vowel}"));
+    }
+
+    let romaji = match (first, second) {
+        ('て', 'ぃ') => "ti",
+        ('で', 'ぃ') => "di",
+        ('と', 'ぅ') => "tu",
+        ('ど', 'ぅ') => "du",
+        ('つ', 'ぁ') => "tsa",
+        ('ふ', 'ぁ') => "fa",
+        ('ふ', 'ぃ') => "fi",
+        ('ふ', 'ぇ') => "fe",
+        ('ふ', 'ぉ') => "fo",
+        ('う', 'ぃ') => "wi",
+        ('う', 'ぇ') => "we",
+        ('う', 'ぉ') => "wo",
+        ('ゔ', 'ぁ') => "va",
+        ('ゔ', 'ぃ') => "vi",
+        ('ゔ', 'ぇ') => "ve",
+        ('ゔ', 'ぉ') => "vo",
+        _ => return None,
+    };
+    Some(romaji.to_string())
+}
+
+/// 1文字のかな・記号のローマ字
+fn romaji_single(c: char) -> Option<&'static str> {
+    let romaji = match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' | 'ゐ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' | 'ゑ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゔ' => "vu",
+        '。' => ".",
+        '、' => ",",
+        '！' => "!",
+        '？' => "?",
+        '「' | '『' => "\u{201C}",
+        '」' | '』' => "\u{201D}",
+        '（' => "(",
+        '）' => ")",
+        '・' | '\u{3000}' => " ",
+        _ => return None,
+    };
+    Some(romaji)
+}
+
+/// 母音を長音符号付きの母音に変換する
+fn macron(vowel: char) -> Option<char> {
+    match vowel {
+        'a' => Some('ā'),
+        'i' => Some('ī'),
+        'u' => Some('ū'),
+        'e' => Some('ē'),
+        'o' => Some('ō'),
+        _ => None,
+    }
+}
+
+/// かなを修正ヘボン式ローマ字に変換する
+///
+/// 促音（っ）は次の子音を重ね、撥音（ん）は母音・yの前で"n'"とする。
+/// 長音は長音符号で表し、「おう」「おお」は"ō"、「うう」は"ū"、長音符（ー）は直前の母音に長音符号を付ける
+/// （例: とうきょう→"tōkyō"、コーヒー→"kōhī"）。「えい」「いい」はそのまま"ei"・"ii"とする。
+/// 語の境界は区別しないため、「思う」のような語中の「おう」も"ō"になる。かな以外の文字はそのまま残す
+pub fn kana_to_romaji(kana: &str) -> String {
+    let chars: Vec<char> = to_hiragana(kana).chars().collect();
+    let mut output = String::new();
+    let mut geminate = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == 'っ' {
+            geminate = true;
+            i += 1;
+            continue;
+        }
+        let next = chars.get(i + 1).copied();
+        let lengthens = match c {
+            'ー' => true,
+            'う' => {
+                output.ends_with(['o', 'u'])
+                    && next.is_none_or(|next| romaji_digraph(c, next).is_none())
+            }
+            'お' => output.ends_with('o'),
+            _ => false,
+        };
+        if lengthens && !geminate {
+            if let Some(long) = output.chars().last().and_then(macron) {
+                output.pop();
+                output.push(long);
+            }
+            i += 1;
+            continue;
+        }
+
+        let (syllable, consumed) = match chars.get(i + 1).and_then(|&next| romaji_digraph(c, next))
+        {
+            Some(digraph) => (digraph, 2),
+            None if c == 'ん' => {
+                let next = chars
+                    .get(i + 1)
+                    .and_then(|&next| romaji_single(next))
+                    .unwrap_or_default();
+                let apostrophe = next.starts_with(['a', 'i', 'u', 'e', 'o', 'y']);
+                (if apostrophe { "n'" } else { "n" }.to_string(), 1)
+            }
+            None => (
+                romaji_single(c)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| c.to_string()),
+                1,
+            ),
+        };
+
+        if geminate {
+            if syllable.starts_with("ch") {
+                output.push('t');
+            } else if let Some(consonant) = syllable
+                .chars()
+                .next()
+                .filter(|c| c.is_ascii_alphabetic() && !"aiueo".contains(*c))
+            {
+                output.push(consonant);
+            }
+            geminate = false;
+        }
+        output.push_str(&syllable);
+        i += consumed;
+    }
+
+    output
+}
+
+/// 単語のローマ字（助詞の「は」「へ」は発音どおり"wa"・"e"とする）
+fn word_romaji(text: &str, reading: Option<&str>) -> String {
+    match text {
+        "は" => "wa".to_string(),
+        "へ" => "e".to_string(),
+        _ => kana_to_romaji(reading.unwrap_or(text)),
+    }
+}
+
+/// 単語ごとのローマ字を空白でつなげる（句読点・括弧の前後には空白を入れない）
+fn join_romaji(words: impl Iterator<Item = String>) -> String {
+    let mut output = String::new();
+    for word in words {
+        let word = word.trim();
+        if word.is_empty() {
+            continue;
+        }
+        let attaches_to_previous = word.starts_with(['.', ',', '!', '?', ')', '\u{201D}']);
+        if !output.is_empty() && !attaches_to_previous && !output.ends_with(['(', '\u{201C}']) {
+            output.push(' ');
+        }
+        output.push_str(word);
+    }
+    output
+}
+
+/// 単語の表記と読みから、送り仮名を除いたルビ付きの区間に分割する
+///
+/// 例: 「食べる」「たべる」→「食(た)」「べる」
+fn split_okurigana(text: &str, reading: &str) -> Vec<RubySegment> {
+    let text_chars: Vec<char> = text.chars().collect();
+    let reading_chars: Vec<char> = to_hiragana(reading).chars().collect();
+    let same_kana = |t: char, r: char| !is_kanji(t) && to_hiragana(&t.to_string()).starts_with(r);
+
+    let mut suffix = 0;
+    while suffix < text_chars.len()
+        && suffix < reading_chars.len()
+        && same_kana(
+            text_chars[text_chars.len() - 1 - suffix],
+            reading_chars[reading_chars.len() - 1 - suffix],
+        )
+    {
+        suffix += 1;
+    }
+    let mut prefix = 0;
+    while prefix < text_chars.len() - suffix
+        && prefix < reading_chars.len() - suffix
+        && same_kana(text_chars[prefix], reading_chars[prefix])
+    {
+        prefix += 1;
+    }
+
+    let middle_text: String = text_chars[prefix..text_chars.len() - suffix]
+        .iter()
+        .collect();
+    let middle_reading: String = reading_chars[prefix..reading_chars.len() - suffix]
+        .iter()
+        .collect();
+    if middle_text.is_empty() || middle_reading.is_empty() {
+        return vec![RubySegment {
+            text: text.to_string(),
+            reading: Some(to_hiragana(reading)),
+        }];
+    }
+
+    let plain = |chars: &[char]| -> Option<RubySegment> {
+        (!chars.is_empty()).then(|| RubySegment {
+            text: chars.iter().collect(),
+            reading: None,
+        })
+    };
+    plain(&text_chars[..prefix])
+        .into_iter()
+        .chain(std::iter::once(RubySegment {
+            text: middle_text,
+            reading: Some(middle_reading),
+        }))
+        .chain(plain(&text_chars[text_chars.len() - suffix..]))
+        .collect()
+}
+
+/// 単語（表記, ひらがなの読み）の列から読みの注釈を組み立てる
+///
+/// 読みがNoneの単語は日本語以外の文字列として扱う
+fn build_annotation(
+    words: &[(String, Option<String>)],
+    source: ReadingSource,
+) -> ReadingAnnotation {
+    let mut segments: Vec<RubySegment> = Vec::new();
+    for (text, reading) in words {
+        let split = match reading {
+            Some(reading) if contains_kanji(text) => split_okurigana(text, reading),
+            _ => vec![RubySegment {
+                text: text.clone(),
+                reading: None,
+            }],
+        };
+        for segment in split {
+            // 読みのない区間は直前の読みのない区間とまとめる
+            match segments.last_mut() {
+                Some(last) if last.reading.is_none() && segment.reading.is_none() => {
+                    last.text.push_str(&segment.text)
+                }
+                _ => segments.push(segment),
+            }
+        }
+    }
+
+    ReadingAnnotation {
+        segments,
+        romaji: join_romaji(
+            words
+                .iter()
+                .map(|(text, reading)| word_romaji(text, reading.as_deref())),
+        ),
+        source,
+    }
+}
+
+/// 漢字を含まないテキストの注釈（かなからローマ字に変換するのみ）
+pub fn annotate_kana(text: &str) -> ReadingAnnotation {
+    build_annotation(
+        &[(text.to_string(), Some(to_hiragana(text)))],
+        ReadingSource::Kana,
+    )
+}
+
+/// 辞書の見出しから、活用した表記の読みを求める
+///
+/// 例: 表記「食べた」、辞書形「食べる」（たべる）→「たべた」
+fn surface_reading(dictionary: &Dictionary, surface: &str) -> Option<String> {
+    let found = dictionary.lookup(surface).into_iter().next()?;
+    if !contains_kanji(surface) {
+        return Some(to_hiragana(surface));
+    }
+
+    let reading = found.entry.readings.first()?;
+    let common = surface
+        .chars()
+        .zip(found.base_form.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let base_rest: String = found.base_form.chars().skip(common).collect();
+    let surface_rest: String = surface.chars().skip(common).collect();
+    if contains_kanji(&base_rest) || contains_kanji(&surface_rest) {
+        return None;
+    }
+
+    let stem = to_hiragana(reading)
+        .strip_suffix(to_hiragana(&base_rest).as_str())?
+        .to_string();
+    Some(format!("{stem}{}", to_hiragana(&surface_rest)))
+}
+
+/// オフライン辞書で単語に分割して読みを付ける
+///
+/// 先頭から最長一致で辞書の見出し（活用形を含む）を探す。辞書にない漢字がある場合はNone
+pub fn annotate_with_dictionary(text: &str, dictionary: &Dictionary) -> Option<ReadingAnnotation> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words: Vec<(String, Option<String>)> = Vec::new();
+    let mut unknown_kana = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !dictionary::is_japanese_char(chars[i]) {
+            let end = (i..chars.len())
+                .find(|&j| dictionary::is_japanese_char(chars[j]))
+                .unwrap_or(chars.len());
+            words.push((chars[i..end].iter().collect(), None));
+            unknown_kana = false;
+            i = end;
+            continue;
+        }
+
+        let run = (i..chars.len())
+            .take_while(|&j| dictionary::is_japanese_char(chars[j]))
+            .count();
+        let found = (1..=run.min(MAX_WORD_CHARS)).rev().find_map(|len| {
+            let surface: String = chars[i..i + len].iter().collect();
+            surface_reading(dictionary, &surface).map(|reading| (len, surface, reading))
+        });
+
+        match found {
+            Some((len, surface, reading)) => {
+                words.push((surface, Some(reading)));
+                unknown_kana = false;
+                i += len;
+            }
+            None if is_kanji(chars[i]) => return None,
+            None => {
+                // 辞書にないかなは続くかなとまとめて1語とする
+                let kana = chars[i].to_string();
+                match words.last_mut() {
+                    Some((text, Some(reading))) if unknown_kana => {
+                        text.push_str(&kana);
+                        reading.push_str(&to_hiragana(&kana));
+                    }
+                    _ => words.push((kana.clone(), Some(to_hiragana(&kana)))),
+                }
+                unknown_kana = true;
+                i += 1;
+            }
+        }
+    }
+
+    Some(build_annotation(&words, ReadingSource::Dictionary))
+}
+
+/// 読み生成用のシステムプロンプト
+pub fn system_prompt() -> &'static str {
+    "あなたは日本語の読み仮名の専門家です。指定されたJSON形式でのみ出力してください。"
+}
+
+/// 読み生成用のプロンプトを構築
+pub fn build_reading_prompt(text: &str) -> String {
+    format!(
+        "次の日本語の文を単語に分割し、各単語の読みをひらがなで出力してください。\n\
+         JSONで出力し、\"segments\"に{{\"text\": 単語の表記, \"reading\": ひらがなの読み}}を文中の順に並べてください。\
+         表記をつなげると元の文と完全に一致するようにし、記号・英数字・空白の読みは空文字にしてください。\n\n文: {}",
+        text
+    )
+}
+
+/// 読み生成の構造化出力スキーマ（Ollamaの`format`パラメータ）
+pub fn reading_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "segments": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "text": { "type": "string" },
+                        "reading": { "type": "string" }
+                    },
+                    "required": ["text", "reading"]
+                }
+            }
+        },
+        "required": ["segments"]
+    })
+}
+
+/// モデルの出力から読みの注釈を組み立てる
+///
+/// 単語をつなげた表記が元のテキストと一致しない場合や、漢字を含む単語に読みがない場合はエラー
+pub fn build_llm_annotation(content: &str, text: &str) -> Result<ReadingAnnotation, ReadingError> {
+    let parsed: StructuredReading =
+        parse_json_object(content).ok_or(ReadingError::InvalidResponse)?;
+
+    let without_whitespace = |s: &str| s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    let joined: String = parsed.segments.iter().map(|w| w.text.as_str()).collect();
+    if without_whitespace(&joined) != without_whitespace(text) {
+        return Err(ReadingError::InvalidResponse);
+    }
+
+    let words = parsed
+        .segments
+        .into_iter()
+        .map(|word| {
+            let reading = to_hiragana(word.reading.trim());
+            if contains_kanji(&word.text) && reading.is_empty() {
+                return Err(ReadingError::InvalidResponse);
+            }
+            let reading = if !reading.is_empty() {
+                Some(reading)
+            } else if word.text.chars().any(dictionary::is_japanese_char) {
+                Some(to_hiragana(&word.text))
+            } else {
+                None
+            };
+            Ok((word.text, reading))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(build_annotation(&words, ReadingSource::Llm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::dictionary::parse_edict;
+
+    #[test]
+    fn test_kana_to_romaji() {
+        assert_eq!(kana_to_romaji("とうきょう"), "tōkyō");
+        assert_eq!(kana_to_romaji("がっこう"), "gakkō");
+        assert_eq!(kana_to_romaji("まっちゃ"), "matcha");
+        assert_eq!(kana_to_romaji("しんぶん"), "shinbun");
+        assert_eq!(kana_to_romaji("きんえん"), "kin'en");
+        assert_eq!(kana_to_romaji("コーヒー"), "kōhī");
+        assert_eq!(kana_to_romaji("パーティー"), "pātī");
+        assert_eq!(kana_to_romaji("ふつか。"), "futsuka.");
+        assert_eq!(kana_to_romaji("おおさか"), "ōsaka");
+        assert_eq!(kana_to_romaji("くうき"), "kūki");
+        assert_eq!(kana_to_romaji("せんせい"), "sensei");
+        assert_eq!(kana_to_romaji("ABC"), "ABC");
+    }
+
+    #[test]
+    fn test_split_okurigana() {
+        assert_eq!(
+            split_okurigana("食べる", "たべる"),
+            vec![
+                RubySegment {
+                    text: "食".to_string(),
+                    reading: Some("た".to_string()),
+                },
+                RubySegment {
+                    text: "べる".to_string(),
+                    reading: None,
+                },
+            ]
+        );
+        assert_eq!(
+            split_okurigana("お茶", "おちゃ"),
+            vec![
+                RubySegment {
+                    text: "お".to_string(),
+                    reading: None,
+                },
+                RubySegment {
+                    text: "茶".to_string(),
+                    reading: Some("ちゃ".to_string()),
+                },
+            ]
+        );
+        assert_eq!(
+            split_okurigana("日本", "にほん"),
+            vec![RubySegment {
+                text: "日本".to_string(),
+                reading: Some("にほん".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_annotate_kana() {
+        let annotation = annotate_kana("ありがとう");
+        assert_eq!(annotation.segments.len(), 1);
+        assert_eq!(annotation.segments[0].reading, None);
+        assert_eq!(annotation.romaji, "arigatō");
+        assert_eq!(annotation.source, ReadingSource::Kana);
+    }
+
+    #[test]
+    fn test_annotate_with_dictionary() {
+        let dictionary = Dictionary::new(parse_edict(
+            "私 [わたし] /(pn) I/\n\
+             は /(prt) topic marker particle/\n\
+             学生 [がくせい] /(n) student/\n\
+             食べる [たべる] /(v1,vt) to eat/\n\
+             寿司 [すし] /(n) sushi/\n\
+             を /(prt) object marker particle/\n",
+        ));
+
+        let annotation = annotate_with_dictionary("私は寿司を食べた。", &dictionary).unwrap();
+        assert_eq!(annotation.romaji, "watashi wa sushi o tabeta.");
+        assert_eq!(annotation.source, ReadingSource::Dictionary);
+        assert_eq!(
+            annotation
+                .segments
+                .iter()
+                .map(|s| (s.text.as_str(), s.reading.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("私", Some("わたし")),
+                ("は", None),
+                ("寿司", Some("すし")),
+                ("を", None),
+                ("食", Some("た")),
+                ("べた。", None),
+            ]
+        );
+
+        // 辞書にない漢字がある場合はNone
+        assert!(annotate_with_dictionary("私は猫", &dictionary).is_none());
+    }
+
+    #[test]
+    fn test_build_llm_annotation() {
+        let content = r#"{"segments": [
+            {"text": "東京", "reading": "とうきょう"},
+            {"text": "へ", "reading": "へ"},
+            {"text": "行く", "reading": "いく"},
+            {"text": "。", "reading": ""}
+        ]}"#;
+
+        let annotation = build_llm_annotation(content, "東京へ行く。").unwrap();
+        assert_eq!(annotation.romaji, "tōkyō e iku.");
+        assert_eq!(annotation.source, ReadingSource::Llm);
+        assert_eq!(
+            annotation.segments[0].reading.as_deref(),
+            Some("とうきょう")
+        );
+
+        // 表記が元のテキストと一致しない
+        assert!(matches!(
+            build_llm_annotation(content, "大阪へ行く。"),
+            Err(ReadingError::InvalidResponse)
+        ));
+        // 漢字に読みがない
+        assert!(matches!(
+            build_llm_annotation(r#"{"segments": [{"text": "東京", "reading": ""}]}"#, "東京"),
+            Err(ReadingError::InvalidResponse)
+        ));
+    }
+}
@@ -0,0 +1,427 @@
+//! 翻訳の改善（リファインメント）セッション
+//!
+//! 翻訳結果を見たあとの「もっと短く」「『デプロイ』ではなく『deploy』を使って」などの
+//! 追加指示に対応するため、元のプロンプト・出力・追加指示の会話履歴を保持する。
+//! セッションはメモリ上にIDをキーとして保存し、アプリの終了とともに破棄する。
+//! Claude CLIは単発の呼び出しのため、会話履歴を1つのプロンプトにまとめて渡す
+
+use crate::services::quality::QaWarning;
+use crate::services::translation::{Language, TranslationError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// 保持するセッションの最大数（超えた場合は最も古いセッションを破棄する）
+const MAX_SESSIONS: usize = 20;
+
+/// 会話メッセージの役割
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    /// ユーザー（翻訳依頼・追加指示）
+    User,
+    /// モデル（訳文）
+    Assistant,
+}
+
+/// 会話メッセージ（Ollamaのchat APIの形式）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+/// リファインメントセッション
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefinementSession {
+    /// セッションID
+    pub id: String,
+    /// 翻訳元テキスト
+    pub source_text: String,
+    /// 翻訳元言語
+    pub source_lang: Language,
+    /// 翻訳先言語
+    pub target_lang: Language,
+    /// 元の翻訳で使用したシステムプロンプト（Claude CLIのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// 会話履歴（元のプロンプト、訳文、追加指示、改訂版の訳文の順）
+    pub messages: Vec<ChatMessage>,
+    /// 作成順（古いセッションの破棄に使用）
+    #[serde(skip)]
+    sequence: u64,
+}
+
+impl RefinementSession {
+    /// 現在の訳文（最後のモデルの応答）
+    pub fn current_translation(&self) -> &str {
+        self.messages
+            .iter()
+            .rev()
+            .find(|message| message.role == ChatRole::Assistant)
+            .map(|message| message.content.as_str())
+            .unwrap_or_default()
+    }
+
+    /// 追加指示を会話履歴に追加する
+    ///
+    /// 修正後の訳文のみを出力するよう、翻訳先言語の指示を添える
+    pub fn push_instruction(&mut self, instruction: &str) {
+        let content = match self.target_lang {
+            Language::Japanese => format!(
+                "{}\n\n上記の指示に従って訳文を修正し、修正後の訳文のみを出力してください。",
+                instruction.trim()
+            ),
+            Language::English => format!(
+                "{}\n\nRevise the translation following the instruction above and output only the revised translation.",
+                instruction.trim()
+            ),
+        };
+        self.messages.push(ChatMessage {
+            role: ChatRole::User,
+            content,
+        });
+    }
+
+    /// 改訂版の訳文を会話履歴に追加する
+    pub fn push_revision(&mut self, translated_text: &str) {
+        self.messages.push(ChatMessage {
+            role: ChatRole::Assistant,
+            content: translated_text.to_string(),
+        });
+    }
+}
+
+/// リファインメント完了イベント
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefinementComplete {
+    pub session_id: String,
+    pub translated_text: String,
+    pub duration_ms: u64,
+    pub warnings: Vec<QaWarning>,
+}
+
+/// リファインメントエラー
+#[derive(Debug, Error)]
+pub enum RefinementError {
+    #[error("リファインメントセッションが見つかりません: {0}")]
+    SessionNotFound(String),
+    #[error("前の指示による訳文の修正が完了していません")]
+    InProgress,
+    #[error("翻訳時のプロンプトが指定されていません")]
+    PromptRequired,
+    #[error("{0}")]
+    TranslationFailed(#[from] TranslationError),
+}
+
+impl Serialize for RefinementError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// リファインメントセッションの保存先（Tauriの状態として管理する）
+#[derive(Debug, Default)]
+pub struct RefinementState {
+    sessions: Mutex<HashMap<String, RefinementSession>>,
+    next_sequence: AtomicU64,
+}
+
+impl RefinementState {
+    /// 元のプロンプトと訳文からセッションを作成して保存する
+    pub fn create(
+        &self,
+        source_text: &str,
+        source_lang: Language,
+        target_lang: Language,
+        system_prompt: Option<String>,
+        prompt: String,
+        translated_text: &str,
+    ) -> RefinementSession {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst) + 1;
+        let session = RefinementSession {
+            id: format!("refine-{}", sequence),
+            source_text: source_text.to_string(),
+            source_lang,
+            target_lang,
+            system_prompt,
+            messages: vec![
+                ChatMessage {
+                    role: ChatRole::User,
+                    content: prompt,
+                },
+                ChatMessage {
+                    role: ChatRole::Assistant,
+                    content: translated_text.to_string(),
+                },
+            ],
+            sequence,
+        };
+
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if sessions.len() >= MAX_SESSIONS {
+            let oldest = sessions
+                .values()
+                .min_by_key(|session| session.sequence)
+                .map(|session| session.id.clone());
+            if let Some(oldest) = oldest {
+                sessions.remove(&oldest);
+            }
+        }
+        sessions.insert(session.id.clone(), session.clone());
+
+        session
+    }
+
+    /// セッションを取得
+    pub fn get(&self, id: &str) -> Result<RefinementSession, RefinementError> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+            .cloned()
+            .ok_or_else(|| RefinementError::SessionNotFound(id.to_string()))
+    }
+
+    /// 追加指示をセッションの会話履歴に追加し、追加後のセッションを返す
+    ///
+    /// 取得・追加・保存を1回のロックで行う。前の指示による改訂版の生成中
+    /// （最後のメッセージが追加指示）の場合はエラーを返す
+    pub fn push_instruction(
+        &self,
+        id: &str,
+        instruction: &str,
+    ) -> Result<RefinementSession, RefinementError> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let session = sessions
+            .get_mut(id)
+            .ok_or_else(|| RefinementError::SessionNotFound(id.to_string()))?;
+
+        if session
+            .messages
+            .last()
+            .is_some_and(|message| message.role == ChatRole::User)
+        {
+            return Err(RefinementError::InProgress);
+        }
+
+        session.push_instruction(instruction);
+        Ok(session.clone())
+    }
+
+    /// 改訂版の訳文をセッションの会話履歴に追加する（破棄済みの場合は何もしない）
+    pub fn push_revision(&self, id: &str, translated_text: &str) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(session) = sessions.get_mut(id) {
+            session.push_revision(translated_text);
+        }
+    }
+
+    /// 改訂版の生成に失敗した追加指示を会話履歴から取り除く
+    pub fn discard_instruction(&self, id: &str) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(session) = sessions.get_mut(id) {
+            if session
+                .messages
+                .last()
+                .is_some_and(|message| message.role == ChatRole::User)
+            {
+                session.messages.pop();
+            }
+        }
+    }
+
+    /// セッションを破棄し、存在していたかどうかを返す
+    pub fn remove(&self, id: &str) -> bool {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id)
+            .is_some()
+    }
+}
+
+/// 改訂版の訳文を生成するシステムプロンプト（Claude CLI、翻訳先言語で記述）
+///
+/// 元の翻訳のシステムプロンプトがある場合は、翻訳方針・用語・文体を引き継ぐためそのまま使用し、
+/// 会話履歴の扱いの指示のみを追加する
+pub fn system_prompt(session: &RefinementSession) -> String {
+    let (role, instruction) = match session.target_lang {
+        Language::Japanese => (
+            "あなたはプロの翻訳者です。",
+            "これまでの翻訳の依頼・訳文・修正の指示のやり取りを踏まえ、最後の指示に従って修正した訳文のみを出力してください。",
+        ),
+        Language::English => (
+            "You are a professional translator. ",
+            "Based on the exchange of translation requests, translations, and revision instructions so far, output only the translation revised according to the last instruction.",
+        ),
+    };
+
+    match &session.system_prompt {
+        Some(original) => format!("{}\n\n{}", original.trim_end(), instruction),
+        None => format!("{}{}", role, instruction),
+    }
+}
+
+/// 会話履歴を1つのプロンプトにまとめる（Claude CLI用）
+///
+/// 依頼・指示と訳文を見出しで区切り、出現順に並べる
+pub fn build_refinement_prompt(session: &RefinementSession) -> String {
+    let (user_label, assistant_label) = match session.target_lang {
+        Language::Japanese => ("依頼", "訳文"),
+        Language::English => ("Request", "Translation"),
+    };
+
+    session
+        .messages
+        .iter()
+        .map(|message| {
+            let label = match message.role {
+                ChatRole::User => user_label,
+                ChatRole::Assistant => assistant_label,
+            };
+            format!("### {}\n{}", label, message.content.trim())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create(state: &RefinementState) -> RefinementSession {
+        state.create(
+            "Deploy the app.",
+            Language::English,
+            Language::Japanese,
+            None,
+            "以下の英文を日本語に翻訳してください:\nDeploy the app.".to_string(),
+            "アプリをデプロイしてください。",
+        )
+    }
+
+    #[test]
+    fn test_create_and_refine_session() {
+        let state = RefinementState::default();
+        let session = create(&state);
+        assert_eq!(session.id, "refine-1");
+        assert_eq!(
+            session.current_translation(),
+            "アプリをデプロイしてください。"
+        );
+
+        let session = state
+            .push_instruction(&session.id, "「デプロイ」ではなく「deploy」を使って")
+            .unwrap();
+        assert_eq!(session.messages.len(), 3);
+        assert!(session.messages[2]
+            .content
+            .starts_with("「デプロイ」ではなく「deploy」を使って\n\n上記の指示"));
+
+        // 改訂版の生成中は次の指示を受け付けない
+        assert!(matches!(
+            state.push_instruction(&session.id, "もっと短く"),
+            Err(RefinementError::InProgress)
+        ));
+
+        state.push_revision(&session.id, "アプリをdeployしてください。");
+
+        let stored = state.get("refine-1").unwrap();
+        assert_eq!(stored.messages.len(), 4);
+        assert_eq!(stored.current_translation(), "アプリをdeployしてください。");
+    }
+
+    #[test]
+    fn test_discard_instruction() {
+        let state = RefinementState::default();
+        let session = create(&state);
+
+        state.push_instruction(&session.id, "もっと短く").unwrap();
+        state.discard_instruction(&session.id);
+        assert_eq!(state.get(&session.id).unwrap().messages.len(), 2);
+
+        // 訳文で終わる会話履歴は変更しない
+        state.discard_instruction(&session.id);
+        assert_eq!(state.get(&session.id).unwrap().messages.len(), 2);
+        assert!(state.push_instruction(&session.id, "もっと短く").is_ok());
+    }
+
+    #[test]
+    fn test_build_refinement_prompt() {
+        let state = RefinementState::default();
+        let session = create(&state);
+        let session = state.push_instruction(&session.id, "もっと短く").unwrap();
+
+        let prompt = build_refinement_prompt(&session);
+        assert!(prompt.starts_with("### 依頼\n以下の英文を日本語に翻訳してください"));
+        assert!(
+            prompt.contains("\n\n### 訳文\nアプリをデプロイしてください。\n\n### 依頼\nもっと短く")
+        );
+    }
+
+    #[test]
+    fn test_system_prompt_keeps_original_translation_prompt() {
+        let state = RefinementState::default();
+        let session = create(&state);
+        assert!(system_prompt(&session).starts_with("あなたはプロの翻訳者です。これまでの"));
+
+        let session = state.create(
+            "Deploy the app.",
+            Language::English,
+            Language::Japanese,
+            Some("あなたは技術文書の翻訳者です。\n".to_string()),
+            "Deploy the app.".to_string(),
+            "アプリをデプロイしてください。",
+        );
+        assert_eq!(
+            system_prompt(&session),
+            "あなたは技術文書の翻訳者です。\n\nこれまでの翻訳の依頼・訳文・修正の指示のやり取りを踏まえ、最後の指示に従って修正した訳文のみを出力してください。"
+        );
+        assert_eq!(
+            build_refinement_prompt(&session),
+            "### 依頼\nDeploy the app.\n\n### 訳文\nアプリをデプロイしてください。"
+        );
+    }
+
+    #[test]
+    fn test_remove_and_missing_session() {
+        let state = RefinementState::default();
+        let session = create(&state);
+        assert!(state.remove(&session.id));
+        assert!(!state.remove(&session.id));
+        assert_eq!(
+            state.get(&session.id).unwrap_err().to_string(),
+            "リファインメントセッションが見つかりません: refine-1"
+        );
+    }
+
+    #[test]
+    fn test_oldest_session_is_evicted() {
+        let state = RefinementState::default();
+        for _ in 0..=MAX_SESSIONS {
+            create(&state);
+        }
+        assert!(state.get("refine-1").is_err());
+        assert!(state.get("refine-2").is_ok());
+    }
+
+    #[test]
+    fn test_chat_message_serialization() {
+        let message = ChatMessage {
+            role: ChatRole::Assistant,
+            content: "訳文".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"role":"assistant","content":"訳文"}"#
+        );
+    }
+}
@@ -0,0 +1,1438 @@
+//! リッチテキスト翻訳サービス
+//!
+//! HTML・Markdownを解析してテキスト部分のみを翻訳し、太字・リンク・リスト・表などの
+//! 書式を保持したまま再構築する。テキスト部分は文脈を保つため複数まとめて翻訳する
+
+use crate::services::translation::{Language, TranslationError};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::ops::Range;
+
+/// 1回の翻訳リクエストにまとめるテキストの最大文字数
+pub const DEFAULT_BATCH_CHARS: usize = 1500;
+
+/// 翻訳しないHTML要素（中身をそのまま残す）
+const HTML_SKIP_ELEMENTS: &[&str] = &["script", "style", "code", "pre", "textarea", "kbd", "samp"];
+
+/// プレーンテキスト変換時に改行を入れるHTML要素
+const HTML_BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "div",
+    "dl",
+    "dt",
+    "dd",
+    "figcaption",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// ブロック要素のほかに翻訳単位を区切るHTML要素
+const HTML_SEGMENT_ELEMENTS: &[&str] = &[
+    "body", "caption", "details", "fieldset", "figure", "form", "head", "html", "legend", "main",
+    "nav", "option", "summary", "tbody", "td", "tfoot", "th", "thead", "title",
+];
+
+/// リッチテキストの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RichTextFormat {
+    Html,
+    Markdown,
+}
+
+/// リッチテキスト翻訳結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RichTranslationResult {
+    /// ドキュメントの形式
+    pub format: RichTextFormat,
+    /// 翻訳後のドキュメント（HTMLまたはMarkdown）
+    pub translated_content: String,
+    /// 翻訳後のプレーンテキスト（クリップボードの代替テキスト用）
+    pub plain_text: String,
+    /// 翻訳元言語
+    pub source_lang: Language,
+    /// 翻訳先言語
+    pub target_lang: Language,
+    /// 翻訳にかかった時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// ドキュメントの構成要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Part {
+    /// そのまま出力する部分（タグ・記号・コードなど）
+    Raw(String),
+    /// 翻訳対象のテキスト
+    Text(String),
+    /// 複数行をまとめた翻訳対象のテキスト（翻訳後に元の行数で改行し直す）
+    Lines {
+        /// 各行を空白でつなげたテキスト
+        text: String,
+        /// 元の各行の文字数
+        line_lengths: Vec<usize>,
+        /// 改行文字
+        newline: String,
+    },
+    /// インライン要素を含む翻訳対象のテキスト（HTML）
+    Inline {
+        /// インライン要素を番号付きトークン（`⟪n⟫`）に置き換えたテキスト
+        text: String,
+        /// トークンに対応するタグ
+        tags: Vec<String>,
+    },
+}
+
+/// 解析済みドキュメント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RichDocument {
+    format: RichTextFormat,
+    parts: Vec<Part>,
+}
+
+impl RichDocument {
+    /// ドキュメントを解析する
+    pub fn parse(content: &str, format: RichTextFormat) -> Self {
+        let mut parts = Vec::new();
+        match format {
+            RichTextFormat::Html => parse_html(content, &mut parts),
+            RichTextFormat::Markdown => parse_markdown(content, &mut parts),
+        }
+        Self { format, parts }
+    }
+
+    /// 構成要素からドキュメントを作成する（ファイル翻訳用）
+    pub(crate) fn from_parts(format: RichTextFormat, parts: Vec<Part>) -> Self {
+        Self { format, parts }
+    }
+
+    /// 翻訳対象のテキストを出現順に取得
+    pub fn segments(&self) -> Vec<String> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Text(text) | Part::Lines { text, .. } | Part::Inline { text, .. } => {
+                    Some(text.clone())
+                }
+                Part::Raw(_) => None,
+            })
+            .collect()
+    }
+
+    /// 翻訳済みテキストでドキュメントを再構築する
+    ///
+    /// 翻訳が不足している、または空の場合は元のテキストを使用する
+    pub fn reassemble(&self, translations: &[String]) -> String {
+        let mut translations = translations.iter();
+        let mut output = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Raw(raw) => output.push_str(raw),
+                Part::Inline { text, tags } => {
+                    // トークンを復元できない翻訳は書式が崩れるため元のテキストを使用する
+                    let restored = translations
+                        .next()
+                        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+                        .filter(|t| !t.is_empty())
+                        .and_then(|t| restore_markup(&t, tags));
+                    match restored {
+                        Some(restored) => output.push_str(&restored),
+                        None => output.push_str(&restore_markup(text, tags).unwrap_or_default()),
+                    }
+                }
+                Part::Text(original) | Part::Lines { text: original, .. } => {
+                    // 改行が入ると書式が崩れるため1行にまとめる
+                    let text = translations
+                        .next()
+                        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+                        .filter(|t| !t.is_empty())
+                        .unwrap_or_else(|| original.clone());
+                    // 複数行のテキストは元の行数に合わせて改行し直す
+                    let text = match part {
+                        Part::Lines {
+                            line_lengths,
+                            newline,
+                            ..
+                        } => wrap_lines(&text, line_lengths, newline),
+                        _ => text,
+                    };
+
+                    match self.format {
+                        RichTextFormat::Html => output.push_str(&escape_html(&text)),
+                        RichTextFormat::Markdown => output.push_str(&text),
+                    }
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// 直前がRawであれば連結してRawを追加
+pub(crate) fn push_raw(parts: &mut Vec<Part>, raw: &str) {
+    if raw.is_empty() {
+        return;
+    }
+    if let Some(Part::Raw(last)) = parts.last_mut() {
+        last.push_str(raw);
+    } else {
+        parts.push(Part::Raw(raw.to_string()));
+    }
+}
+
+/// 前後の空白をRawとして分離し、テキストを追加する
+///
+/// 文字を含まない部分（数字・記号のみ）は翻訳不要のためRawとして扱う
+pub(crate) fn push_text(parts: &mut Vec<Part>, raw: &str, decode: impl Fn(&str) -> String) {
+    let start = raw.len() - raw.trim_start().len();
+    let end = raw.trim_end().len().max(start);
+    let body = &raw[start..end];
+
+    if !body.chars().any(char::is_alphabetic) {
+        push_raw(parts, raw);
+        return;
+    }
+
+    push_raw(parts, &raw[..start]);
+    parts.push(Part::Text(decode(body)));
+    push_raw(parts, &raw[end..]);
+}
+
+/// 複数行のテキストを1つの翻訳対象として追加する
+///
+/// 各行を空白でつなげて翻訳し、再構築時に元の行数で改行し直す。
+/// 文字を含まない場合は翻訳不要のためRawとして扱う
+pub(crate) fn push_lines(parts: &mut Vec<Part>, lines: &[&str], newline: &str) {
+    if lines.len() <= 1 {
+        push_text(parts, &lines.concat(), str::to_string);
+        return;
+    }
+    let text = lines
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !text.chars().any(char::is_alphabetic) {
+        push_raw(parts, &lines.join(newline));
+        return;
+    }
+
+    parts.push(Part::Lines {
+        text,
+        line_lengths: lines
+            .iter()
+            .map(|line| line.trim().chars().count())
+            .collect(),
+        newline: newline.to_string(),
+    });
+}
+
+/// 改行位置として優先する、直前の単語の末尾の句読点
+const BREAK_AFTER: [char; 15] = [
+    '、', '。', '，', '．', '！', '？', '」', '』', '）', ',', '.', '!', '?', ';', ':',
+];
+
+/// 訳文を元の行数で改行する
+///
+/// 元の各行の文字数の比率に近い位置を、単語（空白で区切らない言語は文字）の境界から選ぶ。
+/// 句読点の直後を優先する。空行は字幕のキューを区切ってしまうため、単語数が行数より少ない場合
+/// （空白で区切らない言語は1行2文字に満たない場合）は行数を減らす
+pub(crate) fn wrap_lines(text: &str, line_lengths: &[usize], newline: &str) -> String {
+    let spaced = text.contains(char::is_whitespace);
+    let separator = if spaced { " " } else { "" };
+    let tokens: Vec<String> = if spaced {
+        text.split_whitespace().map(str::to_string).collect()
+    } else {
+        let mut tokens: Vec<String> = Vec::new();
+        for c in text.chars() {
+            match tokens.last_mut() {
+                // 句読点・閉じ括弧は行頭に来ないよう直前の文字とまとめる
+                Some(last) if BREAK_AFTER.contains(&c) => last.push(c),
+                // 英数字の連続は1単語として扱う
+                Some(last)
+                    if c.is_ascii_alphanumeric()
+                        && last.ends_with(|p: char| p.is_ascii_alphanumeric()) =>
+                {
+                    last.push(c)
+                }
+                _ => tokens.push(c.to_string()),
+            }
+        }
+        tokens
+    };
+
+    let max_lines = if spaced {
+        tokens.len()
+    } else {
+        tokens.len() / 2
+    };
+    let lines = line_lengths.len().min(max_lines);
+    if lines <= 1 {
+        return tokens.join(separator);
+    }
+
+    // 各単語より前の文字数
+    let mut offsets = vec![0];
+    for token in &tokens {
+        offsets.push(offsets[offsets.len() - 1] + token.chars().count());
+    }
+    let total = offsets[tokens.len()];
+    let weight_total = line_lengths[..lines].iter().sum::<usize>().max(1);
+    let bonus = total / lines / 3;
+
+    let mut breaks = Vec::new();
+    let mut previous = 0;
+    let mut weight = 0;
+    for line in 1..lines {
+        weight += line_lengths[line - 1];
+        let target = total * weight / weight_total;
+        // 残りの各行に少なくとも1単語を残す
+        let split = (previous + 1..=tokens.len() - (lines - line))
+            .min_by_key(|&j| {
+                let cost = offsets[j].abs_diff(target);
+                if tokens[j - 1].ends_with(BREAK_AFTER) {
+                    (cost.saturating_sub(bonus), false)
+                } else {
+                    (cost, true)
+                }
+            })
+            .unwrap_or(previous + 1);
+        breaks.push(split);
+        previous = split;
+    }
+    breaks.push(tokens.len());
+
+    let mut start = 0;
+    breaks
+        .into_iter()
+        .map(|end| {
+            let line = tokens[start..end].join(separator);
+            start = end;
+            line
+        })
+        .collect::<Vec<_>>()
+        .join(newline)
+}
+
+// ============================================================================
+// HTML
+// ============================================================================
+
+/// 文字列の先頭がタグ・コメントの場合、その長さを返す
+pub(crate) fn markup_len(s: &str) -> Option<usize> {
+    if s.starts_with("<!--") {
+        return Some(s.find("-->").map(|pos| pos + 3).unwrap_or(s.len()));
+    }
+
+    let mut chars = s.chars();
+    if chars.next() != Some('<') {
+        return None;
+    }
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?') => {}
+        _ => return None,
+    }
+
+    // 属性値内の'>'を考慮して閉じ括弧を探す
+    let mut quote: Option<char> = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// 次のタグ・コメントの開始位置を返す
+fn next_markup_start(s: &str) -> usize {
+    s.char_indices()
+        .skip(1)
+        .find(|&(i, c)| c == '<' && markup_len(&s[i..]).is_some())
+        .map(|(i, _)| i)
+        .unwrap_or(s.len())
+}
+
+/// タグ名（小文字）と、閉じタグ・自己終了タグかどうかを取得
+fn parse_tag(markup: &str) -> Option<(String, bool, bool)> {
+    let inner = markup.strip_prefix('<')?;
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, inner),
+    };
+
+    let name: String = inner
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((name, closing, markup.ends_with("/>")))
+}
+
+/// ブロック要素の間のテキストの構成要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InlinePiece {
+    /// テキスト（エンティティはデコード前）
+    Text(String),
+    /// インライン要素のタグ、または翻訳しない要素全体
+    Markup(String),
+}
+
+/// 翻訳単位を区切る要素かどうか
+fn is_segment_boundary(name: &str) -> bool {
+    HTML_BLOCK_ELEMENTS.contains(&name) || HTML_SEGMENT_ELEMENTS.contains(&name)
+}
+
+/// ブロック要素の間のテキストを1つの翻訳対象として追加する
+///
+/// インライン要素はトークンに置き換えて文の途中に残し、再構築時に元のタグへ戻す。
+/// 先頭・末尾のタグと空白、文字を含まないテキストはRawとして分離する
+fn push_inline_run(parts: &mut Vec<Part>, run: &mut Vec<InlinePiece>) {
+    let pieces = std::mem::take(run);
+    let has_letters = |piece: &InlinePiece| matches!(piece, InlinePiece::Text(t) if t.chars().any(char::is_alphabetic));
+
+    let (Some(first), Some(last)) = (
+        pieces.iter().position(has_letters),
+        pieces.iter().rposition(has_letters),
+    ) else {
+        for piece in &pieces {
+            match piece {
+                InlinePiece::Text(text) | InlinePiece::Markup(text) => push_raw(parts, text),
+            }
+        }
+        return;
+    };
+
+    for piece in &pieces[..first] {
+        match piece {
+            InlinePiece::Text(text) | InlinePiece::Markup(text) => push_raw(parts, text),
+        }
+    }
+
+    let mut masked = String::new();
+    let mut tags = Vec::new();
+    for piece in &pieces[first..=last] {
+        match piece {
+            InlinePiece::Text(text) => masked.push_str(text),
+            InlinePiece::Markup(markup) => {
+                masked.push_str(&format!("⟪{}⟫", tags.len()));
+                tags.push(markup.clone());
+            }
+        }
+    }
+
+    // 前後の空白はRawとして残す
+    let start = masked.len() - masked.trim_start().len();
+    let end = masked.trim_end().len();
+    push_raw(parts, &masked[..start]);
+    let text = decode_entities(&masked[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if tags.is_empty() {
+        parts.push(Part::Text(text));
+    } else {
+        parts.push(Part::Inline { text, tags });
+    }
+    push_raw(parts, &masked[end..]);
+
+    for piece in &pieces[last + 1..] {
+        match piece {
+            InlinePiece::Text(text) | InlinePiece::Markup(text) => push_raw(parts, text),
+        }
+    }
+}
+
+/// 翻訳結果のトークンを元のタグに戻し、テキスト部分をエスケープする
+///
+/// トークンの欠落・重複がある場合はNone
+fn restore_markup(translated: &str, tags: &[String]) -> Option<String> {
+    let mut output = String::new();
+    let mut used = vec![0usize; tags.len()];
+    let mut rest = translated;
+
+    while let Some(start) = rest.find('⟪') {
+        output.push_str(&escape_html(&rest[..start]));
+        let token = &rest[start + '⟪'.len_utf8()..];
+        let end = token.find('⟫')?;
+        let index: usize = token[..end].trim().parse().ok()?;
+        output.push_str(tags.get(index)?);
+        *used.get_mut(index)? += 1;
+        rest = &token[end + '⟫'.len_utf8()..];
+    }
+    output.push_str(&escape_html(rest));
+
+    used.iter().all(|&count| count == 1).then_some(output)
+}
+
+/// HTMLを解析してタグとテキストに分割
+///
+/// ブロック要素の間のテキストを1つの翻訳対象とし、太字・リンクなどのインライン要素は
+/// 文を分断しないようトークンとして翻訳対象の中に残す
+fn parse_html(html: &str, parts: &mut Vec<Part>) {
+    // 翻訳しない要素の中にいる場合は要素名とネストの深さ
+    let mut skip: Option<(String, usize)> = None;
+    // 翻訳しない要素がインラインの場合は、要素全体をまとめたタグ（ブロックの場合は空）
+    let mut skipped = String::new();
+    let mut run: Vec<InlinePiece> = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let (len, is_markup) = match markup_len(rest) {
+            Some(len) => (len, true),
+            None => (next_markup_start(rest), false),
+        };
+        let piece = &rest[..len];
+        rest = &rest[len..];
+
+        let tag = if is_markup { parse_tag(piece) } else { None };
+
+        if let Some((skip_name, depth)) = &mut skip {
+            if let Some((name, closing, self_closing)) = &tag {
+                if name == skip_name {
+                    if *closing {
+                        *depth -= 1;
+                    } else if !*self_closing {
+                        *depth += 1;
+                    }
+                }
+            }
+            let finished = *depth == 0;
+
+            if skipped.is_empty() {
+                push_raw(parts, piece);
+            } else {
+                skipped.push_str(piece);
+            }
+            if finished {
+                skip = None;
+                if !skipped.is_empty() {
+                    run.push(InlinePiece::Markup(std::mem::take(&mut skipped)));
+                }
+            }
+            continue;
+        }
+
+        let Some((name, closing, self_closing)) = tag else {
+            run.push(if is_markup {
+                InlinePiece::Markup(piece.to_string())
+            } else {
+                InlinePiece::Text(piece.to_string())
+            });
+            continue;
+        };
+
+        let boundary = is_segment_boundary(&name);
+        if boundary {
+            push_inline_run(parts, &mut run);
+        }
+
+        if !closing && !self_closing && HTML_SKIP_ELEMENTS.contains(&name.as_str()) {
+            skip = Some((name, 1));
+            // ブロックの翻訳しない要素はそのまま、インラインの場合は要素全体を1つのタグとして残す
+            if boundary {
+                push_raw(parts, piece);
+            } else {
+                skipped.push_str(piece);
+            }
+        } else if boundary {
+            push_raw(parts, piece);
+        } else {
+            run.push(InlinePiece::Markup(piece.to_string()));
+        }
+    }
+
+    if !skipped.is_empty() {
+        run.push(InlinePiece::Markup(skipped));
+    }
+    push_inline_run(parts, &mut run);
+}
+
+/// HTMLエンティティをデコード
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(pos) = rest.find('&') {
+        output.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    if let Some(hex) = entity
+                        .strip_prefix("#x")
+                        .or_else(|| entity.strip_prefix("#X"))
+                    {
+                        u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                    } else if let Some(dec) = entity.strip_prefix('#') {
+                        dec.parse::<u32>().ok().and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                }
+            };
+            c.map(|c| (c, end + 1))
+        });
+
+        match decoded {
+            Some((c, len)) => {
+                output.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// テキストをHTML用にエスケープ
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// 末尾が改行でなければ改行を追加
+fn ensure_newline(output: &mut String) {
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+}
+
+/// HTMLをプレーンテキストに変換する
+///
+/// ブロック要素と`<br>`は改行、リスト項目は「- 」、表のセルはタブ区切りにする
+pub fn html_to_plain_text(html: &str) -> String {
+    let mut output = String::new();
+    let mut hidden_depth = 0usize;
+    let mut pre_depth = 0usize;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(len) = markup_len(rest) {
+            if let Some((name, closing, self_closing)) = parse_tag(&rest[..len]) {
+                match name.as_str() {
+                    "script" | "style" if !self_closing => {
+                        if closing {
+                            hidden_depth = hidden_depth.saturating_sub(1);
+                        } else {
+                            hidden_depth += 1;
+                        }
+                    }
+                    "br" => output.push('\n'),
+                    // 行頭以外のセルはタブで区切る
+                    "td" | "th" if !closing && !output.is_empty() && !output.ends_with('\n') => {
+                        output.push('\t');
+                    }
+                    _ if HTML_BLOCK_ELEMENTS.contains(&name.as_str()) => {
+                        ensure_newline(&mut output);
+                        if name == "pre" && !self_closing {
+                            if closing {
+                                pre_depth = pre_depth.saturating_sub(1);
+                            } else {
+                                pre_depth += 1;
+                            }
+                        }
+                        if name == "li" && !closing {
+                            output.push_str("- ");
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            rest = &rest[len..];
+        } else {
+            let end = next_markup_start(rest);
+            let text = decode_entities(&rest[..end]).replace('\u{a0}', " ");
+
+            if hidden_depth == 0 {
+                if pre_depth > 0 {
+                    output.push_str(&text);
+                } else {
+                    // 連続する空白を1つにまとめ、行頭の空白は除去する
+                    let mut previous_space =
+                        output.is_empty() || output.ends_with(['\n', ' ', '\t']);
+                    for c in text.chars() {
+                        if c.is_whitespace() {
+                            if !previous_space {
+                                output.push(' ');
+                                previous_space = true;
+                            }
+                        } else {
+                            output.push(c);
+                            previous_space = false;
+                        }
+                    }
+                }
+            }
+
+            rest = &rest[end..];
+        }
+    }
+
+    // 行末の空白と連続する空行を除去
+    let mut lines: Vec<&str> = Vec::new();
+    for line in output.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+// ============================================================================
+// Markdown
+// ============================================================================
+
+/// 水平線（`---`、`***`、`___`）かどうか
+fn is_thematic_break(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && matches!(chars[0], '-' | '*' | '_') && chars.iter().all(|&c| c == chars[0])
+}
+
+/// 表の区切り行（`|---|:---:|`）かどうか
+fn is_table_separator(line: &str) -> bool {
+    line.contains('|')
+        && line.contains('-')
+        && line
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+/// 行頭のブロック記号（インデント・引用・見出し・リスト・タスク）の長さを返す
+fn block_prefix_len(line: &str) -> usize {
+    let bytes = line.as_bytes();
+    let mut pos = 0;
+
+    let skip_spaces = |pos: &mut usize| {
+        while *pos < bytes.len() && (bytes[*pos] == b' ' || bytes[*pos] == b'\t') {
+            *pos += 1;
+        }
+    };
+
+    // 引用（ネスト可）
+    loop {
+        skip_spaces(&mut pos);
+        if pos < bytes.len() && bytes[pos] == b'>' {
+            pos += 1;
+        } else {
+            break;
+        }
+    }
+
+    // 見出し
+    let hashes = bytes[pos..].iter().take_while(|&&b| b == b'#').count();
+    if (1..=6).contains(&hashes) && matches!(bytes.get(pos + hashes), Some(b' ') | None) {
+        pos += hashes;
+        skip_spaces(&mut pos);
+        return pos;
+    }
+
+    // 箇条書き
+    if matches!(bytes.get(pos), Some(b'-' | b'*' | b'+')) && bytes.get(pos + 1) == Some(&b' ') {
+        pos += 1;
+    } else {
+        // 番号付きリスト
+        let digits = bytes[pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if (1..=9).contains(&digits)
+            && matches!(bytes.get(pos + digits), Some(b'.' | b')'))
+            && bytes.get(pos + digits + 1) == Some(&b' ')
+        {
+            pos += digits + 1;
+        } else {
+            return pos;
+        }
+    }
+    skip_spaces(&mut pos);
+
+    // タスクリスト
+    for task in ["[ ] ", "[x] ", "[X] "] {
+        if line[pos..].starts_with(task) {
+            pos += task.len();
+            break;
+        }
+    }
+
+    pos
+}
+
+/// バッククォートで囲まれたインラインコードの長さを返す
+fn code_span_len(s: &str) -> Option<usize> {
+    let ticks = s.chars().take_while(|&c| c == '`').count();
+    let fence = &s[..ticks];
+    s[ticks..].find(fence).map(|pos| ticks + pos + ticks)
+}
+
+/// `[text](url)`形式のリンクを解析し、(テキスト部分の範囲, 全体の長さ)を返す
+fn link_len(s: &str) -> Option<(Range<usize>, usize)> {
+    let close = s.find("](")?;
+    let text = &s[1..close];
+    if text.contains(['[', '\n']) {
+        return None;
+    }
+    let url_end = s[close + 2..].find(')')?;
+    Some((1..close, close + 2 + url_end + 1))
+}
+
+/// URLの長さを返す（空白まで）
+fn bare_url_len(s: &str) -> Option<usize> {
+    if s.starts_with("http://") || s.starts_with("https://") {
+        Some(s.find(char::is_whitespace).unwrap_or(s.len()))
+    } else {
+        None
+    }
+}
+
+/// 行内のテキストを解析し、インラインコード・リンク先・画像・HTMLタグ・URLを保持する
+fn parse_markdown_inline(text: &str, parts: &mut Vec<Part>) {
+    let mut buffer_start = 0;
+    let mut pos = 0;
+
+    let flush = |parts: &mut Vec<Part>, start: usize, end: usize| {
+        push_text(parts, &text[start..end], str::to_string);
+    };
+
+    while pos < text.len() {
+        let rest = &text[pos..];
+
+        // (Rawとして残す長さ, リンクテキストの範囲)
+        let matched: Option<(usize, Option<Range<usize>>)> = if rest.starts_with('`') {
+            code_span_len(rest).map(|len| (len, None))
+        } else if rest.starts_with("![") {
+            link_len(&rest[1..]).map(|(_, len)| (len + 1, None))
+        } else if rest.starts_with('[') {
+            link_len(rest).map(|(range, len)| (len, Some(range)))
+        } else if rest.starts_with('<') {
+            markup_len(rest).map(|len| (len, None))
+        } else {
+            bare_url_len(rest).map(|len| (len, None))
+        };
+
+        match matched {
+            Some((len, link_text)) => {
+                flush(parts, buffer_start, pos);
+                match link_text {
+                    Some(range) => {
+                        push_raw(parts, &rest[..range.start]);
+                        push_text(parts, &rest[range.clone()], str::to_string);
+                        push_raw(parts, &rest[range.end..len]);
+                    }
+                    None => push_raw(parts, &rest[..len]),
+                }
+                pos += len;
+                buffer_start = pos;
+            }
+            None => {
+                pos += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+            }
+        }
+    }
+
+    flush(parts, buffer_start, text.len());
+}
+
+/// 段落の行（ブロック記号を持たない通常のテキスト行）かどうか
+fn is_paragraph_line(body: &str) -> bool {
+    let trimmed = body.trim_start();
+    !trimmed.is_empty()
+        && !trimmed.starts_with("```")
+        && !trimmed.starts_with("~~~")
+        && !trimmed.starts_with('|')
+        && !is_thematic_break(trimmed)
+        && !is_table_separator(trimmed)
+        // 見出しの下線（`===`）
+        && !trimmed.trim_end().chars().all(|c| c == '=')
+        && block_prefix_len(body) == body.len() - trimmed.len()
+}
+
+/// 段落の行をまとめて翻訳対象として追加する
+///
+/// 段落内の折り返しは表示上は空白と同じため、行をつなげて1文として翻訳する。
+/// インライン要素を含まない段落は元の行数で改行し直し、含む場合はインライン要素の前後で分割する
+fn flush_markdown_paragraph(parts: &mut Vec<Part>, lines: &mut Vec<&str>, newline: &str) {
+    if lines.is_empty() {
+        return;
+    }
+
+    let mut inline = Vec::new();
+    parse_markdown_inline(&lines.join(newline), &mut inline);
+    let plain = inline
+        .iter()
+        .filter(|part| !matches!(part, Part::Raw(raw) if raw.trim().is_empty()))
+        .count()
+        == 1
+        && inline.iter().any(|part| matches!(part, Part::Text(_)));
+
+    if lines.len() > 1 && plain {
+        let first = lines[0];
+        let last = lines[lines.len() - 1];
+        push_raw(parts, &first[..first.len() - first.trim_start().len()]);
+        push_lines(parts, lines, newline);
+        // 行末の空白2つによる改行を残す
+        push_raw(parts, &last[last.trim_end().len()..]);
+    } else {
+        for part in inline {
+            match part {
+                Part::Raw(raw) => push_raw(parts, &raw),
+                Part::Text(text) => {
+                    parts.push(Part::Text(
+                        text.split_whitespace().collect::<Vec<_>>().join(" "),
+                    ));
+                }
+                other => parts.push(other),
+            }
+        }
+    }
+
+    push_raw(parts, newline);
+    lines.clear();
+}
+
+/// Markdownを解析してブロック記号・コードとテキストに分割
+pub(crate) fn parse_markdown(markdown: &str, parts: &mut Vec<Part>) {
+    // コードフェンス内の場合はフェンス記号
+    let mut fence: Option<&str> = None;
+    // 翻訳前にまとめる段落の行と改行文字
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut paragraph_newline = "";
+
+    for line in markdown.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let newline = &line[body.len()..];
+        let trimmed = body.trim_start();
+
+        if fence.is_none() && is_paragraph_line(body) {
+            paragraph.push(body);
+            paragraph_newline = newline;
+            // 行末の空白2つは改行のため、段落をここで区切る
+            if body.ends_with("  ") {
+                flush_markdown_paragraph(parts, &mut paragraph, paragraph_newline);
+            }
+            continue;
+        }
+        flush_markdown_paragraph(parts, &mut paragraph, paragraph_newline);
+
+        if let Some(marker) = fence {
+            push_raw(parts, line);
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            push_raw(parts, line);
+            continue;
+        }
+
+        if trimmed.is_empty() || is_thematic_break(trimmed) || is_table_separator(trimmed) {
+            push_raw(parts, line);
+            continue;
+        }
+
+        if trimmed.starts_with('|') {
+            // 表の行はセルごとに翻訳する
+            for (i, cell) in body.split('|').enumerate() {
+                if i > 0 {
+                    push_raw(parts, "|");
+                }
+                parse_markdown_inline(cell, parts);
+            }
+        } else {
+            let prefix = block_prefix_len(body);
+            push_raw(parts, &body[..prefix]);
+            parse_markdown_inline(&body[prefix..], parts);
+        }
+
+        push_raw(parts, newline);
+    }
+
+    flush_markdown_paragraph(parts, &mut paragraph, paragraph_newline);
+}
+
+// ============================================================================
+// バッチ翻訳
+// ============================================================================
+
+/// 翻訳対象テキストを最大文字数ごとのバッチに分割する
+fn build_batches(segments: &[String], max_chars: usize) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut chars = 0;
+
+    for (i, segment) in segments.iter().enumerate() {
+        let len = segment.chars().count();
+        if i > start && chars + len > max_chars {
+            batches.push(start..i);
+            start = i;
+            chars = 0;
+        }
+        chars += len;
+    }
+
+    if start < segments.len() {
+        batches.push(start..segments.len());
+    }
+
+    batches
+}
+
+/// バッチ内の各テキストに番号マーカーを付けて1つのテキストにまとめる
+fn format_batch(segments: &[String]) -> String {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| format!("⟦{}⟧ {}", i + 1, segment))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 番号マーカー付きの翻訳結果を分割する
+///
+/// マーカーが欠けている、または空の翻訳がある場合はNone
+fn parse_batch(response: &str, expected: usize) -> Option<Vec<String>> {
+    let mut results = Vec::with_capacity(expected);
+    let mut rest = response;
+
+    for n in 1..=expected {
+        let marker = format!("⟦{}⟧", n);
+        let start = rest.find(&marker)?;
+        if n > 1 {
+            results.push(rest[..start].trim().to_string());
+        }
+        rest = &rest[start + marker.len()..];
+    }
+    results.push(rest.trim().to_string());
+
+    if results.iter().any(String::is_empty) {
+        return None;
+    }
+
+    Some(results)
+}
+
+/// テキストをバッチごとに翻訳する
+///
+/// 番号マーカーで区切った複数のテキストを1回で翻訳し、前後の文脈を保つ。
+/// 翻訳結果を分割できなかったバッチは1つずつ翻訳し直す
+pub async fn translate_segments<F, Fut>(
+    segments: &[String],
+    max_batch_chars: usize,
+    translate: F,
+) -> Result<Vec<String>, TranslationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    translate_segments_with_progress(segments, max_batch_chars, translate, |_, _| {}).await
+}
+
+/// テキストをバッチごとに翻訳し、バッチが完了するたびに進捗を通知する
+///
+/// `on_progress`には翻訳済みのテキスト数と、完了したバッチの翻訳結果が渡される
+pub async fn translate_segments_with_progress<F, Fut, P>(
+    segments: &[String],
+    max_batch_chars: usize,
+    mut translate: F,
+    mut on_progress: P,
+) -> Result<Vec<String>, TranslationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+    P: FnMut(usize, &[String]),
+{
+    let mut translated = Vec::with_capacity(segments.len());
+
+    for batch in build_batches(segments, max_batch_chars) {
+        let items = &segments[batch];
+        let batch_start = translated.len();
+
+        if items.len() == 1 {
+            translated.push(translate(items[0].clone()).await?);
+        } else {
+            let response = translate(format_batch(items)).await?;
+            match parse_batch(&response, items.len()) {
+                Some(results) => translated.extend(results),
+                None => {
+                    for item in items {
+                        translated.push(translate(item.clone()).await?);
+                    }
+                }
+            }
+        }
+
+        on_progress(translated.len(), &translated[batch_start..]);
+    }
+
+    Ok(translated)
+}
+
+/// HTML・Markdownを書式を保持したまま翻訳する
+///
+/// # Arguments
+/// * `content` - 翻訳するHTMLまたはMarkdown
+/// * `format` - ドキュメントの形式
+/// * `translate` - テキストを翻訳する関数（プロバイダーの翻訳処理）
+pub async fn translate_document<F, Fut>(
+    content: &str,
+    format: RichTextFormat,
+    source_lang: Language,
+    target_lang: Language,
+    translate: F,
+) -> Result<RichTranslationResult, TranslationError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, TranslationError>>,
+{
+    let start = std::time::Instant::now();
+
+    let document = RichDocument::parse(content, format);
+    let translations =
+        translate_segments(&document.segments(), DEFAULT_BATCH_CHARS, translate).await?;
+    let translated_content = document.reassemble(&translations);
+
+    let plain_text = match format {
+        RichTextFormat::Html => html_to_plain_text(&translated_content),
+        RichTextFormat::Markdown => translated_content.clone(),
+    };
+
+    Ok(RichTranslationResult {
+        format,
+        translated_content,
+        plain_text,
+        source_lang,
+        target_lang,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テキストを大文字にする疑似翻訳
+    fn upper(segments: &[String]) -> Vec<String> {
+        segments.iter().map(|s| s.to_uppercase()).collect()
+    }
+
+    #[test]
+    fn test_html_segments_and_reassemble() {
+        let html = r#"<p>Hello <b>bold</b> and <a href="https://example.com">link</a>.</p>"#;
+        let document = RichDocument::parse(html, RichTextFormat::Html);
+
+        assert_eq!(document.segments(), vec!["Hello ⟪0⟫bold⟪1⟫ and ⟪2⟫link"]);
+        assert_eq!(
+            document.reassemble(&upper(&document.segments())),
+            r#"<p>HELLO <b>BOLD</b> AND <a href="https://example.com">LINK</a>.</p>"#
+        );
+    }
+
+    #[test]
+    fn test_html_inline_markup_moves_with_translation() {
+        let html =
+            r#"<p>Click <a href="/x">here</a> to <b>continue</b> &amp; save.</p><p>Next</p>"#;
+        let document = RichDocument::parse(html, RichTextFormat::Html);
+
+        assert_eq!(
+            document.segments(),
+            vec!["Click ⟪0⟫here⟪1⟫ to ⟪2⟫continue⟪3⟫ & save.", "Next"]
+        );
+        // 語順が変わってもタグは訳語を囲んだまま復元される
+        assert_eq!(
+            document.reassemble(&[
+                "⟪2⟫続行⟪3⟫して保存するには⟪0⟫ここ⟪1⟫をクリック & 完了".to_string(),
+                "次へ".to_string(),
+            ]),
+            r#"<p><b>続行</b>して保存するには<a href="/x">ここ</a>をクリック &amp; 完了</p><p>次へ</p>"#
+        );
+
+        // トークンが欠けた翻訳は書式が崩れるため元のテキストを使用する
+        assert_eq!(
+            document.reassemble(&["ここをクリック".to_string(), "次へ".to_string()]),
+            r#"<p>Click <a href="/x">here</a> to <b>continue</b> &amp; save.</p><p>次へ</p>"#
+        );
+    }
+
+    #[test]
+    fn test_html_splits_only_on_block_elements() {
+        let html = "<table><tr><td>Name <i>first</i></td><td>Age</td></tr></table>\
+                    <p>Line one<br>line two</p>";
+        let document = RichDocument::parse(html, RichTextFormat::Html);
+
+        assert_eq!(
+            document.segments(),
+            vec!["Name ⟪0⟫first", "Age", "Line one⟪0⟫line two"]
+        );
+    }
+
+    #[test]
+    fn test_html_skips_code_and_script() {
+        let html =
+            "<p>Run <code>npm install</code></p><script>var a = 1;</script><pre><b>x</b> y</pre>";
+        let document = RichDocument::parse(html, RichTextFormat::Html);
+
+        assert_eq!(document.segments(), vec!["Run"]);
+        assert_eq!(
+            document.reassemble(&["実行".to_string()]),
+            "<p>実行 <code>npm install</code></p><script>var a = 1;</script><pre><b>x</b> y</pre>"
+        );
+    }
+
+    #[test]
+    fn test_html_entities() {
+        let html = "<p>Tom &amp; Jerry&#39;s &lt;show&gt;</p>";
+        let document = RichDocument::parse(html, RichTextFormat::Html);
+
+        assert_eq!(document.segments(), vec!["Tom & Jerry's <show>"]);
+        assert_eq!(
+            document.reassemble(&["トム&ジェリー<番組>".to_string()]),
+            "<p>トム&amp;ジェリー&lt;番組&gt;</p>"
+        );
+    }
+
+    #[test]
+    fn test_html_attribute_with_angle_bracket() {
+        let html = r#"<span title="a > b">text</span>"#;
+        let document = RichDocument::parse(html, RichTextFormat::Html);
+        assert_eq!(document.segments(), vec!["text"]);
+    }
+
+    #[test]
+    fn test_html_to_plain_text() {
+        let html = "<h1>Title</h1><p>Hello <b>world</b><br>next</p>\
+                    <ul><li>one</li><li>two</li></ul>\
+                    <table><tr><td>a</td><td>b</td></tr></table>\
+                    <style>p { color: red; }</style>";
+
+        assert_eq!(
+            html_to_plain_text(html),
+            "Title\nHello world\nnext\n- one\n- two\na\tb"
+        );
+    }
+
+    #[test]
+    fn test_markdown_block_prefixes() {
+        let markdown = "# Title\n\n- item one\n1. first\n> quote\n- [x] done\n";
+        let document = RichDocument::parse(markdown, RichTextFormat::Markdown);
+
+        assert_eq!(
+            document.segments(),
+            vec!["Title", "item one", "first", "quote", "done"]
+        );
+        assert_eq!(
+            document.reassemble(&upper(&document.segments())),
+            "# TITLE\n\n- ITEM ONE\n1. FIRST\n> QUOTE\n- [x] DONE\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_inline_elements() {
+        let markdown =
+            "See [the docs](https://example.com) and run `cargo test` ![logo](logo.png) https://example.com/x";
+        let document = RichDocument::parse(markdown, RichTextFormat::Markdown);
+
+        assert_eq!(document.segments(), vec!["See", "the docs", "and run"]);
+        assert_eq!(
+            document.reassemble(&upper(&document.segments())),
+            "SEE [THE DOCS](https://example.com) AND RUN `cargo test` ![logo](logo.png) https://example.com/x"
+        );
+    }
+
+    #[test]
+    fn test_markdown_keeps_emphasis_in_segment() {
+        let document = RichDocument::parse("This is **bold** text", RichTextFormat::Markdown);
+        assert_eq!(document.segments(), vec!["This is **bold** text"]);
+    }
+
+    #[test]
+    fn test_markdown_joins_soft_wrapped_paragraphs() {
+        let markdown = "First line\nsecond line\n\nSee [docs](https://example.com)\nfor more\n\nHard  \nbreak\n# Title\n";
+        let document = RichDocument::parse(markdown, RichTextFormat::Markdown);
+
+        assert_eq!(
+            document.segments(),
+            vec![
+                "First line second line",
+                "See",
+                "docs",
+                "for more",
+                "Hard",
+                "break",
+                "Title"
+            ]
+        );
+        assert_eq!(
+            document.reassemble(&upper(&document.segments())),
+            "FIRST LINE\nSECOND LINE\n\nSEE [DOCS](https://example.com)\nFOR MORE\n\nHARD  \nBREAK\n# TITLE\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_code_fence_and_table() {
+        let markdown = "```rust\nlet x = \"hello\";\n```\n| Name | Value |\n|---|:---:|\n| apple | 10 |\n---\n";
+        let document = RichDocument::parse(markdown, RichTextFormat::Markdown);
+
+        assert_eq!(document.segments(), vec!["Name", "Value", "apple"]);
+        assert_eq!(
+            document.reassemble(&upper(&document.segments())),
+            "```rust\nlet x = \"hello\";\n```\n| NAME | VALUE |\n|---|:---:|\n| APPLE | 10 |\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_reassemble_flattens_newlines_and_falls_back() {
+        let document = RichDocument::parse("- one\n- two\n", RichTextFormat::Markdown);
+        assert_eq!(
+            document.reassemble(&["いち\nです".to_string()]),
+            "- いち です\n- two\n"
+        );
+    }
+
+    #[test]
+    fn test_build_batches() {
+        let segments: Vec<String> = ["aaaa", "bbbb", "cccc", "dddddddddd"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(build_batches(&segments, 8), vec![0..2, 2..3, 3..4]);
+        assert_eq!(build_batches(&segments, 100), vec![0..4]);
+        assert!(build_batches(&[], 8).is_empty());
+    }
+
+    #[test]
+    fn test_format_and_parse_batch() {
+        let segments = vec!["Hello".to_string(), "World".to_string()];
+        assert_eq!(format_batch(&segments), "⟦1⟧ Hello\n⟦2⟧ World");
+
+        assert_eq!(
+            parse_batch("翻訳:\n⟦1⟧ こんにちは\n⟦2⟧ 世界\n", 2),
+            Some(vec!["こんにちは".to_string(), "世界".to_string()])
+        );
+        assert_eq!(parse_batch("⟦1⟧ こんにちは 世界", 2), None);
+        assert_eq!(parse_batch("⟦1⟧\n⟦2⟧ 世界", 2), None);
+    }
+
+    #[tokio::test]
+    async fn test_translate_segments_batches_and_falls_back() {
+        let segments = vec!["one".to_string(), "two".to_string()];
+
+        // マーカーを保持する翻訳はまとめて1回で翻訳
+        let mut calls = 0;
+        let result = translate_segments(&segments, 100, |text| {
+            calls += 1;
+            async move { Ok(text.to_uppercase()) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, vec!["ONE", "TWO"]);
+        assert_eq!(calls, 1);
+
+        // マーカーが失われた場合は1つずつ翻訳し直す
+        let mut calls = 0;
+        let result = translate_segments(&segments, 100, |text| {
+            calls += 1;
+            async move { Ok(text.replace('⟦', "").to_uppercase()) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, vec!["ONE", "TWO"]);
+        assert_eq!(calls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_translate_segments_progress() {
+        let segments: Vec<String> = ["aaaa", "bbbb", "cccc"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let mut progress = Vec::new();
+        translate_segments_with_progress(
+            &segments,
+            8,
+            |text| async move { Ok(text.to_uppercase()) },
+            |completed, batch| progress.push((completed, batch.to_vec())),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            progress,
+            vec![
+                (2, vec!["AAAA".to_string(), "BBBB".to_string()]),
+                (3, vec!["CCCC".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_document_html() {
+        let result = translate_document(
+            "<ul><li>one</li><li>two</li></ul>",
+            RichTextFormat::Html,
+            Language::English,
+            Language::Japanese,
+            |text| async move { Ok(text.replace("one", "いち").replace("two", "に")) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result.translated_content,
+            "<ul><li>いち</li><li>に</li></ul>"
+        );
+        assert_eq!(result.plain_text, "- いち\n- に");
+        assert_eq!(result.format, RichTextFormat::Html);
+    }
+}
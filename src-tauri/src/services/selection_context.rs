@@ -0,0 +1,193 @@
+//! 選択範囲の前後の文脈取得サービス
+//!
+//! 翻訳対象の選択テキストの前後にあるテキストを取得し、文脈付き翻訳に使用する。
+//! macOSではアクセシビリティAPIでフォーカス中の要素の全文と選択範囲を読み取る。
+//! その他のプラットフォームでは取得しない
+
+use crate::services::translation::TranslationContext;
+
+/// 前後それぞれに含める文脈の最大文字数
+pub const CONTEXT_WINDOW_CHARS: usize = 500;
+
+/// フォーカス中の要素から選択範囲の前後の文脈を取得する
+///
+/// 取得できない場合（アクセシビリティ非対応のアプリ、選択範囲の不一致など）はNone
+pub fn capture_context(selected_text: &str) -> Option<TranslationContext> {
+    #[cfg(target_os = "macos")]
+    {
+        let (full_text, location, length) = macos::focused_text_and_selection()?;
+        extract_context(
+            &full_text,
+            location,
+            length,
+            selected_text,
+            CONTEXT_WINDOW_CHARS,
+        )
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = selected_text;
+        None
+    }
+}
+
+/// 全文と選択範囲（UTF-16単位）から前後の文脈を切り出す
+///
+/// 選択範囲の内容が選択テキストと一致しない場合は、別の要素の値を読んだとみなしてNone
+pub fn extract_context(
+    full_text: &str,
+    location_utf16: usize,
+    length_utf16: usize,
+    selected_text: &str,
+    window_chars: usize,
+) -> Option<TranslationContext> {
+    let start = utf16_to_byte_offset(full_text, location_utf16)?;
+    let end = utf16_to_byte_offset(full_text, location_utf16 + length_utf16)?;
+
+    if full_text[start..end].trim() != selected_text.trim() {
+        return None;
+    }
+
+    let before_chars: Vec<char> = full_text[..start].chars().collect();
+    let before: String = before_chars[before_chars.len().saturating_sub(window_chars)..]
+        .iter()
+        .collect();
+    let after: String = full_text[end..].chars().take(window_chars).collect();
+
+    let context = TranslationContext {
+        before: before.trim().to_string(),
+        after: after.trim().to_string(),
+    };
+    (!context.is_empty()).then_some(context)
+}
+
+/// UTF-16単位のオフセットをバイトオフセットに変換（範囲外・サロゲートペアの途中はNone）
+fn utf16_to_byte_offset(text: &str, offset_utf16: usize) -> Option<usize> {
+    let mut utf16 = 0;
+    for (byte, c) in text.char_indices() {
+        if utf16 == offset_utf16 {
+            return Some(byte);
+        }
+        if utf16 > offset_utf16 {
+            return None;
+        }
+        utf16 += c.len_utf16();
+    }
+    (utf16 == offset_utf16).then_some(text.len())
+}
+
+/// macOSのアクセシビリティAPIでフォーカス中の要素を読み取る
+#[cfg(target_os = "macos")]
+mod macos {
+    use core_foundation::base::{CFRelease, CFType, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use std::ffi::c_void;
+
+    type AXUIElementRef = *const c_void;
+    type AXError = i32;
+
+    const AX_ERROR_SUCCESS: AXError = 0;
+    /// kAXValueCFRangeType
+    const AX_VALUE_CF_RANGE_TYPE: u32 = 4;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct CFRange {
+        location: isize,
+        length: isize,
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, value_ptr: *mut c_void) -> u8;
+    }
+
+    /// 要素の属性値を取得
+    unsafe fn copy_attribute(element: AXUIElementRef, name: &'static str) -> Option<CFType> {
+        let attribute = CFString::from_static_string(name);
+        let mut value: CFTypeRef = std::ptr::null();
+        let err =
+            AXUIElementCopyAttributeValue(element, attribute.as_concrete_TypeRef(), &mut value);
+        if err != AX_ERROR_SUCCESS || value.is_null() {
+            return None;
+        }
+        Some(CFType::wrap_under_create_rule(value))
+    }
+
+    /// フォーカス中の要素の全文と選択範囲（UTF-16単位の位置, 長さ）を取得
+    pub fn focused_text_and_selection() -> Option<(String, usize, usize)> {
+        unsafe {
+            let system = AXUIElementCreateSystemWide();
+            if system.is_null() {
+                return None;
+            }
+            let focused = copy_attribute(system, "AXFocusedUIElement");
+            CFRelease(system as CFTypeRef);
+
+            let focused = focused?;
+            let element = focused.as_CFTypeRef() as AXUIElementRef;
+            let full_text = copy_attribute(element, "AXValue")?
+                .downcast::<CFString>()?
+                .to_string();
+
+            let range_value = copy_attribute(element, "AXSelectedTextRange")?;
+            let mut range = CFRange::default();
+            let ok = AXValueGetValue(
+                range_value.as_CFTypeRef(),
+                AX_VALUE_CF_RANGE_TYPE,
+                &mut range as *mut CFRange as *mut c_void,
+            );
+            if ok == 0 || range.location < 0 || range.length <= 0 {
+                return None;
+            }
+
+            Some((full_text, range.location as usize, range.length as usize))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_context() {
+        let full = "The server restarts nightly. It takes about five minutes. Contact the admin.";
+        let location = full.find("It takes").unwrap();
+        let length = "It takes about five minutes.".len();
+
+        let context =
+            extract_context(full, location, length, "It takes about five minutes.", 500).unwrap();
+        assert_eq!(context.before, "The server restarts nightly.");
+        assert_eq!(context.after, "Contact the admin.");
+    }
+
+    #[test]
+    fn test_extract_context_window_and_utf16() {
+        // 「😀」はUTF-16で2単位
+        let full = "😀前の文です。選択した文。後の文です。";
+        let location = "😀前の文です。".encode_utf16().count();
+        let length = "選択した文。".encode_utf16().count();
+
+        let context = extract_context(full, location, length, "選択した文。", 3).unwrap();
+        assert_eq!(context.before, "です。");
+        assert_eq!(context.after, "後の文");
+    }
+
+    #[test]
+    fn test_extract_context_mismatch() {
+        // 選択範囲が選択テキストと一致しない
+        assert_eq!(extract_context("Hello world", 0, 5, "world", 500), None);
+        // 範囲外
+        assert_eq!(extract_context("Hello", 3, 10, "lo", 500), None);
+        // 前後に文脈がない
+        assert_eq!(extract_context("Hello", 0, 5, "Hello", 500), None);
+    }
+}
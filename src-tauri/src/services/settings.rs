@@ -2,6 +2,9 @@
 //!
 //! アプリケーション設定の永続化とデフォルト値管理を提供
 
+use crate::services::domain::Domain;
+use crate::services::shortcut::{self, ShortcutBinding};
+use crate::services::translation::{Formality, Language, TranslationOptions};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -21,6 +24,43 @@ pub struct AppSettings {
     /// Claude CLIの実行パス
     #[serde(default)]
     pub claude_cli_path: Option<String>,
+    /// ショートカットとアクションの割り当て（空の場合はshortcutに翻訳を割り当てる）
+    #[serde(default)]
+    pub keymap: Vec<ShortcutBinding>,
+    /// 母国語（クリップボード監視でこの言語のテキストは翻訳しない）
+    #[serde(default = "default_native_language")]
+    pub native_language: Language,
+    /// 翻訳文の文体の既定値（翻訳時に指定がない場合に使用）
+    #[serde(default)]
+    pub formality: Formality,
+    /// 翻訳ドメインの既定値（翻訳時に指定がない場合に使用）
+    #[serde(default)]
+    pub domain: Domain,
+    /// 翻訳先が日本語の場合に訳文のふりがな・ローマ字を生成する
+    #[serde(default)]
+    pub furigana: bool,
+}
+
+impl AppSettings {
+    /// 実際に登録するキーマップを取得
+    ///
+    /// キーマップが未設定の場合は、従来のshortcutに翻訳アクションを割り当てる
+    pub fn effective_keymap(&self) -> Vec<ShortcutBinding> {
+        if self.keymap.is_empty() {
+            shortcut::default_keymap(&self.shortcut)
+        } else {
+            self.keymap.clone()
+        }
+    }
+
+    /// 既定の文体・ドメイン
+    pub fn translation_options(&self) -> TranslationOptions {
+        TranslationOptions {
+            formality: self.formality,
+            domain: self.domain,
+            context: None,
+        }
+    }
 }
 
 /// providerフィールドのデフォルト値
@@ -28,6 +68,11 @@ fn default_provider() -> String {
     "ollama".to_string()
 }
 
+/// native_languageフィールドのデフォルト値
+fn default_native_language() -> Language {
+    Language::Japanese
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -36,6 +81,11 @@ impl Default for AppSettings {
             ollama_endpoint: "http://localhost:11434".to_string(),
             provider: default_provider(),
             claude_cli_path: None,
+            keymap: Vec::new(),
+            native_language: default_native_language(),
+            formality: Formality::default(),
+            domain: Domain::default(),
+            furigana: false,
         }
     }
 }
@@ -67,6 +117,7 @@ impl Serialize for SettingsError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::shortcut::ShortcutAction;
 
     #[test]
     fn test_default_settings() {
@@ -140,6 +191,9 @@ mod tests {
         let settings: AppSettings = serde_json::from_str(json).unwrap();
         assert_eq!(settings.provider, "ollama"); // デフォルト値が適用される
         assert_eq!(settings.claude_cli_path, None); // デフォルト値が適用される
+        assert_eq!(settings.native_language, Language::Japanese); // デフォルト値が適用される
+        assert_eq!(settings.formality, Formality::Auto); // デフォルト値が適用される
+        assert_eq!(settings.domain, Domain::Technical); // デフォルト値が適用される
     }
 
     #[test]
@@ -151,6 +205,11 @@ mod tests {
             ollama_endpoint: "http://localhost:11434".to_string(),
             provider: "claude-cli".to_string(),
             claude_cli_path: Some("/opt/homebrew/bin/claude".to_string()),
+            keymap: Vec::new(),
+            native_language: Language::Japanese,
+            formality: Formality::Polite,
+            domain: Domain::Chat,
+            furigana: true,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -158,6 +217,9 @@ mod tests {
         assert!(json.contains("\"claude-cli\""));
         assert!(json.contains("\"claudeCliPath\""));
         assert!(json.contains("/opt/homebrew/bin/claude"));
+        assert!(json.contains("\"formality\":\"polite\""));
+        assert!(json.contains("\"furigana\":true"));
+        assert!(json.contains("\"domain\":\"chat\""));
     }
 
     #[test]
@@ -178,4 +240,67 @@ mod tests {
             Some("/usr/local/bin/claude".to_string())
         );
     }
+
+    #[test]
+    fn test_effective_keymap_fallback() {
+        // キーマップ未設定の場合はshortcutに翻訳アクションが割り当てられる
+        let settings = AppSettings::default();
+        let keymap = settings.effective_keymap();
+        assert_eq!(keymap.len(), 1);
+        assert_eq!(keymap[0].shortcut, "CommandOrControl+J");
+        assert_eq!(keymap[0].action, ShortcutAction::Translate);
+    }
+
+    #[test]
+    fn test_keymap_deserialization() {
+        let json = r#"{
+            "shortcut": "CommandOrControl+J",
+            "ollamaModel": "qwen2.5:3b",
+            "ollamaEndpoint": "http://localhost:11434",
+            "keymap": [
+                {"shortcut": "CommandOrControl+J", "action": "translate"},
+                {"shortcut": "CommandOrControl+Shift+J", "action": "translate-and-replace"}
+            ]
+        }"#;
+
+        let settings: AppSettings = serde_json::from_str(json).unwrap();
+        let keymap = settings.effective_keymap();
+        assert_eq!(keymap.len(), 2);
+        assert_eq!(keymap[1].action, ShortcutAction::TranslateAndReplace);
+    }
+
+    #[test]
+    fn test_native_language_deserialization() {
+        let json = r#"{
+            "shortcut": "CommandOrControl+J",
+            "ollamaModel": "qwen2.5:3b",
+            "ollamaEndpoint": "http://localhost:11434",
+            "nativeLanguage": "english"
+        }"#;
+
+        let settings: AppSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.native_language, Language::English);
+    }
+
+    #[test]
+    fn test_formality_deserialization() {
+        let json = r#"{
+            "shortcut": "CommandOrControl+J",
+            "ollamaModel": "qwen2.5:3b",
+            "ollamaEndpoint": "http://localhost:11434",
+            "formality": "plain",
+            "domain": "legal"
+        }"#;
+
+        let settings: AppSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.formality, Formality::Plain);
+        assert_eq!(
+            settings.translation_options(),
+            TranslationOptions {
+                formality: Formality::Plain,
+                domain: Domain::Legal,
+                context: None,
+            }
+        );
+    }
 }
@@ -2,7 +2,9 @@
 //!
 //! グローバルショートカットの登録・解除・管理を提供
 
-use serde::Serialize;
+use crate::services::domain::Domain;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, MutexGuard};
 use thiserror::Error;
 
 /// ショートカットエラー
@@ -14,9 +16,10 @@ pub enum ShortcutError {
     UnregistrationFailed(String),
     #[error("無効なショートカット形式です: {0}")]
     InvalidFormat(String),
-    #[allow(dead_code)]
     #[error("ショートカットが競合しています: {0}")]
     Conflict(String),
+    #[error("ショートカットはこのアプリで既に登録されています: {0}")]
+    AlreadyRegistered(String),
 }
 
 impl Serialize for ShortcutError {
@@ -38,14 +41,313 @@ pub struct ShortcutStatus {
     pub is_registered: bool,
 }
 
-/// ショートカット文字列を検証する
+/// ショートカットに割り当て可能なアクション
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShortcutAction {
+    /// 選択テキストを翻訳
+    Translate,
+    /// 選択テキストを翻訳して置き換え
+    TranslateAndReplace,
+    /// 選択テキストを要約
+    Summarize,
+    /// 選択テキストへの返信を生成
+    Reply,
+    /// クリップボードのテキストを翻訳
+    TranslateClipboard,
+    /// ウィンドウの表示・非表示を切り替え
+    ToggleWindow,
+}
+
+impl ShortcutAction {
+    /// 表示用のアクション名を取得
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::Translate => "翻訳",
+            ShortcutAction::TranslateAndReplace => "翻訳して置換",
+            ShortcutAction::Summarize => "要約",
+            ShortcutAction::Reply => "返信",
+            ShortcutAction::TranslateClipboard => "クリップボード翻訳",
+            ShortcutAction::ToggleWindow => "ウィンドウ切替",
+        }
+    }
+}
+
+/// ショートカットとアクションの割り当て
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+    /// ショートカット文字列（例: "CommandOrControl+J"）
+    pub shortcut: String,
+    /// 割り当てるアクション
+    pub action: ShortcutAction,
+    /// 翻訳に使用するドメイン（未指定の場合は設定の既定値）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<Domain>,
+}
+
+/// ショートカット押下時にフロントエンドへ送るイベントペイロード
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutTriggered {
+    /// 実行するアクション
+    pub action: ShortcutAction,
+    /// 押されたショートカット文字列
+    pub shortcut: String,
+    /// 翻訳に使用するドメイン（未指定の場合は設定の既定値）
+    pub domain: Option<Domain>,
+}
+
+/// 登録中のキーマップ（Tauriの状態として管理する）
 ///
-/// # Arguments
-/// * `shortcut` - 検証するショートカット文字列（例: "CommandOrControl+Shift+T"）
+/// キーマップの再登録時に、新しいキーマップに含まれない割り当てを解除し、
+/// 登録に失敗した場合に元のキーマップへ戻すために使用する
+#[derive(Debug, Default)]
+pub struct KeymapState {
+    registered: Mutex<Vec<ShortcutBinding>>,
+}
+
+impl KeymapState {
+    /// 登録中のキーマップをロックして取得する
+    ///
+    /// 再登録の間はロックを保持し、同時に実行された登録が互いの割り当てを上書きしないようにする
+    pub fn lock(&self) -> MutexGuard<'_, Vec<ShortcutBinding>> {
+        self.registered.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 単一ショートカット設定からキーマップを生成する
+///
+/// キーマップ未保存の既存設定との互換用に、翻訳アクションのみを割り当てる
+pub fn default_keymap(shortcut: &str) -> Vec<ShortcutBinding> {
+    vec![ShortcutBinding {
+        shortcut: shortcut.to_string(),
+        action: ShortcutAction::Translate,
+        domain: None,
+    }]
+}
+
+/// OS・エディタで広く使われている予約済みショートカット（ショートカット, 用途）
+const RESERVED_SHORTCUTS: &[(&str, &str)] = &[
+    ("CommandOrControl+C", "コピー"),
+    ("CommandOrControl+V", "貼り付け"),
+    ("CommandOrControl+X", "切り取り"),
+    ("CommandOrControl+Z", "取り消し"),
+    ("CommandOrControl+Shift+Z", "やり直し"),
+    ("CommandOrControl+A", "すべてを選択"),
+    ("CommandOrControl+S", "保存"),
+    ("CommandOrControl+F", "検索"),
+    ("CommandOrControl+P", "印刷"),
+    ("CommandOrControl+N", "新規作成"),
+    ("CommandOrControl+T", "新規タブ"),
+    ("CommandOrControl+W", "ウィンドウを閉じる"),
+    ("CommandOrControl+Q", "アプリケーションを終了"),
+];
+
+/// プラットフォーム固有の予約済みショートカット（ショートカット, 用途）
+#[cfg(target_os = "macos")]
+const PLATFORM_RESERVED_SHORTCUTS: &[(&str, &str)] = &[
+    ("Command+Space", "Spotlight"),
+    ("Control+Space", "入力ソースの切り替え"),
+    ("Command+Tab", "アプリケーションの切り替え"),
+    ("Command+H", "アプリケーションを隠す"),
+    ("Command+M", "ウィンドウをしまう"),
+    ("Command+Alt+Escape", "強制終了"),
+    ("Control+Command+Q", "画面のロック"),
+    ("Control+Command+Space", "絵文字と記号"),
+    ("Command+Shift+3", "スクリーンショット"),
+    ("Command+Shift+4", "スクリーンショット"),
+    ("Command+Shift+5", "スクリーンショット"),
+];
+
+/// プラットフォーム固有の予約済みショートカット（ショートカット, 用途）
+#[cfg(not(target_os = "macos"))]
+const PLATFORM_RESERVED_SHORTCUTS: &[(&str, &str)] = &[
+    ("Alt+Tab", "ウィンドウの切り替え"),
+    ("Alt+F4", "ウィンドウを閉じる"),
+    ("Super+L", "画面のロック"),
+    ("Super+D", "デスクトップの表示"),
+    ("Control+Alt+Delete", "セキュリティオプション"),
+    ("Control+Alt+T", "ターミナル"),
+];
+
+/// グローバルショートカットプラグインが受け付ける名前付きキー
+const NAMED_KEYS: &[&str] = &[
+    "BACKQUOTE",
+    "BACKSLASH",
+    "BRACKETLEFT",
+    "BRACKETRIGHT",
+    "PAUSE",
+    "COMMA",
+    "EQUAL",
+    "MINUS",
+    "PERIOD",
+    "QUOTE",
+    "SEMICOLON",
+    "SLASH",
+    "BACKSPACE",
+    "CAPSLOCK",
+    "ENTER",
+    "SPACE",
+    "TAB",
+    "DELETE",
+    "END",
+    "HOME",
+    "INSERT",
+    "PAGEDOWN",
+    "PAGEUP",
+    "PRINTSCREEN",
+    "SCROLLLOCK",
+    "ARROWDOWN",
+    "ARROWLEFT",
+    "ARROWRIGHT",
+    "ARROWUP",
+    "NUMLOCK",
+    "NUMPADADD",
+    "NUMPADDECIMAL",
+    "NUMPADDIVIDE",
+    "NUMPADENTER",
+    "NUMPADEQUAL",
+    "NUMPADMULTIPLY",
+    "NUMPADSUBTRACT",
+    "ESCAPE",
+    "AUDIOVOLUMEDOWN",
+    "AUDIOVOLUMEUP",
+    "AUDIOVOLUMEMUTE",
+    "MEDIAPLAY",
+    "MEDIAPAUSE",
+    "MEDIAPLAYPAUSE",
+    "MEDIASTOP",
+    "MEDIATRACKNEXT",
+    "MEDIATRACKPREV",
+];
+
+/// 修飾キーを正規化する
+///
+/// グローバルショートカットプラグインと同じ規則で解釈し、
+/// CommandOrControlは実行中のプラットフォームに応じて解決する
+fn canonical_modifier(token: &str) -> Option<&'static str> {
+    match token.to_uppercase().as_str() {
+        "OPTION" | "ALT" => Some("Alt"),
+        "CONTROL" | "CTRL" => Some("Control"),
+        "COMMAND" | "CMD" | "SUPER" => Some("Super"),
+        "SHIFT" => Some("Shift"),
+        "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
+            if cfg!(target_os = "macos") {
+                Some("Super")
+            } else {
+                Some("Control")
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 保存済みのショートカットの旧表記を移行する
+///
+/// 以前は修飾キーとして"Meta"を受け付けていたが、グローバルショートカットプラグインは
+/// 解釈しないため、設定の読み込み時に同じキーを表す"Super"へ置き換える
+pub fn migrate_shortcut(shortcut: &str) -> String {
+    shortcut
+        .split('+')
+        .map(|part| {
+            if part.trim().eq_ignore_ascii_case("meta") {
+                "Super"
+            } else {
+                part
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// キーを正規化する
+///
+/// グローバルショートカットプラグインが受け付けるキーコードのみを有効とし、
+/// 別名（"A"と"KeyA"、"Esc"と"Escape"など）を同じ表記に揃える
+fn canonical_key(token: &str) -> Option<String> {
+    let upper = token.to_uppercase();
+    let key = match upper.as_str() {
+        "`" => "BACKQUOTE",
+        "\\" => "BACKSLASH",
+        "[" => "BRACKETLEFT",
+        "]" => "BRACKETRIGHT",
+        "PAUSEBREAK" => "PAUSE",
+        "," => "COMMA",
+        "=" => "EQUAL",
+        "-" => "MINUS",
+        "." => "PERIOD",
+        "'" => "QUOTE",
+        ";" => "SEMICOLON",
+        "/" => "SLASH",
+        "DOWN" => "ARROWDOWN",
+        "LEFT" => "ARROWLEFT",
+        "RIGHT" => "ARROWRIGHT",
+        "UP" => "ARROWUP",
+        "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => "NUMPADADD",
+        "NUMDECIMAL" => "NUMPADDECIMAL",
+        "NUMDIVIDE" => "NUMPADDIVIDE",
+        "NUMENTER" => "NUMPADENTER",
+        "NUMEQUAL" => "NUMPADEQUAL",
+        "NUMMULTIPLY" => "NUMPADMULTIPLY",
+        "NUMSUBTRACT" => "NUMPADSUBTRACT",
+        "ESC" => "ESCAPE",
+        "VOLUMEDOWN" => "AUDIOVOLUMEDOWN",
+        "VOLUMEUP" => "AUDIOVOLUMEUP",
+        "VOLUMEMUTE" => "AUDIOVOLUMEMUTE",
+        "MEDIATRACKPREVIOUS" => "MEDIATRACKPREV",
+        other => other,
+    };
+
+    if NAMED_KEYS.contains(&key) {
+        return Some(key.to_string());
+    }
+
+    // 英字・数字（"A" / "KEYA"、"1" / "DIGIT1"）
+    let single = key
+        .strip_prefix("KEY")
+        .or_else(|| key.strip_prefix("DIGIT"))
+        .unwrap_or(key);
+    if single.len() == 1 {
+        let ch = single.chars().next().unwrap();
+        if ch.is_ascii_uppercase() {
+            return Some(format!("KEY{}", ch));
+        }
+        if ch.is_ascii_digit() {
+            return Some(format!("DIGIT{}", ch));
+        }
+    }
+
+    // テンキー（"NUMPAD1" / "NUM1"）
+    if let Some(digit) = key
+        .strip_prefix("NUMPAD")
+        .or_else(|| key.strip_prefix("NUM"))
+    {
+        if digit.len() == 1 && digit.chars().all(|c| c.is_ascii_digit()) {
+            return Some(format!("NUMPAD{}", digit));
+        }
+    }
+
+    // ファンクションキー（F1〜F24）
+    if let Some(number) = key.strip_prefix('F') {
+        if let Ok(n) = number.parse::<u32>() {
+            if (1..=24).contains(&n) && !number.starts_with('0') {
+                return Some(key.to_string());
+            }
+        }
+    }
+
+    None
+}
+
+/// ショートカット文字列を正規化する
+///
+/// 修飾キーを固定順（Control, Alt, Shift, Super）に並べ、キーを正規表記に揃えた文字列を返す。
+/// 表記の異なる同一ショートカットの比較に使用する
 ///
 /// # Returns
-/// 有効な場合はOk(())、無効な場合はエラー
-pub fn validate_shortcut(shortcut: &str) -> Result<(), ShortcutError> {
+/// 正規化されたショートカット文字列、または形式エラー
+pub fn normalize_shortcut(shortcut: &str) -> Result<String, ShortcutError> {
     if shortcut.is_empty() {
         return Err(ShortcutError::InvalidFormat(
             "ショートカットが空です".to_string(),
@@ -53,10 +355,7 @@ pub fn validate_shortcut(shortcut: &str) -> Result<(), ShortcutError> {
     }
 
     // 基本的な形式チェック
-    let parts: Vec<&str> = shortcut.split('+').collect();
-    if parts.is_empty() {
-        return Err(ShortcutError::InvalidFormat("無効な形式です".to_string()));
-    }
+    let parts: Vec<&str> = shortcut.split('+').map(|p| p.trim()).collect();
 
     // 最後のパートがキーである必要がある
     let key = parts.last().unwrap();
@@ -66,28 +365,91 @@ pub fn validate_shortcut(shortcut: &str) -> Result<(), ShortcutError> {
         ));
     }
 
-    // 有効な修飾キー
-    let valid_modifiers = [
-        "Command",
-        "Cmd",
-        "Control",
-        "Ctrl",
-        "CommandOrControl",
-        "CmdOrCtrl",
-        "Alt",
-        "Option",
-        "Shift",
-        "Super",
-        "Meta",
-    ];
-
     // 修飾キーの検証（最後のパート以外）
+    let mut modifiers = Vec::new();
     for part in &parts[..parts.len() - 1] {
-        let is_valid_modifier = valid_modifiers.iter().any(|m| m.eq_ignore_ascii_case(part));
-        if !is_valid_modifier {
-            return Err(ShortcutError::InvalidFormat(format!(
-                "無効な修飾キー: {}",
-                part
+        match canonical_modifier(part) {
+            Some(modifier) => {
+                if !modifiers.contains(&modifier) {
+                    modifiers.push(modifier);
+                }
+            }
+            None => {
+                return Err(ShortcutError::InvalidFormat(format!(
+                    "無効な修飾キー: {}",
+                    part
+                )));
+            }
+        }
+    }
+
+    // キーの検証（プラグインが受け付けるキーコードのみ有効）
+    let key = canonical_key(key)
+        .ok_or_else(|| ShortcutError::InvalidFormat(format!("無効なキー: {}", key)))?;
+
+    let order = ["Control", "Alt", "Shift", "Super"];
+    modifiers.sort_by_key(|m| order.iter().position(|o| o == m));
+
+    let mut normalized = modifiers.join("+");
+    if !normalized.is_empty() {
+        normalized.push('+');
+    }
+    normalized.push_str(&key);
+
+    Ok(normalized)
+}
+
+/// ショートカット文字列を検証する
+///
+/// # Arguments
+/// * `shortcut` - 検証するショートカット文字列（例: "CommandOrControl+Shift+T"）
+///
+/// # Returns
+/// 有効な場合はOk(())、無効な場合はエラー
+pub fn validate_shortcut(shortcut: &str) -> Result<(), ShortcutError> {
+    normalize_shortcut(shortcut).map(|_| ())
+}
+
+/// 予約済みショートカットとの競合を確認する
+///
+/// コピー・貼り付けなどのOS・エディタ標準ショートカットと同じキーの場合は
+/// 競合相手のショートカットと用途を含むエラーを返す
+pub fn check_reserved_conflict(shortcut: &str) -> Result<(), ShortcutError> {
+    let normalized = normalize_shortcut(shortcut)?;
+
+    for (reserved, description) in RESERVED_SHORTCUTS
+        .iter()
+        .chain(PLATFORM_RESERVED_SHORTCUTS.iter())
+    {
+        if normalize_shortcut(reserved).ok().as_deref() == Some(normalized.as_str()) {
+            return Err(ShortcutError::Conflict(format!(
+                "{}（{}）",
+                reserved, description
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// ショートカットが予約済みショートカットやキーマップ内の他の割り当てと競合しないか確認する
+///
+/// # Arguments
+/// * `shortcut` - 確認するショートカット文字列
+/// * `keymap` - 比較対象のキーマップ（変更対象の割り当ては含めない）
+pub fn check_shortcut_conflict(
+    shortcut: &str,
+    keymap: &[ShortcutBinding],
+) -> Result<(), ShortcutError> {
+    check_reserved_conflict(shortcut)?;
+
+    let normalized = normalize_shortcut(shortcut)?;
+    for binding in keymap {
+        if normalize_shortcut(&binding.shortcut).ok().as_deref() == Some(normalized.as_str()) {
+            return Err(ShortcutError::Conflict(format!(
+                "{}（{}）",
+                binding.shortcut,
+                binding.action.label()
             )));
         }
     }
@@ -95,6 +457,44 @@ pub fn validate_shortcut(shortcut: &str) -> Result<(), ShortcutError> {
     Ok(())
 }
 
+/// キーマップ全体を検証する
+///
+/// 各ショートカットの形式、予約済みショートカットとの競合、
+/// キーマップ内での重複を検証する
+pub fn validate_keymap(keymap: &[ShortcutBinding]) -> Result<(), ShortcutError> {
+    for (i, binding) in keymap.iter().enumerate() {
+        check_shortcut_conflict(&binding.shortcut, &keymap[..i])?;
+    }
+    Ok(())
+}
+
+/// ショートカット登録の失敗の原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistrationFailure {
+    /// このアプリで既に登録されている
+    RegisteredByApp,
+    /// OSがホットキーの登録を拒否した（他のアプリケーションが使用中など）
+    RejectedByOs(String),
+    /// その他のエラー
+    Other(String),
+}
+
+/// ショートカット登録の失敗をエラーに変換する
+///
+/// このアプリ自身の登録と、他のアプリケーションとの競合を区別して報告する
+pub fn registration_error(shortcut: &str, failure: RegistrationFailure) -> ShortcutError {
+    match failure {
+        RegistrationFailure::RegisteredByApp => {
+            ShortcutError::AlreadyRegistered(shortcut.to_string())
+        }
+        RegistrationFailure::RejectedByOs(message) => ShortcutError::Conflict(format!(
+            "{}（他のアプリケーションで使用されている可能性があります: {}）",
+            shortcut, message
+        )),
+        RegistrationFailure::Other(message) => ShortcutError::RegistrationFailed(message),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,6 +528,62 @@ mod tests {
         assert_eq!(json, "\"ショートカットの登録に失敗しました: test error\"");
     }
 
+    #[test]
+    fn test_shortcut_action_serialization() {
+        let json = serde_json::to_string(&ShortcutAction::TranslateAndReplace).unwrap();
+        assert_eq!(json, "\"translate-and-replace\"");
+
+        let action: ShortcutAction = serde_json::from_str("\"toggle-window\"").unwrap();
+        assert_eq!(action, ShortcutAction::ToggleWindow);
+    }
+
+    #[test]
+    fn test_shortcut_binding_deserialization() {
+        let json = r#"[
+            {"shortcut": "CommandOrControl+J", "action": "translate"},
+            {"shortcut": "CommandOrControl+Shift+S", "action": "summarize"},
+            {"shortcut": "CommandOrControl+Shift+M", "action": "translate", "domain": "marketing"}
+        ]"#;
+        let keymap: Vec<ShortcutBinding> = serde_json::from_str(json).unwrap();
+        assert_eq!(keymap.len(), 3);
+        assert_eq!(keymap[1].action, ShortcutAction::Summarize);
+        assert_eq!(keymap[1].domain, None);
+        assert_eq!(keymap[2].domain, Some(Domain::Marketing));
+    }
+
+    #[test]
+    fn test_shortcut_triggered_serialization() {
+        let payload = ShortcutTriggered {
+            action: ShortcutAction::Reply,
+            shortcut: "Cmd+Shift+R".to_string(),
+            domain: Some(Domain::Chat),
+        };
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(json.contains("\"action\":\"reply\""));
+        assert!(json.contains("\"shortcut\":\"Cmd+Shift+R\""));
+        assert!(json.contains("\"domain\":\"chat\""));
+    }
+
+    #[test]
+    fn test_default_keymap() {
+        let keymap = default_keymap("CommandOrControl+J");
+        assert_eq!(keymap.len(), 1);
+        assert_eq!(keymap[0].shortcut, "CommandOrControl+J");
+        assert_eq!(keymap[0].action, ShortcutAction::Translate);
+    }
+
+    #[test]
+    fn test_validate_keymap() {
+        assert!(validate_keymap(&default_keymap("CommandOrControl+J")).is_ok());
+
+        let keymap = vec![ShortcutBinding {
+            shortcut: "Invalid+J".to_string(),
+            action: ShortcutAction::Translate,
+            domain: None,
+        }];
+        assert!(validate_keymap(&keymap).is_err());
+    }
+
     #[test]
     fn test_validate_shortcut_valid() {
         assert!(validate_shortcut("CommandOrControl+Shift+T").is_ok());
@@ -163,4 +619,130 @@ mod tests {
             assert!(msg.contains("キーが指定されていません"));
         }
     }
+
+    #[test]
+    fn test_validate_shortcut_invalid_key() {
+        let result = validate_shortcut("Shift+Foo");
+        assert!(result.is_err());
+        if let Err(ShortcutError::InvalidFormat(msg)) = result {
+            assert!(msg.contains("無効なキー"));
+        }
+
+        assert!(validate_shortcut("Ctrl+F25").is_err());
+        assert!(validate_shortcut("Ctrl+C+A").is_err());
+    }
+
+    #[test]
+    fn test_validate_shortcut_key_aliases() {
+        assert!(validate_shortcut("Ctrl+Esc").is_ok());
+        assert!(validate_shortcut("Alt+Space").is_ok());
+        assert!(validate_shortcut("Shift+Num1").is_ok());
+        assert!(validate_shortcut("Cmd+KeyK").is_ok());
+        assert!(validate_shortcut("Cmd+Digit1").is_ok());
+        assert!(validate_shortcut("F13").is_ok());
+    }
+
+    #[test]
+    fn test_migrate_shortcut() {
+        assert_eq!(migrate_shortcut("Meta+Shift+J"), "Super+Shift+J");
+        assert_eq!(migrate_shortcut("Shift+meta+K"), "Shift+Super+K");
+        assert_eq!(migrate_shortcut("CommandOrControl+J"), "CommandOrControl+J");
+        assert!(validate_shortcut(&migrate_shortcut("Meta+J")).is_ok());
+        assert!(validate_shortcut("Meta+J").is_err());
+    }
+
+    #[test]
+    fn test_normalize_shortcut() {
+        assert_eq!(
+            normalize_shortcut("Shift+Ctrl+a").unwrap(),
+            "Control+Shift+KEYA"
+        );
+        assert_eq!(
+            normalize_shortcut("Control+Shift+KeyA").unwrap(),
+            normalize_shortcut("shift+ctrl+A").unwrap()
+        );
+        assert_eq!(normalize_shortcut("Option+Esc").unwrap(), "Alt+ESCAPE");
+        assert_eq!(normalize_shortcut("Cmd+Super+1").unwrap(), "Super+DIGIT1");
+    }
+
+    #[test]
+    fn test_check_reserved_conflict() {
+        let result = check_reserved_conflict("CmdOrCtrl+c");
+        match result {
+            Err(ShortcutError::Conflict(msg)) => {
+                assert!(msg.contains("CommandOrControl+C"));
+                assert!(msg.contains("コピー"));
+            }
+            _ => panic!("Expected Conflict error"),
+        }
+
+        assert!(check_reserved_conflict("CommandOrControl+J").is_ok());
+        assert!(check_reserved_conflict("CommandOrControl+Shift+C").is_ok());
+    }
+
+    #[test]
+    fn test_validate_keymap_duplicate() {
+        let keymap = vec![
+            ShortcutBinding {
+                shortcut: "CommandOrControl+J".to_string(),
+                action: ShortcutAction::Translate,
+                domain: None,
+            },
+            ShortcutBinding {
+                shortcut: "CmdOrCtrl+KeyJ".to_string(),
+                action: ShortcutAction::Summarize,
+                domain: None,
+            },
+        ];
+
+        match validate_keymap(&keymap) {
+            Err(ShortcutError::Conflict(msg)) => {
+                assert!(msg.contains("CommandOrControl+J"));
+                assert!(msg.contains("翻訳"));
+            }
+            _ => panic!("Expected Conflict error"),
+        }
+    }
+
+    #[test]
+    fn test_validate_keymap_reserved() {
+        let keymap = vec![ShortcutBinding {
+            shortcut: "CommandOrControl+V".to_string(),
+            action: ShortcutAction::TranslateAndReplace,
+            domain: None,
+        }];
+
+        assert!(matches!(
+            validate_keymap(&keymap),
+            Err(ShortcutError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_registration_error() {
+        let err = registration_error(
+            "CommandOrControl+J",
+            RegistrationFailure::RejectedByOs("Unable to register hotkey".to_string()),
+        );
+        match err {
+            ShortcutError::Conflict(msg) => {
+                assert!(msg.contains("CommandOrControl+J"));
+                assert!(msg.contains("他のアプリケーション"));
+            }
+            _ => panic!("Expected Conflict error"),
+        }
+
+        // このアプリ自身の登録は他のアプリケーションとの競合として扱わない
+        let err = registration_error("CommandOrControl+J", RegistrationFailure::RegisteredByApp);
+        assert_eq!(
+            err.to_string(),
+            "ショートカットはこのアプリで既に登録されています: CommandOrControl+J"
+        );
+
+        let err = registration_error(
+            "CommandOrControl+J",
+            RegistrationFailure::Other("some other error".to_string()),
+        );
+        assert!(matches!(err, ShortcutError::RegistrationFailed(_)));
+    }
 }
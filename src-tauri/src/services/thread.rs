@@ -0,0 +1,553 @@
+//! メール・チャットのスレッド要約サービス
+//!
+//! 引用付きのメール返信（`>`による引用、"On ... wrote:"などの引用ヘッダー）や
+//! Slack形式の`名前: 発言`の行を解析して発言者ごとに分割し、
+//! 要約・参加者ごとのアクションアイテム・決定事項を構造化データとして生成する
+
+use crate::services::translation::{parse_json_object, Language};
+use serde::{Deserialize, Serialize};
+
+/// チャット形式の発言者名の最大文字数
+const MAX_SPEAKER_NAME_CHARS: usize = 32;
+
+/// スレッドの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThreadFormat {
+    /// 引用付きのメール
+    Email,
+    /// `名前: 発言`形式のチャット
+    Chat,
+    /// 発言者を判別できないテキスト
+    Plain,
+}
+
+/// スレッド内の1つの発言
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadMessage {
+    /// 発言者（判別できない場合はNone）
+    pub speaker: Option<String>,
+    /// 発言内容
+    pub text: String,
+}
+
+/// 発言者ごとに分割したスレッド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thread {
+    pub format: ThreadFormat,
+    pub messages: Vec<ThreadMessage>,
+}
+
+/// 参加者ごとの要約
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipantSummary {
+    /// 参加者名
+    pub name: String,
+    /// 参加者が担当するアクションアイテム
+    #[serde(default)]
+    pub action_items: Vec<String>,
+}
+
+/// スレッド要約結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadSummary {
+    /// スレッド全体の要約
+    pub summary: String,
+    /// 参加者ごとのアクションアイテム
+    pub participants: Vec<ParticipantSummary>,
+    /// 決定事項
+    pub decisions: Vec<String>,
+    /// 発言者ごとに分割した発言
+    pub messages: Vec<ThreadMessage>,
+    /// スレッドの形式
+    pub format: ThreadFormat,
+    /// 処理時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// モデルが出力する要約（構造化出力）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StructuredThreadSummary {
+    summary: String,
+    #[serde(default)]
+    participants: Vec<ParticipantSummary>,
+    #[serde(default)]
+    decisions: Vec<String>,
+}
+
+impl Thread {
+    /// テキストを解析して発言者ごとに分割する
+    ///
+    /// 引用や引用ヘッダーがあればメール、`名前: 発言`の行が2行以上あればチャットとして扱う
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+
+        let is_email = lines.iter().any(|line| {
+            let (depth, content) = split_quote(line);
+            depth > 0 || attribution(content).is_some() || header_sender(content).is_some()
+        });
+        if is_email {
+            return Self {
+                format: ThreadFormat::Email,
+                messages: parse_email(&lines),
+            };
+        }
+
+        if lines
+            .iter()
+            .filter(|line| chat_line(line).is_some())
+            .count()
+            >= 2
+        {
+            return Self {
+                format: ThreadFormat::Chat,
+                messages: parse_chat(&lines),
+            };
+        }
+
+        Self {
+            format: ThreadFormat::Plain,
+            messages: vec![ThreadMessage {
+                speaker: None,
+                text: text.trim().to_string(),
+            }],
+        }
+    }
+
+    /// 登場順の参加者一覧
+    pub fn participants(&self) -> Vec<String> {
+        let mut participants: Vec<String> = Vec::new();
+        for speaker in self.messages.iter().filter_map(|m| m.speaker.as_ref()) {
+            if !participants.contains(speaker) {
+                participants.push(speaker.clone());
+            }
+        }
+        participants
+    }
+
+    /// モデルに渡す`[発言者] 発言`形式の書き起こし
+    pub fn transcript(&self) -> String {
+        self.messages
+            .iter()
+            .map(|message| {
+                format!(
+                    "[{}] {}",
+                    message.speaker.as_deref().unwrap_or("?"),
+                    message.text
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 行頭の引用記号（`>`、`> >`など）を数え、引用の深さと本文を返す
+fn split_quote(line: &str) -> (usize, &str) {
+    let mut depth = 0;
+    let mut rest = line.trim_start();
+    while let Some(stripped) = rest.strip_prefix('>') {
+        depth += 1;
+        rest = stripped.trim_start();
+    }
+    (depth, rest)
+}
+
+/// 引用ヘッダーの日時などを除いた部分から名前を取り出す
+///
+/// 例: "Mon, Jan 1, 2024 at 10:00 AM John Doe <john@example.com>" → "John Doe"
+fn extract_name(header: &str) -> Option<String> {
+    let (before_address, address) = match header.find('<') {
+        Some(start) => (
+            &header[..start],
+            header[start + 1..].split('>').next().unwrap_or_default(),
+        ),
+        None => (header, ""),
+    };
+
+    let mut words: Vec<&str> = before_address
+        .split_whitespace()
+        .rev()
+        .take_while(|word| {
+            !word.chars().any(|c| c.is_ascii_digit())
+                && !word.ends_with(',')
+                && !matches!(*word, "AM" | "PM" | "am" | "pm" | "at" | "午前" | "午後")
+        })
+        .collect();
+    words.reverse();
+
+    let name = words.join(" ");
+    let name = name.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
+    if !name.is_empty() {
+        Some(name.to_string())
+    } else if !address.trim().is_empty() {
+        Some(address.trim().to_string())
+    } else {
+        None
+    }
+}
+
+/// 引用ヘッダー（"On ... wrote:"、"... さんは書きました:"、"... <address>:"）から名前を取り出す
+fn attribution(line: &str) -> Option<String> {
+    let line = line.trim();
+
+    if let Some(header) = line.strip_suffix("wrote:") {
+        let header = header.trim().strip_prefix("On ").unwrap_or(header);
+        return extract_name(header);
+    }
+    for suffix in [
+        "さんは書きました:",
+        "さんは書きました：",
+        "は書きました:",
+        "は書きました：",
+    ] {
+        if let Some(header) = line.strip_suffix(suffix) {
+            return extract_name(header);
+        }
+    }
+    if (line.ends_with(">:") || line.ends_with(">：")) && line.contains('<') {
+        return extract_name(line);
+    }
+
+    None
+}
+
+/// 転送・返信ヘッダーの差出人行（"From: ..."、"差出人: ..."）から名前を取り出す
+fn header_sender(line: &str) -> Option<String> {
+    ["From:", "差出人:", "差出人："]
+        .iter()
+        .find_map(|prefix| line.trim().strip_prefix(prefix))
+        .and_then(extract_name)
+}
+
+/// 差出人行に続くヘッダー行かどうか
+fn is_header_field(line: &str) -> bool {
+    const FIELDS: &[&str] = &[
+        "Sent:",
+        "To:",
+        "Cc:",
+        "Subject:",
+        "Date:",
+        "送信日時:",
+        "宛先:",
+        "件名:",
+        "日付:",
+    ];
+    let line = line.trim();
+    FIELDS.iter().any(|field| line.starts_with(field))
+        || (line.starts_with("-----") && line.ends_with("-----"))
+}
+
+/// メールのスレッドを引用の深さと引用ヘッダーで発言ごとに分割する
+fn parse_email(lines: &[&str]) -> Vec<ThreadMessage> {
+    // 引用の深さごとの発言者（最上位の差出人は不明）
+    let mut speakers: Vec<Option<String>> = vec![None];
+    let mut messages = Vec::new();
+    let mut current: Option<(usize, Option<String>, Vec<&str>)> = None;
+
+    let flush = |current: &mut Option<(usize, Option<String>, Vec<&str>)>,
+                 messages: &mut Vec<ThreadMessage>| {
+        if let Some((_, speaker, body)) = current.take() {
+            let text = body.join("\n").trim().to_string();
+            if !text.is_empty() {
+                messages.push(ThreadMessage { speaker, text });
+            }
+        }
+    };
+
+    for line in lines {
+        let (depth, content) = split_quote(line);
+
+        if let Some(name) = attribution(content) {
+            // 引用ヘッダーは1段深い引用の発言者を表す
+            flush(&mut current, &mut messages);
+            speakers.resize(depth + 2, None);
+            speakers[depth + 1] = Some(name);
+            continue;
+        }
+        if let Some(name) = header_sender(content) {
+            // 転送・返信ヘッダーは同じ深さで新しい発言者の発言が始まることを表す
+            flush(&mut current, &mut messages);
+            speakers.resize(depth + 1, None);
+            speakers[depth] = Some(name);
+            current = Some((depth, speakers[depth].clone(), Vec::new()));
+            continue;
+        }
+        if is_header_field(content) && current.as_ref().is_some_and(|(_, _, b)| b.is_empty()) {
+            continue;
+        }
+
+        if current.as_ref().map(|(d, _, _)| *d) != Some(depth) {
+            flush(&mut current, &mut messages);
+            let speaker = speakers.get(depth).cloned().flatten();
+            current = Some((depth, speaker, Vec::new()));
+        }
+        if let Some((_, _, body)) = current.as_mut() {
+            body.push(content);
+        }
+    }
+    flush(&mut current, &mut messages);
+
+    messages
+}
+
+/// `名前: 発言`形式の行であれば（名前, 発言）を返す
+fn chat_line(line: &str) -> Option<(&str, &str)> {
+    let separator = line.find([':', '：'])?;
+    let name = line[..separator].trim();
+    let separator_len = line[separator..].chars().next()?.len_utf8();
+    let message = line[separator + separator_len..].trim();
+
+    let is_name = !name.is_empty()
+        && name.chars().count() <= MAX_SPEAKER_NAME_CHARS
+        && name.split_whitespace().count() <= 3
+        && !name.contains(['/', '@', '<', '>'])
+        && !name.chars().next()?.is_ascii_digit()
+        && !line[separator..].starts_with("://");
+
+    (is_name && !message.is_empty()).then_some((name, message))
+}
+
+/// チャットのスレッドを`名前: 発言`の行で発言ごとに分割する
+///
+/// 名前のない行は直前の発言の続きとして扱う
+fn parse_chat(lines: &[&str]) -> Vec<ThreadMessage> {
+    let mut messages: Vec<ThreadMessage> = Vec::new();
+
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match (chat_line(line), messages.last_mut()) {
+            (Some((name, message)), _) => messages.push(ThreadMessage {
+                speaker: Some(name.to_string()),
+                text: message.to_string(),
+            }),
+            (None, Some(last)) => {
+                last.text.push('\n');
+                last.text.push_str(line.trim());
+            }
+            (None, None) => messages.push(ThreadMessage {
+                speaker: None,
+                text: line.trim().to_string(),
+            }),
+        }
+    }
+
+    messages
+}
+
+/// スレッド要約用のシステムプロンプト（要約言語で記述）
+pub fn system_prompt(language: Language) -> &'static str {
+    match language {
+        Language::Japanese => {
+            "あなたはメールやチャットのスレッドを整理する専門家です。必ず日本語で、指定されたJSON形式でのみ出力してください。"
+        }
+        Language::English => {
+            "You are an expert at organizing email and chat threads. You MUST respond in English and output only the requested JSON."
+        }
+    }
+}
+
+/// スレッド要約用のプロンプトを構築
+pub fn build_thread_prompt(thread: &Thread, language: Language) -> String {
+    match language {
+        Language::Japanese => format!(
+            "以下はメールやチャットのスレッドです。各行は「[発言者] 発言」の形式です（発言者が不明な場合は[?]）。\n\
+             JSONで出力し、\"summary\"にスレッド全体の要約（3文以内）、\"participants\"に参加者ごとの{{\"name\": 参加者名, \"actionItems\": [その参加者が行うべきこと]}}、\"decisions\"に決定事項の一覧を日本語で入れてください。該当するものがない場合は空の配列にしてください。\n\n{}",
+            thread.transcript()
+        ),
+        Language::English => format!(
+            "The following is an email or chat thread. Each line has the form \"[speaker] message\" ([?] if the speaker is unknown).\n\
+             Output JSON in English with a summary of the whole thread (3 sentences or less) in \"summary\", each participant's {{\"name\": participant name, \"actionItems\": [things the participant needs to do]}} in \"participants\", and the list of decisions in \"decisions\". Use empty arrays when there are none.\n\n{}",
+            thread.transcript()
+        ),
+    }
+}
+
+/// スレッド要約の構造化出力スキーマ（Ollamaの`format`パラメータ）
+pub fn summary_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string" },
+            "participants": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "actionItems": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["name", "actionItems"]
+                }
+            },
+            "decisions": { "type": "array", "items": { "type": "string" } }
+        },
+        "required": ["summary", "participants", "decisions"]
+    })
+}
+
+/// モデルの出力からスレッド要約を組み立てる
+///
+/// JSONとして解釈できない場合は出力全体を要約として扱う。
+/// スレッドから検出した参加者がモデルの出力に含まれない場合は、アクションアイテムなしで追加する
+pub fn build_summary(content: &str, thread: Thread, duration_ms: u64) -> ThreadSummary {
+    let structured = parse_json_object::<StructuredThreadSummary>(content);
+
+    let (summary, mut participants, decisions) = match structured {
+        Some(parsed) => (parsed.summary, parsed.participants, parsed.decisions),
+        None => (content.trim().to_string(), Vec::new(), Vec::new()),
+    };
+
+    for name in thread.participants() {
+        if !participants.iter().any(|p| p.name == name) {
+            participants.push(ParticipantSummary {
+                name,
+                action_items: Vec::new(),
+            });
+        }
+    }
+
+    ThreadSummary {
+        summary: summary.trim().to_string(),
+        participants,
+        decisions,
+        messages: thread.messages,
+        format: thread.format,
+        duration_ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_email_thread() {
+        let text = "Sounds good, I'll send the draft tomorrow.\n\
+                    \n\
+                    On Mon, Jan 6, 2025 at 10:00 AM Alice Smith <alice@example.com> wrote:\n\
+                    > Can you prepare the proposal by Friday?\n\
+                    >\n\
+                    > On Sun, Jan 5, 2025 at 9:00 PM Bob <bob@example.com> wrote:\n\
+                    >> The client asked for a revised proposal.";
+
+        let thread = Thread::parse(text);
+        assert_eq!(thread.format, ThreadFormat::Email);
+        assert_eq!(
+            thread.messages,
+            vec![
+                ThreadMessage {
+                    speaker: None,
+                    text: "Sounds good, I'll send the draft tomorrow.".to_string(),
+                },
+                ThreadMessage {
+                    speaker: Some("Alice Smith".to_string()),
+                    text: "Can you prepare the proposal by Friday?".to_string(),
+                },
+                ThreadMessage {
+                    speaker: Some("Bob".to_string()),
+                    text: "The client asked for a revised proposal.".to_string(),
+                },
+            ]
+        );
+        assert_eq!(thread.participants(), vec!["Alice Smith", "Bob"]);
+    }
+
+    #[test]
+    fn test_parse_forwarded_email_headers() {
+        let text = "了解しました。\n\
+                    \n\
+                    差出人: 山田 太郎 <yamada@example.com>\n\
+                    送信日時: 2025年1月6日 10:00\n\
+                    件名: 見積もりについて\n\
+                    見積もりを金曜日までにお送りください。";
+
+        let thread = Thread::parse(text);
+        assert_eq!(thread.format, ThreadFormat::Email);
+        assert_eq!(thread.messages.len(), 2);
+        assert_eq!(thread.messages[1].speaker.as_deref(), Some("山田 太郎"));
+        assert_eq!(
+            thread.messages[1].text,
+            "見積もりを金曜日までにお送りください。"
+        );
+    }
+
+    #[test]
+    fn test_parse_chat_thread() {
+        let text = "alice: deploy is blocked by the failing test\n\
+                    bob: I'll fix it today\n\
+                    see https://example.com/ci/123\n\
+                    alice: thanks, then we release on Friday";
+
+        let thread = Thread::parse(text);
+        assert_eq!(thread.format, ThreadFormat::Chat);
+        assert_eq!(thread.messages.len(), 3);
+        assert_eq!(
+            thread.messages[1].text,
+            "I'll fix it today\nsee https://example.com/ci/123"
+        );
+        assert_eq!(thread.participants(), vec!["alice", "bob"]);
+        assert!(thread
+            .transcript()
+            .starts_with("[alice] deploy is blocked by the failing test\n[bob] "));
+    }
+
+    #[test]
+    fn test_parse_plain_text() {
+        let thread = Thread::parse("The meeting starts at 10:30.\nPlease join on time.");
+        assert_eq!(thread.format, ThreadFormat::Plain);
+        assert_eq!(thread.messages.len(), 1);
+        assert_eq!(thread.messages[0].speaker, None);
+    }
+
+    #[test]
+    fn test_attribution() {
+        assert_eq!(
+            attribution("On Jan 5, Alice Smith wrote:"),
+            Some("Alice Smith".to_string())
+        );
+        assert_eq!(
+            attribution("2025年1月6日(月) 10:00 山田太郎 <yamada@example.com>:"),
+            Some("山田太郎".to_string())
+        );
+        assert_eq!(
+            attribution("On Mon, Jan 6, 2025 <bob@example.com> wrote:"),
+            Some("bob@example.com".to_string())
+        );
+        assert_eq!(attribution("Thanks for writing:"), None);
+    }
+
+    #[test]
+    fn test_build_summary() {
+        let thread = Thread::parse("alice: can you fix the test?\nbob: sure, today");
+        let content = r#"{"summary": "Bob will fix the test.", "participants": [{"name": "bob", "actionItems": ["Fix the test today"]}], "decisions": []}"#;
+
+        let summary = build_summary(content, thread, 100);
+        assert_eq!(summary.summary, "Bob will fix the test.");
+        assert_eq!(
+            summary.participants,
+            vec![
+                ParticipantSummary {
+                    name: "bob".to_string(),
+                    action_items: vec!["Fix the test today".to_string()],
+                },
+                ParticipantSummary {
+                    name: "alice".to_string(),
+                    action_items: Vec::new(),
+                },
+            ]
+        );
+        assert_eq!(summary.format, ThreadFormat::Chat);
+
+        // JSONでない出力は要約として扱う
+        let thread = Thread::parse("alice: hi\nbob: hello");
+        let summary = build_summary("Greetings were exchanged.", thread, 100);
+        assert_eq!(summary.summary, "Greetings were exchanged.");
+        assert_eq!(summary.participants.len(), 2);
+    }
+}
@@ -2,6 +2,13 @@
 //!
 //! Ollama APIを使用したテキスト翻訳機能を提供
 
+use crate::services::domain::{self, Domain};
+use crate::services::explain::{self, ExplainError, Explanation};
+use crate::services::proofread::{self, ProofreadError, ProofreadResult};
+use crate::services::quality::{self, QaWarning};
+use crate::services::reading::{self, ReadingAnnotation, ReadingError};
+use crate::services::refinement::{RefinementComplete, RefinementSession};
+use crate::services::thread::{self, Thread, ThreadSummary};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
@@ -25,6 +32,227 @@ impl Language {
             Language::English => "English",
         }
     }
+
+    /// ISO 639-1の言語コードを取得
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Japanese => "ja",
+            Language::English => "en",
+        }
+    }
+
+    /// 反対の言語を取得（日⇔英の翻訳方向決定に使用）
+    pub fn opposite(&self) -> Language {
+        match self {
+            Language::Japanese => Language::English,
+            Language::English => Language::Japanese,
+        }
+    }
+
+    /// テキストの言語を判定
+    ///
+    /// フロントエンドの言語検出と同じ規則を用いる:
+    /// - 10文字未満の場合は日本語文字が1文字でもあれば日本語
+    /// - それ以外は日本語文字（ひらがな、カタカナ、漢字、全角記号）の割合が10%以上なら日本語
+    pub fn detect(text: &str) -> Language {
+        let cleaned: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        if cleaned.is_empty() {
+            // デフォルトは日本語（日→英翻訳）
+            return Language::Japanese;
+        }
+
+        let japanese_count = cleaned.iter().filter(|c| is_japanese_char(**c)).count();
+
+        if cleaned.len() < 10 {
+            if japanese_count > 0 {
+                Language::Japanese
+            } else {
+                Language::English
+            }
+        } else if japanese_count as f64 / cleaned.len() as f64 >= 0.1 {
+            Language::Japanese
+        } else {
+            Language::English
+        }
+    }
+}
+
+/// 翻訳文の文体（丁寧さ・トーン）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Formality {
+    /// 指定なし（モデル・プロンプトの既定に従う）
+    #[default]
+    Auto,
+    /// 丁寧（日本語は「です・ます調」、英語はフォーマル）
+    Polite,
+    /// 平叙（日本語は「である調」、英語はニュートラル）
+    Plain,
+    /// くだけた口語体
+    Casual,
+}
+
+impl Formality {
+    /// プロンプトに付加する文体指示（翻訳先言語で記述、Autoの場合はNone）
+    pub fn instruction(&self, target_lang: Language) -> Option<&'static str> {
+        match (self, target_lang) {
+            (Formality::Auto, _) => None,
+            (Formality::Polite, Language::Japanese) => {
+                Some("「です・ます調」の丁寧語で翻訳してください。")
+            }
+            (Formality::Polite, Language::English) => Some("Use a formal, polite tone."),
+            (Formality::Plain, Language::Japanese) => {
+                Some("「である調」（常体）で翻訳してください。")
+            }
+            (Formality::Plain, Language::English) => Some("Use a neutral, plain tone."),
+            (Formality::Casual, Language::Japanese) => {
+                Some("親しい相手に向けたくだけた口語体で翻訳してください。")
+            }
+            (Formality::Casual, Language::English) => Some("Use a casual, conversational tone."),
+        }
+    }
+}
+
+/// 翻訳対象の前後の文脈（翻訳はせず、参考情報としてモデルに渡す）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationContext {
+    /// 翻訳対象より前のテキスト
+    #[serde(default)]
+    pub before: String,
+    /// 翻訳対象より後のテキスト
+    #[serde(default)]
+    pub after: String,
+}
+
+impl TranslationContext {
+    /// 文脈が空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.before.trim().is_empty() && self.after.trim().is_empty()
+    }
+}
+
+/// 翻訳の文体・ドメイン・文脈の指定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationOptions {
+    /// 文体
+    #[serde(default)]
+    pub formality: Formality,
+    /// ドメイン
+    #[serde(default)]
+    pub domain: Domain,
+    /// 前後の文脈
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<TranslationContext>,
+}
+
+impl TranslationOptions {
+    /// 空でない文脈を取得
+    pub fn context(&self) -> Option<&TranslationContext> {
+        self.context.as_ref().filter(|context| !context.is_empty())
+    }
+}
+
+/// 文脈付き翻訳で翻訳対象を囲むタグ
+const TRANSLATE_OPEN_TAG: &str = "<translate>";
+const TRANSLATE_CLOSE_TAG: &str = "</translate>";
+
+/// 文脈のラベル（翻訳先言語ごと）
+const CONTEXT_LABELS: &[&str] = &[
+    "前の文脈:",
+    "後の文脈:",
+    "Preceding context:",
+    "Following context:",
+];
+
+/// 文脈の扱いについての指示（翻訳先言語で記述）
+fn context_instruction(target_lang: Language) -> &'static str {
+    match target_lang {
+        Language::Japanese => {
+            "前後の文脈は参考情報です。文脈は翻訳せず、<translate>タグ内のテキストの訳文のみを出力してください。"
+        }
+        Language::English => {
+            "The surrounding context is for reference only. Do not translate it; output only the translation of the text inside the <translate> tags."
+        }
+    }
+}
+
+/// 文脈と`<translate>`タグで囲んだ翻訳対象テキストを整形
+fn format_context_body(text: &str, context: &TranslationContext, target_lang: Language) -> String {
+    let (before_label, after_label) = match target_lang {
+        Language::Japanese => (CONTEXT_LABELS[0], CONTEXT_LABELS[1]),
+        Language::English => (CONTEXT_LABELS[2], CONTEXT_LABELS[3]),
+    };
+
+    let mut lines = Vec::new();
+    if !context.before.trim().is_empty() {
+        lines.push(format!("{} {}", before_label, context.before.trim()));
+    }
+    if !context.after.trim().is_empty() {
+        lines.push(format!("{} {}", after_label, context.after.trim()));
+    }
+    lines.push(format!(
+        "{}{}{}",
+        TRANSLATE_OPEN_TAG, text, TRANSLATE_CLOSE_TAG
+    ));
+    lines.join("\n")
+}
+
+/// 文脈付きの翻訳メッセージを構築（Claude CLIのユーザーメッセージ用）
+pub(crate) fn build_context_message(
+    text: &str,
+    context: &TranslationContext,
+    target_lang: Language,
+) -> String {
+    format!(
+        "{}\n{}",
+        context_instruction(target_lang),
+        format_context_body(text, context, target_lang)
+    )
+}
+
+/// 文脈付き翻訳の出力から翻訳対象部分の訳文のみを取り出す
+///
+/// `<translate>`タグで囲まれていればその中身を使い、
+/// モデルが文脈のラベル付きの行を出力した場合は取り除く
+pub(crate) fn extract_selection_translation(output: &str) -> String {
+    let output = output.trim();
+    let inner = match (
+        output.find(TRANSLATE_OPEN_TAG),
+        output.rfind(TRANSLATE_CLOSE_TAG),
+    ) {
+        (Some(start), Some(end)) if start + TRANSLATE_OPEN_TAG.len() <= end => {
+            &output[start + TRANSLATE_OPEN_TAG.len()..end]
+        }
+        _ => output,
+    };
+
+    inner
+        .replace(TRANSLATE_OPEN_TAG, "")
+        .replace(TRANSLATE_CLOSE_TAG, "")
+        .lines()
+        .filter(|line| {
+            !CONTEXT_LABELS
+                .iter()
+                .any(|label| line.trim_start().starts_with(label))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// 日本語文字（ひらがな、カタカナ、漢字、全角記号、半角カタカナ）かどうか
+fn is_japanese_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{309F}'
+            | '\u{30A0}'..='\u{30FF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{3000}'..='\u{303F}'
+            | '\u{FF65}'..='\u{FF9F}'
+    )
 }
 
 /// 翻訳結果
@@ -39,6 +267,41 @@ pub struct TranslationResult {
     pub target_lang: Language,
     /// 翻訳にかかった時間（ミリ秒）
     pub duration_ms: u64,
+    /// 品質チェックの警告
+    #[serde(default)]
+    pub warnings: Vec<QaWarning>,
+    /// 訳文を取り出した経路
+    #[serde(default)]
+    pub output_path: OutputPath,
+    /// 訳文の読み（翻訳先が日本語で、ふりがな表示が有効な場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reading: Option<ReadingAnnotation>,
+    /// 翻訳時に送ったプロンプト（Claude CLIのみ、リファインメントの開始時に渡す）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<TranslationPrompt>,
+}
+
+/// Claude CLIでの翻訳時に送ったプロンプト
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationPrompt {
+    /// システムプロンプト
+    pub system_prompt: String,
+    /// 翻訳対象のメッセージ（前後の文脈を含む）
+    pub message: String,
+}
+
+/// 訳文を取り出した経路
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputPath {
+    /// 構造化出力（JSONスキーマ）から取得
+    Structured,
+    /// 構造化出力に従わなかったため、ヒューリスティックな整形にフォールバック
+    Fallback,
+    /// ヒューリスティックな整形のみ（翻訳特化モデル・ストリーミング・Claude CLI）
+    #[default]
+    Heuristic,
 }
 
 /// 要約結果
@@ -59,13 +322,13 @@ pub struct SummarizeResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplyResult {
-    /// 返信テキスト（翻訳先言語）
-    /// 例: 日→英翻訳の場合は英語の返信
+    /// 返信テキスト（返信言語）
+    /// 例: 英語のメールに返信する場合は英語の返信
     pub reply: String,
-    /// 返信の翻訳（翻訳元言語）
-    /// 例: 日→英翻訳の場合は日本語の返信（上記replyを翻訳したもの）
+    /// 返信の翻訳（説明言語）
+    /// 例: 説明言語が日本語の場合は日本語の訳（上記replyを翻訳したもの）
     pub explanation: String,
-    /// 返信の言語（翻訳先言語）
+    /// 返信の言語（返信言語）
     pub language: Language,
     /// 処理時間（ミリ秒）
     pub duration_ms: u64,
@@ -206,6 +469,13 @@ fn build_api_options(model_type: ModelType) -> serde_json::Value {
     }
 }
 
+/// 翻訳用のAPIパラメータを構築（ドメインに応じてサンプリングを調整）
+fn build_translation_api_options(model_type: ModelType, domain: Domain) -> serde_json::Value {
+    let mut options = build_api_options(model_type);
+    domain.apply_sampling(&mut options);
+    options
+}
+
 /// PLaMo-2-Translate用プロンプトを構築（シンプル）
 fn build_plamo_prompt(text: &str, source_lang: Language, target_lang: Language) -> String {
     match (source_lang, target_lang) {
@@ -230,8 +500,26 @@ fn build_plamo_prompt(text: &str, source_lang: Language, target_lang: Language)
 }
 
 /// 汎用LLM用プロンプトを構築（シンプル）
-fn build_general_prompt(text: &str, source_lang: Language, target_lang: Language) -> String {
-    match (source_lang, target_lang) {
+///
+/// ドメイン・文体が指定されている場合は先頭に指示を付加し、
+/// テキストに含まれるドメインの用語があれば用語集として添える。
+/// 前後の文脈がある場合は参考情報として示し、翻訳対象をタグで囲む
+fn build_general_prompt(
+    text: &str,
+    source_lang: Language,
+    target_lang: Language,
+    translation_options: &TranslationOptions,
+) -> String {
+    let glossary = translation_options
+        .domain
+        .glossary_for(text, source_lang, target_lang);
+    let context = translation_options.context();
+    let text = &match context {
+        Some(context) => format_context_body(text, context, target_lang),
+        None => text.to_string(),
+    };
+
+    let prompt = match (source_lang, target_lang) {
         (Language::Japanese, Language::English) => {
             // 日本語→英語: 超シンプル
             format!(
@@ -251,20 +539,50 @@ fn build_general_prompt(text: &str, source_lang: Language, target_lang: Language
                 text
             )
         }
+    };
+
+    let mut instructions = Vec::new();
+    if let Some(instruction) = translation_options.domain.instruction(target_lang) {
+        instructions.push(instruction.to_string());
+    }
+    if !glossary.is_empty() {
+        let label = match target_lang {
+            Language::Japanese => "用語集",
+            Language::English => "Glossary",
+        };
+        instructions.push(format!("{}: {}", label, domain::format_glossary(&glossary)));
+    }
+    if let Some(instruction) = translation_options.formality.instruction(target_lang) {
+        instructions.push(instruction.to_string());
+    }
+    if context.is_some() {
+        instructions.push(context_instruction(target_lang).to_string());
+    }
+
+    if instructions.is_empty() {
+        prompt
+    } else {
+        format!("{}\n{}", instructions.join("\n"), prompt)
     }
 }
 
 /// 翻訳用プロンプトを構築（モデルと言語方向に応じて最適化）
-fn build_translation_prompt(
+///
+/// 翻訳特化モデルは指示に従わず文脈も翻訳してしまうため、
+/// ドメイン・文体指示と文脈は汎用LLMにのみ付加する
+pub(crate) fn build_translation_prompt(
     text: &str,
     source_lang: Language,
     target_lang: Language,
     model: &str,
+    translation_options: &TranslationOptions,
 ) -> String {
     let model_type = detect_model_type(model);
     match model_type {
         ModelType::PlamoTranslate => build_plamo_prompt(text, source_lang, target_lang),
-        ModelType::GeneralPurpose => build_general_prompt(text, source_lang, target_lang),
+        ModelType::GeneralPurpose => {
+            build_general_prompt(text, source_lang, target_lang, translation_options)
+        }
     }
 }
 
@@ -327,6 +645,116 @@ fn clean_translation_result(text: &str, source_text: &str) -> String {
     result
 }
 
+/// 構造化出力のシステムメッセージ
+const STRUCTURED_OUTPUT_INSTRUCTION: &str =
+    "Respond only with JSON in the form {\"translation\": \"<translated text>\"}.";
+
+/// 翻訳の構造化出力
+#[derive(Debug, Deserialize)]
+struct StructuredTranslation {
+    translation: String,
+}
+
+/// 構造化出力を使用するかどうか
+///
+/// 翻訳特化モデルはJSON形式の指示に従わないため汎用LLMのみ
+fn uses_structured_output(model_type: ModelType) -> bool {
+    model_type == ModelType::GeneralPurpose
+}
+
+/// 翻訳リクエストのボディを構築
+///
+/// 構造化出力を使用する場合は、Ollamaの`format`パラメータにJSONスキーマを指定する
+fn build_translation_request(
+    prompt: &str,
+    model: &str,
+    model_type: ModelType,
+    options: serde_json::Value,
+) -> serde_json::Value {
+    let mut request_body = serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": false,
+        "options": options,
+        "keep_alive": "10m"
+    });
+
+    if uses_structured_output(model_type) {
+        request_body["messages"] = serde_json::json!([
+            {
+                "role": "system",
+                "content": STRUCTURED_OUTPUT_INSTRUCTION
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ]);
+        request_body["format"] = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "translation": { "type": "string" }
+            },
+            "required": ["translation"]
+        });
+    }
+
+    request_body
+}
+
+/// モデル出力をJSONオブジェクトとして解釈する
+///
+/// 前後に説明文やコードブロックが付いている場合はJSON部分のみを解釈する
+pub(crate) fn parse_json_object<T: serde::de::DeserializeOwned>(content: &str) -> Option<T> {
+    let content = content.trim();
+    serde_json::from_str(content).ok().or_else(|| {
+        let start = content.find('{')?;
+        let end = content.rfind('}')?;
+        serde_json::from_str(content.get(start..=end)?).ok()
+    })
+}
+
+/// モデル出力から訳文を取り出す
+///
+/// 構造化出力を解釈できればその訳文を使い、できなければヒューリスティックな整形にフォールバックする。
+/// 前後の文脈を指定した場合は翻訳対象部分の訳文のみを取り出す
+fn extract_translation(
+    content: &str,
+    source_text: &str,
+    model_type: ModelType,
+    translation_options: &TranslationOptions,
+) -> (String, OutputPath) {
+    let structured = uses_structured_output(model_type);
+
+    if structured {
+        if let Some(parsed) = parse_json_object::<StructuredTranslation>(content) {
+            let translation = match translation_options.context() {
+                Some(_) => extract_selection_translation(&parsed.translation),
+                None => parsed.translation.trim().to_string(),
+            };
+            if !translation.is_empty() {
+                return (translation, OutputPath::Structured);
+            }
+        }
+    }
+
+    let content = match translation_options.context() {
+        Some(_) => extract_selection_translation(content),
+        None => content.to_string(),
+    };
+    let output_path = if structured {
+        OutputPath::Fallback
+    } else {
+        OutputPath::Heuristic
+    };
+    (clean_translation_result(&content, source_text), output_path)
+}
+
 /// Ollamaレスポンス（chat API）
 #[derive(Debug, Deserialize)]
 struct OllamaChatResponse {
@@ -360,6 +788,7 @@ pub struct StreamChunk {
 pub struct StreamComplete {
     pub translated_text: String,
     pub duration_ms: u64,
+    pub warnings: Vec<QaWarning>,
 }
 
 /// グローバルHTTPクライアント（コネクションプーリング）
@@ -376,43 +805,14 @@ fn get_http_client() -> &'static reqwest::Client {
     })
 }
 
-/// Ollamaで翻訳を実行
-pub async fn translate_with_ollama(
-    text: &str,
-    source_lang: Language,
-    target_lang: Language,
-    endpoint: &str,
-    model: &str,
-) -> Result<TranslationResult, TranslationError> {
-    let start = Instant::now();
-    let client = get_http_client();
-
-    // モデル種別を判定
-    let model_type = detect_model_type(model);
-
-    // プロンプト構築（モデルと言語方向に応じて最適化）
-    let prompt = build_translation_prompt(text, source_lang, target_lang, model);
-    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
-
-    // APIパラメータ構築
-    let options = build_api_options(model_type);
-
-    let request_body = serde_json::json!({
-        "model": model,
-        "messages": [
-            {
-                "role": "user",
-                "content": prompt
-            }
-        ],
-        "stream": false,
-        "options": options,
-        "keep_alive": "10m"
-    });
-
-    let response = client
-        .post(&url)
-        .json(&request_body)
+/// Ollamaのchat APIにリクエストを送信し、応答メッセージの本文を返す
+async fn send_chat_request(
+    url: &str,
+    request_body: &serde_json::Value,
+) -> Result<String, TranslationError> {
+    let response = get_http_client()
+        .post(url)
+        .json(request_body)
         .send()
         .await
         .map_err(|e| {
@@ -441,164 +841,442 @@ pub async fn translate_with_ollama(
         .await
         .map_err(|e| TranslationError::ApiError(format!("レスポンスのパースに失敗: {}", e)))?;
 
-    let duration_ms = start.elapsed().as_millis() as u64;
-
-    // ポストプロセシング（強化版）
-    let translated = clean_translation_result(&chat_response.message.content, text);
-
-    Ok(TranslationResult {
-        translated_text: translated,
-        source_lang,
-        target_lang,
-        duration_ms,
-    })
+    Ok(chat_response.message.content)
 }
 
-/// Ollama接続状態を確認
-pub async fn check_ollama_status(endpoint: &str) -> ProviderStatus {
-    let client = match reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .build()
-    {
-        Ok(c) => c,
-        Err(e) => {
-            return ProviderStatus::Unavailable {
-                reason: e.to_string(),
-            }
-        }
-    };
-
-    let url = format!("{}/api/tags", endpoint.trim_end_matches('/'));
-
-    match client.get(&url).send().await {
-        Ok(response) if response.status().is_success() => ProviderStatus::Available,
-        Ok(response) => ProviderStatus::Unavailable {
-            reason: format!("HTTPエラー: {}", response.status()),
-        },
-        Err(e) => ProviderStatus::Unavailable {
-            reason: if e.is_connect() {
-                "Ollamaが起動していません".to_string()
-            } else if e.is_timeout() {
-                "接続がタイムアウトしました".to_string()
-            } else {
-                e.to_string()
-            },
-        },
-    }
+/// Ollamaのchat APIにストリーミングでリクエストを送信する
+///
+/// 受信したチャンクごとに`event`イベントを発行し、受信したテキスト全体を返す
+async fn stream_chat_request<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    url: &str,
+    request_body: &serde_json::Value,
+    event: &str,
+) -> Result<String, TranslationError> {
+    stream_chat_request_with(app, url, request_body, event, str::to_string).await
 }
 
-/// Ollamaモデルをプリロード（ウォームアップ）
+/// Ollamaのchat APIにストリーミングでリクエストを送信し、表示用に変換したテキストを配信する
 ///
-/// 空のリクエストを送信してモデルをメモリにロードし、
-/// 初回翻訳時のレイテンシを削減する
-pub async fn preload_ollama_model(endpoint: &str, model: &str) -> Result<(), String> {
-    let client = get_http_client();
-
-    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
-
-    let request_body = serde_json::json!({
-        "model": model,
-        "messages": [
-            {
-                "role": "user",
-                "content": "hi"
-            }
-        ],
-        "stream": false,
-        "keep_alive": "10m"
-    });
-
-    client
-        .post(&url)
-        .json(&request_body)
+/// 受信したテキスト全体を`visible`で表示用のテキストに変換し、その差分を`event`イベントで発行する。
+/// 構造化出力（JSON）のうち特定のフィールドだけを逐次表示する場合に使用する。戻り値は変換前のテキスト全体
+async fn stream_chat_request_with<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    url: &str,
+    request_body: &serde_json::Value,
+    event: &str,
+    visible: impl Fn(&str) -> String,
+) -> Result<String, TranslationError> {
+    let response = get_http_client()
+        .post(url)
+        .json(request_body)
         .send()
         .await
         .map_err(|e| {
-            if e.is_connect() {
-                "Ollamaが起動していません".to_string()
-            } else if e.is_timeout() {
-                "プリロードがタイムアウトしました".to_string()
+            if e.is_timeout() {
+                TranslationError::Timeout
+            } else if e.is_connect() {
+                TranslationError::ConnectionFailed(
+                    "Ollamaが起動していません。Ollamaを起動してください。".to_string(),
+                )
             } else {
-                e.to_string()
+                TranslationError::ConnectionFailed(e.to_string())
             }
         })?;
 
-    Ok(())
-}
+    let mut stream = response.bytes_stream();
+    let mut accumulated = String::new();
+    let mut shown = String::new();
 
-/// ストリーミング翻訳を実行
-pub async fn translate_with_ollama_stream<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|e| TranslationError::ConnectionFailed(e.to_string()))?;
+        let line = String::from_utf8_lossy(&bytes);
+
+        if let Ok(resp) = serde_json::from_str::<OllamaStreamResponse>(&line) {
+            if let Some(msg) = resp.message {
+                accumulated.push_str(&msg.content);
+                let visible_text = visible(&accumulated);
+                let chunk = visible_text
+                    .strip_prefix(shown.as_str())
+                    .unwrap_or(&visible_text)
+                    .to_string();
+                shown = visible_text;
+
+                // チャンクイベント発行
+                let _ = app.emit(
+                    event,
+                    StreamChunk {
+                        chunk,
+                        accumulated: shown.clone(),
+                        done: resp.done,
+                    },
+                );
+            }
+
+            if resp.done {
+                break;
+            }
+        }
+    }
+
+    Ok(accumulated)
+}
+
+/// Ollamaで翻訳を実行
+pub async fn translate_with_ollama(
     text: &str,
     source_lang: Language,
     target_lang: Language,
+    translation_options: &TranslationOptions,
     endpoint: &str,
     model: &str,
-) -> Result<(), TranslationError> {
+) -> Result<TranslationResult, TranslationError> {
     let start = Instant::now();
-    let client = get_http_client();
 
-    // プロンプト構築
-    let prompt = build_translation_prompt(text, source_lang, target_lang, model);
+    // モデル種別を判定
+    let model_type = detect_model_type(model);
+
+    // プロンプト構築（モデルと言語方向に応じて最適化）
+    let prompt =
+        build_translation_prompt(text, source_lang, target_lang, model, translation_options);
     let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+
+    // APIパラメータ構築
+    let options = build_translation_api_options(model_type, translation_options.domain);
+    let request_body = build_translation_request(&prompt, model, model_type, options);
+
+    let content = send_chat_request(&url, &request_body).await?;
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    // ポストプロセシング（構造化出力、またはヒューリスティックな整形）
+    let (translated, output_path) =
+        extract_translation(&content, text, model_type, translation_options);
+    let warnings = quality::check_translation(text, &translated, source_lang, target_lang);
+
+    Ok(TranslationResult {
+        translated_text: translated,
+        source_lang,
+        target_lang,
+        duration_ms,
+        warnings,
+        output_path,
+        reading: None,
+        prompt: None,
+    })
+}
+
+/// 翻訳候補のスタイル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationStyle {
+    /// 通常の翻訳
+    Standard,
+    /// 丁寧・フォーマル
+    Formal,
+    /// くだけた口語体
+    Casual,
+    /// 簡潔
+    Concise,
+}
+
+impl TranslationStyle {
+    /// 候補を生成する順序
+    pub const ALL: [TranslationStyle; 4] = [
+        TranslationStyle::Standard,
+        TranslationStyle::Formal,
+        TranslationStyle::Casual,
+        TranslationStyle::Concise,
+    ];
+
+    /// プロンプトに付加するスタイル指示（翻訳先言語で記述）
+    ///
+    /// 丁寧・口語体は文体設定（`Formality`）と同じ指示を使用する
+    fn instruction(&self, target_lang: Language) -> Option<&'static str> {
+        match (self, target_lang) {
+            (TranslationStyle::Standard, _) => None,
+            (TranslationStyle::Formal, _) => Formality::Polite.instruction(target_lang),
+            (TranslationStyle::Casual, _) => Formality::Casual.instruction(target_lang),
+            (TranslationStyle::Concise, Language::Japanese) => {
+                Some("意味を保ったままできるだけ簡潔に翻訳してください。")
+            }
+            (TranslationStyle::Concise, Language::English) => {
+                Some("Keep the translation as concise as possible without losing meaning.")
+            }
+        }
+    }
+}
+
+/// 翻訳候補
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationCandidate {
+    /// 候補のスタイル
+    pub style: TranslationStyle,
+    /// 翻訳されたテキスト
+    pub translated_text: String,
+    /// 品質チェックの警告
+    pub warnings: Vec<QaWarning>,
+}
+
+/// 複数候補の翻訳結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlternativesResult {
+    /// 品質チェックの警告が少ない順に並べた候補
+    pub candidates: Vec<TranslationCandidate>,
+    /// 翻訳元言語
+    pub source_lang: Language,
+    /// 翻訳先言語
+    pub target_lang: Language,
+    /// 翻訳にかかった時間（ミリ秒）
+    pub duration_ms: u64,
+}
+
+/// 生成する候補数の上限
+pub const MAX_ALTERNATIVES: usize = TranslationStyle::ALL.len();
+
+/// スタイル指示付きの翻訳プロンプトを構築
+///
+/// 翻訳特化モデルは指示に従わないため、スタイル指示は汎用LLMにのみ付加する。
+/// フォーマル・カジュアルの候補ではスタイル指示を優先し、文体指定は適用しない
+fn build_alternative_prompt(
+    text: &str,
+    source_lang: Language,
+    target_lang: Language,
+    model: &str,
+    style: TranslationStyle,
+    translation_options: &TranslationOptions,
+) -> String {
+    let mut translation_options = translation_options.clone();
+    if matches!(style, TranslationStyle::Formal | TranslationStyle::Casual) {
+        translation_options.formality = Formality::Auto;
+    }
+    let prompt =
+        build_translation_prompt(text, source_lang, target_lang, model, &translation_options);
+    match (detect_model_type(model), style.instruction(target_lang)) {
+        (ModelType::GeneralPurpose, Some(instruction)) => format!("{}\n{}", instruction, prompt),
+        _ => prompt,
+    }
+}
+
+/// 候補ごとのAPIパラメータを構築
+///
+/// 候補ごとにシードを変え、2番目以降は温度を少しずつ上げて出力に幅を持たせる
+fn build_alternative_options(
+    model_type: ModelType,
+    domain: Domain,
+    index: usize,
+) -> serde_json::Value {
+    let mut options = build_translation_api_options(model_type, domain);
+    if let Some(temperature) = options["temperature"].as_f64() {
+        options["temperature"] = serde_json::json!(temperature + 0.15 * index as f64);
+    }
+    options["seed"] = serde_json::json!(index);
+    options
+}
+
+/// 候補を品質チェックの警告が少ない順に並べ、重複する訳文を除く
+///
+/// 警告数が同じ場合は生成順（通常の翻訳が先頭）を保つ
+fn rank_candidates(candidates: Vec<TranslationCandidate>) -> Vec<TranslationCandidate> {
+    let mut ranked: Vec<TranslationCandidate> = Vec::new();
+    for candidate in candidates {
+        if candidate.translated_text.is_empty()
+            || ranked
+                .iter()
+                .any(|c| c.translated_text == candidate.translated_text)
+        {
+            continue;
+        }
+        ranked.push(candidate);
+    }
+    ranked.sort_by_key(|c| c.warnings.len());
+    ranked
+}
+
+/// Ollamaで複数の翻訳候補を生成
+///
+/// 通常・フォーマル・カジュアル・簡潔のスタイルから先頭`count`個の候補を並行して生成し、
+/// 品質チェックの警告が少ない順に返す。一部の候補が失敗しても残りの候補を返す
+pub async fn translate_alternatives_with_ollama(
+    text: &str,
+    source_lang: Language,
+    target_lang: Language,
+    translation_options: &TranslationOptions,
+    endpoint: &str,
+    model: &str,
+    count: usize,
+) -> Result<AlternativesResult, TranslationError> {
+    let start = Instant::now();
     let model_type = detect_model_type(model);
-    let options = build_api_options(model_type);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+
+    let requests = TranslationStyle::ALL
+        .iter()
+        .take(count.clamp(1, MAX_ALTERNATIVES))
+        .enumerate()
+        .map(|(index, &style)| {
+            let prompt = build_alternative_prompt(
+                text,
+                source_lang,
+                target_lang,
+                model,
+                style,
+                translation_options,
+            );
+            let request_body = build_translation_request(
+                &prompt,
+                model,
+                model_type,
+                build_alternative_options(model_type, translation_options.domain, index),
+            );
+            let url = url.clone();
+            async move {
+                let content = send_chat_request(&url, &request_body).await?;
+                let (translated_text, _) =
+                    extract_translation(&content, text, model_type, translation_options);
+                let warnings =
+                    quality::check_translation(text, &translated_text, source_lang, target_lang);
+                Ok::<_, TranslationError>(TranslationCandidate {
+                    style,
+                    translated_text,
+                    warnings,
+                })
+            }
+        });
+
+    let mut candidates = Vec::new();
+    let mut last_error = None;
+    for result in futures::future::join_all(requests).await {
+        match result {
+            Ok(candidate) => candidates.push(candidate),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    let candidates = rank_candidates(candidates);
+    if candidates.is_empty() {
+        return Err(last_error.unwrap_or_else(|| {
+            TranslationError::ApiError("翻訳候補を生成できませんでした".to_string())
+        }));
+    }
+
+    Ok(AlternativesResult {
+        candidates,
+        source_lang,
+        target_lang,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Ollama接続状態を確認
+pub async fn check_ollama_status(endpoint: &str) -> ProviderStatus {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            return ProviderStatus::Unavailable {
+                reason: e.to_string(),
+            }
+        }
+    };
+
+    let url = format!("{}/api/tags", endpoint.trim_end_matches('/'));
+
+    match client.get(&url).send().await {
+        Ok(response) if response.status().is_success() => ProviderStatus::Available,
+        Ok(response) => ProviderStatus::Unavailable {
+            reason: format!("HTTPエラー: {}", response.status()),
+        },
+        Err(e) => ProviderStatus::Unavailable {
+            reason: if e.is_connect() {
+                "Ollamaが起動していません".to_string()
+            } else if e.is_timeout() {
+                "接続がタイムアウトしました".to_string()
+            } else {
+                e.to_string()
+            },
+        },
+    }
+}
+
+/// Ollamaモデルをプリロード（ウォームアップ）
+///
+/// 空のリクエストを送信してモデルをメモリにロードし、
+/// 初回翻訳時のレイテンシを削減する
+pub async fn preload_ollama_model(endpoint: &str, model: &str) -> Result<(), String> {
+    let client = get_http_client();
+
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
 
     let request_body = serde_json::json!({
         "model": model,
-        "messages": [{"role": "user", "content": prompt}],
-        "stream": true,
-        "options": options,
+        "messages": [
+            {
+                "role": "user",
+                "content": "hi"
+            }
+        ],
+        "stream": false,
         "keep_alive": "10m"
     });
 
-    let response = client
+    client
         .post(&url)
         .json(&request_body)
         .send()
         .await
         .map_err(|e| {
-            if e.is_timeout() {
-                TranslationError::Timeout
-            } else if e.is_connect() {
-                TranslationError::ConnectionFailed(
-                    "Ollamaが起動していません。Ollamaを起動してください。".to_string(),
-                )
+            if e.is_connect() {
+                "Ollamaが起動していません".to_string()
+            } else if e.is_timeout() {
+                "プリロードがタイムアウトしました".to_string()
             } else {
-                TranslationError::ConnectionFailed(e.to_string())
+                e.to_string()
             }
         })?;
 
-    let mut stream = response.bytes_stream();
-    let mut accumulated = String::new();
+    Ok(())
+}
 
-    while let Some(chunk) = stream.next().await {
-        let bytes = chunk.map_err(|e| TranslationError::ConnectionFailed(e.to_string()))?;
-        let line = String::from_utf8_lossy(&bytes);
+/// ストリーミング翻訳を実行
+pub async fn translate_with_ollama_stream<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    text: &str,
+    source_lang: Language,
+    target_lang: Language,
+    translation_options: &TranslationOptions,
+    endpoint: &str,
+    model: &str,
+) -> Result<(), TranslationError> {
+    let start = Instant::now();
 
-        if let Ok(resp) = serde_json::from_str::<OllamaStreamResponse>(&line) {
-            if let Some(msg) = resp.message {
-                accumulated.push_str(&msg.content);
+    // プロンプト構築
+    let prompt =
+        build_translation_prompt(text, source_lang, target_lang, model, translation_options);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+    let model_type = detect_model_type(model);
+    let options = build_translation_api_options(model_type, translation_options.domain);
 
-                // チャンクイベント発行
-                let _ = app.emit(
-                    "translation-chunk",
-                    StreamChunk {
-                        chunk: msg.content,
-                        accumulated: accumulated.clone(),
-                        done: resp.done,
-                    },
-                );
-            }
+    let request_body = serde_json::json!({
+        "model": model,
+        "messages": [{"role": "user", "content": prompt}],
+        "stream": true,
+        "options": options,
+        "keep_alive": "10m"
+    });
 
-            if resp.done {
-                break;
-            }
-        }
-    }
+    let accumulated = stream_chat_request(app, &url, &request_body, "translation-chunk").await?;
 
     let duration_ms = start.elapsed().as_millis() as u64;
+    let accumulated = match translation_options.context() {
+        Some(_) => extract_selection_translation(&accumulated),
+        None => accumulated,
+    };
     let translated = clean_translation_result(&accumulated, text);
+    let warnings = quality::check_translation(text, &translated, source_lang, target_lang);
 
     // 完了イベント発行
     let _ = app.emit(
@@ -606,24 +1284,161 @@ pub async fn translate_with_ollama_stream<R: tauri::Runtime>(
         StreamComplete {
             translated_text: translated,
             duration_ms,
+            warnings,
         },
     );
 
     Ok(())
 }
 
+/// 会話履歴をもとに改訂版の翻訳をストリーミングで生成
+///
+/// 訳文を`refinement-chunk`イベントで逐次配信し、完了結果を返す。
+/// 完了イベントはセッションの更新後に呼び出し元で発行する
+pub async fn refine_with_ollama_stream<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    session: &RefinementSession,
+    endpoint: &str,
+    model: &str,
+) -> Result<RefinementComplete, TranslationError> {
+    let start = Instant::now();
+
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+    let options = build_api_options(detect_model_type(model));
+    let request_body = serde_json::json!({
+        "model": model,
+        "messages": session.messages,
+        "stream": true,
+        "options": options,
+        "keep_alive": "10m"
+    });
+
+    let accumulated = stream_chat_request(app, &url, &request_body, "refinement-chunk").await?;
+
+    Ok(build_refinement_complete(
+        session,
+        &accumulated,
+        start.elapsed().as_millis() as u64,
+    ))
+}
+
+/// モデルの出力から改訂版の訳文を整形し、品質チェックの結果とともに完了結果を組み立てる
+pub(crate) fn build_refinement_complete(
+    session: &RefinementSession,
+    content: &str,
+    duration_ms: u64,
+) -> RefinementComplete {
+    let translated_text = clean_translation_result(content, &session.source_text);
+    let warnings = quality::check_translation(
+        &session.source_text,
+        &translated_text,
+        session.source_lang,
+        session.target_lang,
+    );
+
+    RefinementComplete {
+        session_id: session.id.clone(),
+        translated_text,
+        duration_ms,
+        warnings,
+    }
+}
+
+/// 要約の長さ・形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SummaryLength {
+    /// 1文
+    OneLine,
+    /// 3文以内（従来の既定）
+    #[default]
+    ThreeSentences,
+    /// 箇条書き
+    Bullets,
+    /// TL;DR＋詳細
+    TldrDetails,
+}
+
+impl SummaryLength {
+    /// 要約の長さ・形式の指示（要約言語で記述）
+    pub fn instruction(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (SummaryLength::OneLine, Language::Japanese) => "1文で",
+            (SummaryLength::OneLine, Language::English) => "in one sentence",
+            (SummaryLength::ThreeSentences, Language::Japanese) => "3文以内で",
+            (SummaryLength::ThreeSentences, Language::English) => "in 3 sentences or less",
+            (SummaryLength::Bullets, Language::Japanese) => {
+                "3〜5個の箇条書き（各行を「- 」で始める）で"
+            }
+            (SummaryLength::Bullets, Language::English) => {
+                "as 3-5 bullet points (each line starting with \"- \")"
+            }
+            (SummaryLength::TldrDetails, Language::Japanese) => {
+                "「TL;DR:」で始まる1行の結論と、それに続く数行の詳細に分けて"
+            }
+            (SummaryLength::TldrDetails, Language::English) => {
+                "as a one-line conclusion starting with \"TL;DR:\" followed by a few lines of details"
+            }
+        }
+    }
+}
+
+/// 返信の意図
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReplyIntent {
+    /// 指定なし（従来の既定）
+    #[default]
+    General,
+    /// 承諾する
+    Accept,
+    /// 断る
+    Decline,
+    /// 不明点を確認する
+    Clarify,
+    /// フォローアップする
+    FollowUp,
+}
+
+impl ReplyIntent {
+    /// 返信の意図の指示（返信言語で記述）
+    pub fn instruction(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (ReplyIntent::General, _) => "",
+            (ReplyIntent::Accept, Language::Japanese) => "依頼や提案を承諾する",
+            (ReplyIntent::Accept, Language::English) => ", accepting the request or proposal",
+            (ReplyIntent::Decline, Language::Japanese) => "依頼や提案を丁重に断る",
+            (ReplyIntent::Decline, Language::English) => {
+                ", politely declining the request or proposal"
+            }
+            (ReplyIntent::Clarify, Language::Japanese) => "不明点を確認する質問をする",
+            (ReplyIntent::Clarify, Language::English) => {
+                ", asking questions to clarify unclear points"
+            }
+            (ReplyIntent::FollowUp, Language::Japanese) => {
+                "進捗や今後の対応を確認するフォローアップの"
+            }
+            (ReplyIntent::FollowUp, Language::English) => {
+                ", following up on progress and next steps"
+            }
+        }
+    }
+}
+
 /// 要約用プロンプトを構築（極限までシンプル化）
-fn build_summarize_prompt(text: &str, language: Language) -> String {
+fn build_summarize_prompt(text: &str, language: Language, length: SummaryLength) -> String {
     match language {
         Language::Japanese => {
             format!(
-                "以下の日本語テキストを3文以内で日本語で要約してください。要約のみを出力してください。\n\n{}",
+                "以下の日本語テキストを{}日本語で要約してください。要約のみを出力してください。\n\n{}",
+                length.instruction(language),
                 text
             )
         }
         Language::English => {
             format!(
-                "Summarize the following English text in 3 sentences or less in English. Output only the summary.\n\n{}",
+                "Summarize the following English text {} in English. Output only the summary.\n\n{}",
+                length.instruction(language),
                 text
             )
         }
@@ -632,104 +1447,285 @@ fn build_summarize_prompt(text: &str, language: Language) -> String {
 
 /// 返信用プロンプトを構築（極限までシンプル化）
 ///
-/// language: 返信を作成する言語
-/// _source_language: 未使用（後方互換性のため保持）
-fn build_reply_prompt(text: &str, language: Language, _source_language: Language) -> String {
-    match language {
+/// reply_language: 返信を作成する言語
+/// _explanation_language: 未使用（説明の翻訳は`build_bilingual_reply_prompt`で指示する）
+/// intent: 返信の意図
+fn build_reply_prompt(
+    text: &str,
+    reply_language: Language,
+    _explanation_language: Language,
+    intent: ReplyIntent,
+) -> String {
+    match reply_language {
         Language::Japanese => {
             format!(
-                "以下の日本語メッセージに対して、丁寧なビジネスメールの返信を日本語で書いてください。返信のみを出力してください。\n\n{}",
+                "以下の日本語メッセージに対して、{}丁寧なビジネスメールの返信を日本語で書いてください。返信のみを出力してください。\n\n{}",
+                intent.instruction(reply_language),
                 text
             )
         }
         Language::English => {
             format!(
-                "Write a polite business email reply to the following English message in English. Output only the reply.\n\n{}",
+                "Write a polite business email reply to the following English message in English{}. Output only the reply.\n\n{}",
+                intent.instruction(reply_language),
                 text
             )
         }
     }
 }
 
-/// 返信レスポンスをパースして返信と翻訳を分離
-/// 注: 2段階処理実装後は未使用。テストのために保持。
-#[allow(dead_code)]
-fn parse_reply_response(response: &str) -> (String, String) {
-    let response = response.trim();
+/// 要約リクエストのボディを構築（ストリーミング・非ストリーミング共通）
+fn build_summarize_request(
+    prompt: &str,
+    language: Language,
+    model: &str,
+    stream: bool,
+) -> serde_json::Value {
+    // モデル種別を判定してAPIパラメータ構築
+    let options = build_api_options(detect_model_type(model));
 
-    // 新形式のマーカー（REPLY:、TRANSLATION:）を優先的に探す
-    let reply_markers = ["REPLY:", "Reply:", "reply:", "[返信]", "[Reply]"];
-    let translation_markers = [
-        "TRANSLATION:",
-        "Translation:",
-        "translation:",
-        "[翻訳]",
-        "[Translation]",
-        "[説明]",
-        "[Explanation]",
-    ];
+    // システムメッセージ（言語固定の指示）
+    let system_message = match language {
+        Language::Japanese => "あなたは日本語の要約専門家です。必ず日本語でのみ応答してください。絶対に英語に翻訳しないでください。",
+        Language::English => "You are an English summarization expert. You MUST respond in English only. DO NOT translate to Japanese.",
+    };
 
-    let mut reply = String::new();
-    let mut translation = String::new();
+    serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": system_message
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": stream,
+        "options": options,
+        "keep_alive": "10m"
+    })
+}
 
-    // 返信部分を抽出
-    for marker in &reply_markers {
-        if let Some(start) = response.find(marker) {
-            let after_marker = &response[start + marker.len()..];
-            // 次のセクション（TRANSLATION:など）までを取得
-            let end = translation_markers
-                .iter()
-                .filter_map(|m| after_marker.find(m))
-                .min()
-                .unwrap_or(after_marker.len());
-            reply = after_marker[..end].trim().to_string();
-            break;
+/// 返信リクエストのボディを構築（ストリーミング・非ストリーミング共通）
+fn build_reply_request(
+    prompt: &str,
+    reply_language: Language,
+    model: &str,
+    stream: bool,
+) -> serde_json::Value {
+    // モデル種別を判定してAPIパラメータ構築
+    let options = build_api_options(detect_model_type(model));
+
+    // システムメッセージ（単一言語の返信のみ）
+    let system_message = match reply_language {
+        Language::Japanese => {
+            "あなたはビジネスメールの返信作成専門家です。必ず日本語でのみ返信を作成してください。"
         }
-    }
+        Language::English => {
+            "You are a business email reply expert. You MUST write the reply in English only."
+        }
+    };
 
-    // 翻訳部分を抽出
-    for marker in &translation_markers {
-        if let Some(start) = response.find(marker) {
-            translation = response[start + marker.len()..].trim().to_string();
-            break;
+    serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": system_message
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": stream,
+        "options": options,
+        "keep_alive": "10m"
+    })
+}
+
+/// 対訳付き返信用プロンプトを構築
+///
+/// 返信（`reply_language`）とその翻訳（`explanation_language`）をJSONで同時に出力させる
+pub(crate) fn build_bilingual_reply_prompt(
+    text: &str,
+    reply_language: Language,
+    explanation_language: Language,
+    intent: ReplyIntent,
+) -> String {
+    match reply_language {
+        Language::Japanese => {
+            let translation_lang = match explanation_language {
+                Language::Japanese => "日本語",
+                Language::English => "英語",
+            };
+            format!(
+                "以下の日本語メッセージに対して、{}丁寧なビジネスメールの返信を日本語で書いてください。\nJSONで出力し、\"reply\"に日本語の返信、\"translation\"にその返信の{}訳を入れてください。\n\n{}",
+                intent.instruction(reply_language),
+                translation_lang,
+                text
+            )
+        }
+        Language::English => {
+            format!(
+                "Write a polite business email reply to the following English message in English{}.\nOutput JSON with the English reply in \"reply\" and its {} translation in \"translation\".\n\n{}",
+                intent.instruction(reply_language),
+                explanation_language.name(),
+                text
+            )
         }
     }
+}
 
-    // マーカーが見つからない場合
-    if reply.is_empty() && translation.is_empty() {
-        eprintln!(
-            "[WARNING] 返信レスポンスのパースに失敗しました。マーカーが見つかりませんでした。"
-        );
-        eprintln!("[WARNING] レスポンス全文: {}", response);
-
-        // 行ごとに分割して、最初の2行を返信と翻訳として扱う
-        let lines: Vec<&str> = response.lines().filter(|l| !l.trim().is_empty()).collect();
-        if lines.len() >= 2 {
-            eprintln!("[INFO] フォールバック: 最初の2行を返信と翻訳として使用");
-            return (lines[0].trim().to_string(), lines[1].trim().to_string());
-        } else if lines.len() == 1 {
-            eprintln!("[INFO] フォールバック: 最初の1行のみを返信として使用");
-            return (lines[0].trim().to_string(), String::new());
-        } else {
-            return (response.to_string(), String::new());
+/// 対訳付き返信リクエストのボディを構築
+///
+/// Ollamaの`format`パラメータにJSONスキーマを指定して出力形式を制約する（ストリーミング・非ストリーミング共通）
+fn build_bilingual_reply_request(
+    prompt: &str,
+    reply_language: Language,
+    model: &str,
+    stream: bool,
+) -> serde_json::Value {
+    let options = build_api_options(detect_model_type(model));
+
+    let system_message = match reply_language {
+        Language::Japanese => {
+            "あなたはビジネスメールの返信作成専門家です。返信は必ず日本語で作成し、指定されたJSON形式でのみ出力してください。"
         }
-    }
+        Language::English => {
+            "You are a business email reply expert. You MUST write the reply in English and output only the requested JSON."
+        }
+    };
+
+    serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": system_message
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": stream,
+        "format": {
+            "type": "object",
+            "properties": {
+                "reply": { "type": "string" },
+                "translation": { "type": "string" }
+            },
+            "required": ["reply", "translation"]
+        },
+        "options": options,
+        "keep_alive": "10m"
+    })
+}
+
+/// 対訳付き返信の構造化出力
+#[derive(Debug, Deserialize)]
+struct StructuredReply {
+    reply: String,
+    #[serde(default)]
+    translation: String,
+}
+
+/// 対訳付き返信の出力をパースして返信と翻訳を取り出す
+///
+/// JSONとして解釈できない場合や返信が空の場合はNone、翻訳が空の場合は翻訳のみNone
+pub(crate) fn parse_structured_reply(content: &str) -> Option<(String, Option<String>)> {
+    let parsed = parse_json_object::<StructuredReply>(content)?;
 
-    // 返信または翻訳が空の場合もログに記録
+    let reply = parsed.reply.trim().to_string();
     if reply.is_empty() {
-        eprintln!("[WARNING] 返信部分が空です");
+        return None;
     }
-    if translation.is_empty() {
-        eprintln!("[WARNING] 翻訳部分が空です");
+    let translation = parsed.translation.trim().to_string();
+    Some((reply, (!translation.is_empty()).then_some(translation)))
+}
+
+/// 受信途中のJSONから文字列フィールドの値を取り出す（ストリーミング表示用）
+///
+/// 閉じられていない文字列はその時点までの値を返し、フィールドがまだない場合は空文字を返す
+fn partial_json_string_field(content: &str, field: &str) -> String {
+    let key = format!("\"{}\"", field);
+    let Some(rest) = content
+        .find(&key)
+        .map(|index| content[index + key.len()..].trim_start())
+        .and_then(|rest| rest.strip_prefix(':'))
+        .and_then(|rest| rest.trim_start().strip_prefix('"'))
+    else {
+        return String::new();
+    };
+
+    let mut value = String::new();
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('r') => value.push('\r'),
+                Some('u') => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                        Some(c) => value.push(c),
+                        None => break,
+                    }
+                }
+                Some(c) => value.push(c),
+                None => break,
+            },
+            c => value.push(c),
+        }
     }
+    value
+}
+
+/// 対訳付き返信の出力から返信と説明を取り出す
+///
+/// モデルが形式に従わなかった場合は応答全体を返信とし、翻訳が得られなかった場合は返信を別途翻訳して説明とする
+async fn resolve_bilingual_reply(
+    content: &str,
+    text: &str,
+    reply_language: Language,
+    explanation_language: Language,
+    endpoint: &str,
+    model: &str,
+) -> Result<(String, String), TranslationError> {
+    let (reply, translation) = parse_structured_reply(content).unwrap_or_else(|| {
+        eprintln!("[WARNING] 構造化出力のパースに失敗しました。応答全体を返信として扱います。");
+        (clean_translation_result(content, text), None)
+    });
 
-    (reply, translation)
+    let explanation = match translation {
+        Some(translation) => translation,
+        None => {
+            eprintln!("[INFO] フォールバック: 返信を別途翻訳します");
+            translate_with_ollama(
+                &reply,
+                reply_language,
+                explanation_language,
+                &TranslationOptions::default(),
+                endpoint,
+                model,
+            )
+            .await?
+            .translated_text
+        }
+    };
+    Ok((reply, explanation))
 }
 
 /// Ollamaで要約を実行
 pub async fn summarize_with_ollama(
     text: &str,
     language: Language,
+    length: SummaryLength,
     endpoint: &str,
     model: &str,
 ) -> Result<SummarizeResult, TranslationError> {
@@ -737,35 +1733,104 @@ pub async fn summarize_with_ollama(
     validate_model_for_advanced_features(model)?;
 
     let start = Instant::now();
-    let client = get_http_client();
 
     // デバッグログ
     eprintln!("[要約] デバッグ情報:");
     eprintln!("  text length: {}", text.len());
     eprintln!("  language: {:?}", language);
+    eprintln!("  length: {:?}", length);
 
     // プロンプト構築
-    let prompt = build_summarize_prompt(text, language);
+    let prompt = build_summarize_prompt(text, language, length);
     let preview = prompt.chars().take(200).collect::<String>();
     eprintln!("  prompt preview: {}", preview);
     let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
 
-    // モデル種別を判定してAPIパラメータ構築
-    let model_type = detect_model_type(model);
-    let options = build_api_options(model_type);
+    let request_body = build_summarize_request(&prompt, language, model, false);
+    let content = send_chat_request(&url, &request_body).await?;
 
-    // システムメッセージ（言語固定の指示）
-    let system_message = match language {
-        Language::Japanese => "あなたは日本語の要約専門家です。必ず日本語でのみ応答してください。絶対に英語に翻訳しないでください。",
-        Language::English => "You are an English summarization expert. You MUST respond in English only. DO NOT translate to Japanese.",
-    };
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    // デバッグログ: レスポンス
+    eprintln!("[要約] レスポンス:");
+    eprintln!("  content: {}", &content);
+
+    // クリーニング
+    let summary = clean_translation_result(&content, text);
+    eprintln!("[要約] クリーニング後:");
+    eprintln!("  summary: {}", &summary);
+
+    Ok(SummarizeResult {
+        summary: summary.clone(),
+        original_length: text.chars().count(),
+        summary_length: summary.chars().count(),
+        duration_ms,
+    })
+}
+
+/// Ollamaでストリーミング要約を実行
+///
+/// 要約を`summary-chunk`イベントで逐次配信し、完了時に`summary-complete`イベントで結果を通知する
+pub async fn summarize_with_ollama_stream<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    text: &str,
+    language: Language,
+    length: SummaryLength,
+    endpoint: &str,
+    model: &str,
+) -> Result<(), TranslationError> {
+    // モデルサイズ検証
+    validate_model_for_advanced_features(model)?;
+
+    let start = Instant::now();
+
+    let prompt = build_summarize_prompt(text, language, length);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+    let request_body = build_summarize_request(&prompt, language, model, true);
+
+    let accumulated = stream_chat_request(app, &url, &request_body, "summary-chunk").await?;
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+    let summary = clean_translation_result(&accumulated, text);
+
+    // 完了イベント発行
+    let _ = app.emit(
+        "summary-complete",
+        SummarizeResult {
+            original_length: text.chars().count(),
+            summary_length: summary.chars().count(),
+            summary,
+            duration_ms,
+        },
+    );
+
+    Ok(())
+}
+
+/// Ollamaで単語・慣用句を解説
+///
+/// 読み・品詞・意味・例文・ニュアンスを構造化出力で生成する
+pub async fn explain_with_ollama(
+    term: &str,
+    source_lang: Language,
+    target_lang: Language,
+    endpoint: &str,
+    model: &str,
+) -> Result<Explanation, ExplainError> {
+    // モデルサイズ検証
+    validate_model_for_advanced_features(model)?;
+
+    let start = Instant::now();
+
+    let prompt = explain::build_explain_prompt(term, source_lang, target_lang);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
 
     let request_body = serde_json::json!({
         "model": model,
         "messages": [
             {
                 "role": "system",
-                "content": system_message
+                "content": explain::system_prompt(target_lang)
             },
             {
                 "role": "user",
@@ -773,109 +1838,119 @@ pub async fn summarize_with_ollama(
             }
         ],
         "stream": false,
-        "options": options,
+        "format": explain::explanation_schema(source_lang),
+        "options": build_api_options(detect_model_type(model)),
         "keep_alive": "10m"
     });
+    let content = send_chat_request(&url, &request_body).await?;
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                TranslationError::Timeout
-            } else if e.is_connect() {
-                TranslationError::ConnectionFailed(
-                    "Ollamaが起動していません。Ollamaを起動してください。".to_string(),
-                )
-            } else {
-                TranslationError::ConnectionFailed(e.to_string())
-            }
-        })?;
+    explain::build_explanation(
+        &content,
+        term,
+        source_lang,
+        target_lang,
+        start.elapsed().as_millis() as u64,
+    )
+}
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(TranslationError::ApiError(format!(
-            "ステータス {}: {}",
-            status, error_text
-        )));
-    }
+/// Ollamaで文章を校正
+///
+/// 校正後のテキストと修正箇所の一覧を構造化出力で生成する
+pub async fn proofread_with_ollama(
+    text: &str,
+    language: Language,
+    endpoint: &str,
+    model: &str,
+) -> Result<ProofreadResult, ProofreadError> {
+    // モデルサイズ検証
+    validate_model_for_advanced_features(model)?;
 
-    let chat_response: OllamaChatResponse = response
-        .json()
-        .await
-        .map_err(|e| TranslationError::ApiError(format!("レスポンスのパースに失敗: {}", e)))?;
+    let start = Instant::now();
 
-    let duration_ms = start.elapsed().as_millis() as u64;
+    let prompt = proofread::build_proofread_prompt(text, language);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
 
-    // デバッグログ: レスポンス
-    eprintln!("[要約] レスポンス:");
-    eprintln!("  content: {}", &chat_response.message.content);
+    let request_body = serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": proofread::system_prompt(language)
+            },
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ],
+        "stream": false,
+        "format": proofread::proofread_schema(),
+        "options": build_api_options(detect_model_type(model)),
+        "keep_alive": "10m"
+    });
+    let content = send_chat_request(&url, &request_body).await?;
 
-    // クリーニング
-    let summary = clean_translation_result(&chat_response.message.content, text);
-    eprintln!("[要約] クリーニング後:");
-    eprintln!("  summary: {}", &summary);
+    proofread::build_proofread_result(&content, text, language, start.elapsed().as_millis() as u64)
+}
 
-    Ok(SummarizeResult {
-        summary: summary.clone(),
-        original_length: text.chars().count(),
-        summary_length: summary.chars().count(),
-        duration_ms,
-    })
+/// Ollamaで日本語テキストの読み（ふりがな・ローマ字）を生成
+///
+/// 単語への分割とひらがなの読みを構造化出力で生成し、ローマ字は読みから変換する
+pub async fn annotate_reading_with_ollama(
+    text: &str,
+    endpoint: &str,
+    model: &str,
+) -> Result<ReadingAnnotation, ReadingError> {
+    // モデルサイズ検証
+    validate_model_for_advanced_features(model)?;
+
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+    let request_body = serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": reading::system_prompt()
+            },
+            {
+                "role": "user",
+                "content": reading::build_reading_prompt(text)
+            }
+        ],
+        "stream": false,
+        "format": reading::reading_schema(),
+        "options": build_api_options(detect_model_type(model)),
+        "keep_alive": "10m"
+    });
+    let content = send_chat_request(&url, &request_body).await?;
+
+    reading::build_llm_annotation(&content, text)
 }
 
-/// Ollamaで返信を生成
+/// Ollamaでメール・チャットのスレッドを要約
 ///
-/// language: 返信を作成する言語（翻訳先言語）
-/// source_language: 説明を作成する言語（翻訳元言語）
-pub async fn generate_reply_with_ollama(
+/// 発言者ごとに分割したスレッドを渡し、要約・参加者ごとのアクションアイテム・決定事項を
+/// 構造化出力で生成する
+pub async fn summarize_thread_with_ollama(
     text: &str,
     language: Language,
-    source_language: Language,
     endpoint: &str,
     model: &str,
-) -> Result<ReplyResult, TranslationError> {
+) -> Result<ThreadSummary, TranslationError> {
     // モデルサイズ検証
     validate_model_for_advanced_features(model)?;
 
     let start = Instant::now();
-    let client = get_http_client();
-
-    // デバッグログ
-    eprintln!("[返信生成] デバッグ情報:");
-    eprintln!("  text length: {}", text.len());
-    eprintln!("  language: {:?}", language);
-    eprintln!("  source_language: {:?}", source_language);
 
-    // プロンプト構築（返信言語と説明言語を指定）
-    let prompt = build_reply_prompt(text, language, source_language);
-    let preview = prompt.chars().take(200).collect::<String>();
-    eprintln!("  prompt preview: {}", preview);
+    let thread = Thread::parse(text);
+    let prompt = thread::build_thread_prompt(&thread, language);
     let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
 
-    // モデル種別を判定してAPIパラメータ構築
-    let model_type = detect_model_type(model);
-    let options = build_api_options(model_type);
-
-    // システムメッセージ（単一言語の返信のみ）
-    let system_message = match language {
-        Language::Japanese => {
-            "あなたはビジネスメールの返信作成専門家です。必ず日本語でのみ返信を作成してください。"
-        }
-        Language::English => {
-            "You are a business email reply expert. You MUST write the reply in English only."
-        }
-    };
-
     let request_body = serde_json::json!({
         "model": model,
         "messages": [
             {
                 "role": "system",
-                "content": system_message
+                "content": thread::system_prompt(language)
             },
             {
                 "role": "user",
@@ -883,59 +1958,157 @@ pub async fn generate_reply_with_ollama(
             }
         ],
         "stream": false,
-        "options": options,
+        "format": thread::summary_schema(),
+        "options": build_api_options(detect_model_type(model)),
         "keep_alive": "10m"
     });
+    let content = send_chat_request(&url, &request_body).await?;
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                TranslationError::Timeout
-            } else if e.is_connect() {
-                TranslationError::ConnectionFailed(
-                    "Ollamaが起動していません。Ollamaを起動してください。".to_string(),
-                )
-            } else {
-                TranslationError::ConnectionFailed(e.to_string())
-            }
-        })?;
+    Ok(thread::build_summary(
+        &content,
+        thread,
+        start.elapsed().as_millis() as u64,
+    ))
+}
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(TranslationError::ApiError(format!(
-            "ステータス {}: {}",
-            status, error_text
-        )));
-    }
+/// Ollamaで返信を生成
+///
+/// reply_language: 返信を作成する言語（元の文章の言語）
+/// explanation_language: 返信の説明（翻訳）を作成する言語
+/// intent: 返信の意図（承諾・辞退・確認・フォローアップ）
+///
+/// 2つの言語が異なる場合は、返信とその翻訳（説明）を構造化出力で1回の呼び出しで生成する。
+/// モデルが形式に従わなかった場合は、返信を別途翻訳して説明とする
+pub async fn generate_reply_with_ollama(
+    text: &str,
+    reply_language: Language,
+    explanation_language: Language,
+    intent: ReplyIntent,
+    endpoint: &str,
+    model: &str,
+) -> Result<ReplyResult, TranslationError> {
+    // モデルサイズ検証
+    validate_model_for_advanced_features(model)?;
 
-    let chat_response: OllamaChatResponse = response
-        .json()
-        .await
-        .map_err(|e| TranslationError::ApiError(format!("レスポンスのパースに失敗: {}", e)))?;
+    let start = Instant::now();
+
+    // デバッグログ
+    eprintln!("[返信生成] デバッグ情報:");
+    eprintln!("  text length: {}", text.len());
+    eprintln!("  reply_language: {:?}", reply_language);
+    eprintln!("  explanation_language: {:?}", explanation_language);
+    eprintln!("  intent: {:?}", intent);
+
+    let bilingual = reply_language != explanation_language;
+
+    // プロンプト構築（返信言語と説明言語を指定）
+    let prompt = if bilingual {
+        build_bilingual_reply_prompt(text, reply_language, explanation_language, intent)
+    } else {
+        build_reply_prompt(text, reply_language, explanation_language, intent)
+    };
+    let preview = prompt.chars().take(200).collect::<String>();
+    eprintln!("  prompt preview: {}", preview);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
+
+    let request_body = if bilingual {
+        build_bilingual_reply_request(&prompt, reply_language, model, false)
+    } else {
+        build_reply_request(&prompt, reply_language, model, false)
+    };
+    let content = send_chat_request(&url, &request_body).await?;
+
+    // デバッグログ: レスポンス
+    eprintln!("[返信生成] レスポンス:");
+    eprintln!("  content: {}", &content);
+
+    let (reply, explanation) = if bilingual {
+        resolve_bilingual_reply(
+            &content,
+            text,
+            reply_language,
+            explanation_language,
+            endpoint,
+            model,
+        )
+        .await?
+    } else {
+        // クリーニング（簡易版）
+        let reply = clean_translation_result(&content, text);
+        (reply.clone(), reply)
+    };
+
+    eprintln!("[返信生成] クリーニング後:");
+    eprintln!("  reply: {}", &reply);
+    eprintln!("  explanation: {}", &explanation);
+
+    Ok(ReplyResult {
+        reply,
+        explanation,
+        language: reply_language,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Ollamaでストリーミング返信生成を実行
+///
+/// 返信を`reply-chunk`イベントで逐次配信し、完了時に`reply-complete`イベントで結果を通知する。
+/// 2つの言語が異なる場合は非ストリーミングと同様に構造化出力で返信と翻訳を生成し、
+/// 返信のフィールドのみを逐次配信する
+pub async fn generate_reply_with_ollama_stream<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    text: &str,
+    reply_language: Language,
+    explanation_language: Language,
+    intent: ReplyIntent,
+    endpoint: &str,
+    model: &str,
+) -> Result<(), TranslationError> {
+    // モデルサイズ検証
+    validate_model_for_advanced_features(model)?;
 
-    let duration_ms = start.elapsed().as_millis() as u64;
+    let start = Instant::now();
 
-    // デバッグログ: レスポンス
-    eprintln!("[返信生成] レスポンス:");
-    eprintln!("  content: {}", &chat_response.message.content);
+    let url = format!("{}/api/chat", endpoint.trim_end_matches('/'));
 
-    // クリーニング（簡易版）
-    let reply = clean_translation_result(&chat_response.message.content, text);
+    let (reply, explanation) = if reply_language != explanation_language {
+        let prompt =
+            build_bilingual_reply_prompt(text, reply_language, explanation_language, intent);
+        let request_body = build_bilingual_reply_request(&prompt, reply_language, model, true);
+        let accumulated =
+            stream_chat_request_with(app, &url, &request_body, "reply-chunk", |content| {
+                partial_json_string_field(content, "reply")
+            })
+            .await?;
+        resolve_bilingual_reply(
+            &accumulated,
+            text,
+            reply_language,
+            explanation_language,
+            endpoint,
+            model,
+        )
+        .await?
+    } else {
+        let prompt = build_reply_prompt(text, reply_language, explanation_language, intent);
+        let request_body = build_reply_request(&prompt, reply_language, model, true);
+        let accumulated = stream_chat_request(app, &url, &request_body, "reply-chunk").await?;
+        let reply = clean_translation_result(&accumulated, text);
+        (reply.clone(), reply)
+    };
 
-    eprintln!("[返信生成] クリーニング後:");
-    eprintln!("  reply: {}", &reply);
+    // 完了イベント発行
+    let _ = app.emit(
+        "reply-complete",
+        ReplyResult {
+            reply,
+            explanation,
+            language: reply_language,
+            duration_ms: start.elapsed().as_millis() as u64,
+        },
+    );
 
-    Ok(ReplyResult {
-        reply: reply.clone(),
-        explanation: reply, // 2段階処理では翻訳はフロントエンドで実施するため、同じ内容を格納
-        language,
-        duration_ms,
-    })
+    Ok(())
 }
 
 #[cfg(test)]
@@ -948,6 +2121,37 @@ mod tests {
         assert_eq!(Language::English.name(), "English");
     }
 
+    #[test]
+    fn test_language_code() {
+        assert_eq!(Language::Japanese.code(), "ja");
+        assert_eq!(Language::English.code(), "en");
+    }
+
+    #[test]
+    fn test_language_opposite() {
+        assert_eq!(Language::Japanese.opposite(), Language::English);
+        assert_eq!(Language::English.opposite(), Language::Japanese);
+    }
+
+    #[test]
+    fn test_language_detect() {
+        assert_eq!(Language::detect("こんにちは"), Language::Japanese);
+        assert_eq!(Language::detect("Hello, World!"), Language::English);
+        // 短いテキストは日本語文字が1文字でもあれば日本語
+        assert_eq!(Language::detect("OK です"), Language::Japanese);
+        // 長いテキストは日本語文字の割合で判定
+        assert_eq!(
+            Language::detect("This is a long English sentence with a 単語 inside"),
+            Language::English
+        );
+        assert_eq!(
+            Language::detect("Rustの所有権について説明します"),
+            Language::Japanese
+        );
+        // 空テキストはデフォルトで日本語
+        assert_eq!(Language::detect("   "), Language::Japanese);
+    }
+
     #[test]
     fn test_detect_model_type_plamo() {
         assert_eq!(
@@ -1020,7 +2224,12 @@ mod tests {
 
     #[test]
     fn test_build_general_prompt_ja_to_en() {
-        let prompt = build_general_prompt("こんにちは", Language::Japanese, Language::English);
+        let prompt = build_general_prompt(
+            "こんにちは",
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions::default(),
+        );
         assert!(prompt.contains("こんにちは"));
         assert!(prompt.contains("Translate"));
         assert!(prompt.contains("English"));
@@ -1028,15 +2237,25 @@ mod tests {
 
     #[test]
     fn test_build_general_prompt_en_to_ja() {
-        let prompt = build_general_prompt("Hello", Language::English, Language::Japanese);
+        let prompt = build_general_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
         assert!(prompt.contains("Hello"));
         assert!(prompt.contains("翻訳"));
     }
 
     #[test]
     fn test_build_translation_prompt_qwen() {
-        let prompt =
-            build_translation_prompt("Hello", Language::English, Language::Japanese, "qwen2.5:3b");
+        let prompt = build_translation_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            "qwen2.5:3b",
+            &TranslationOptions::default(),
+        );
         assert!(prompt.contains("Hello"));
         assert!(prompt.contains("翻訳"));
     }
@@ -1048,11 +2267,179 @@ mod tests {
             Language::Japanese,
             Language::English,
             "mitmul/plamo-2-translate:Q4_K_M",
+            &TranslationOptions::default(),
         );
         assert!(prompt.contains("こんにちは"));
         assert!(prompt.contains("English"));
     }
 
+    #[test]
+    fn test_build_general_prompt_with_formality() {
+        let prompt = build_general_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions {
+                formality: Formality::Plain,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            prompt,
+            "「である調」（常体）で翻訳してください。\n以下の英文を日本語に翻訳してください:\nHello"
+        );
+
+        let prompt = build_general_prompt(
+            "こんにちは",
+            Language::Japanese,
+            Language::English,
+            &TranslationOptions {
+                formality: Formality::Casual,
+                ..Default::default()
+            },
+        );
+        assert!(prompt.starts_with("Use a casual, conversational tone.\n"));
+    }
+
+    #[test]
+    fn test_build_translation_prompt_plamo_ignores_formality() {
+        let prompt = build_translation_prompt(
+            "こんにちは",
+            Language::Japanese,
+            Language::English,
+            "mitmul/plamo-2-translate:Q4_K_M",
+            &TranslationOptions {
+                formality: Formality::Polite,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            prompt,
+            "Translate the following Japanese text to English:\nこんにちは"
+        );
+    }
+
+    #[test]
+    fn test_build_general_prompt_with_domain() {
+        let options = TranslationOptions {
+            formality: Formality::Polite,
+            domain: Domain::Legal,
+            ..Default::default()
+        };
+        let prompt = build_general_prompt(
+            "The Party shall not disclose Confidential Information.",
+            Language::English,
+            Language::Japanese,
+            &options,
+        );
+        let lines: Vec<&str> = prompt.lines().collect();
+        assert!(lines[0].contains("契約文書"));
+        assert_eq!(
+            lines[1],
+            "用語集: party → 当事者, confidential information → 秘密情報"
+        );
+        assert_eq!(lines[2], "「です・ます調」の丁寧語で翻訳してください。");
+        assert_eq!(lines[3], "以下の英文を日本語に翻訳してください:");
+
+        // 技術文書（既定）は従来のプロンプトのまま
+        let prompt = build_general_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            &TranslationOptions::default(),
+        );
+        assert_eq!(prompt, "以下の英文を日本語に翻訳してください:\nHello");
+    }
+
+    #[test]
+    fn test_build_general_prompt_with_context() {
+        let options = TranslationOptions {
+            context: Some(TranslationContext {
+                before: "The server restarts nightly.".to_string(),
+                after: "Contact the admin if it fails.".to_string(),
+            }),
+            ..Default::default()
+        };
+        let prompt = build_general_prompt(
+            "It takes about five minutes.",
+            Language::English,
+            Language::Japanese,
+            &options,
+        );
+        let lines: Vec<&str> = prompt.lines().collect();
+        assert!(lines[0].starts_with("前後の文脈は参考情報です"));
+        assert_eq!(lines[1], "以下の英文を日本語に翻訳してください:");
+        assert_eq!(lines[2], "前の文脈: The server restarts nightly.");
+        assert_eq!(lines[3], "後の文脈: Contact the admin if it fails.");
+        assert_eq!(
+            lines[4],
+            "<translate>It takes about five minutes.</translate>"
+        );
+
+        // 空の文脈は無視される
+        let options = TranslationOptions {
+            context: Some(TranslationContext::default()),
+            ..Default::default()
+        };
+        let prompt = build_general_prompt("Hello", Language::English, Language::Japanese, &options);
+        assert_eq!(prompt, "以下の英文を日本語に翻訳してください:\nHello");
+
+        // 翻訳特化モデルには文脈を渡さない
+        let options = TranslationOptions {
+            context: Some(TranslationContext {
+                before: "前の文".to_string(),
+                after: String::new(),
+            }),
+            ..Default::default()
+        };
+        let prompt = build_translation_prompt(
+            "こんにちは",
+            Language::Japanese,
+            Language::English,
+            "plamo-2-translate",
+            &options,
+        );
+        assert!(!prompt.contains("前の文"));
+    }
+
+    #[test]
+    fn test_extract_selection_translation() {
+        assert_eq!(
+            extract_selection_translation("<translate>約5分かかります。</translate>"),
+            "約5分かかります。"
+        );
+        assert_eq!(
+            extract_selection_translation(
+                "前の文脈: サーバーは毎晩再起動します。\n約5分かかります。"
+            ),
+            "約5分かかります。"
+        );
+        assert_eq!(
+            extract_selection_translation("It takes about five minutes."),
+            "It takes about five minutes."
+        );
+    }
+
+    #[test]
+    fn test_build_translation_api_options() {
+        let options = build_translation_api_options(ModelType::GeneralPurpose, Domain::Technical);
+        assert_eq!(options, build_api_options(ModelType::GeneralPurpose));
+
+        let options = build_translation_api_options(ModelType::PlamoTranslate, Domain::Marketing);
+        assert!((options["temperature"].as_f64().unwrap() - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_formality_serialization() {
+        assert_eq!(Formality::default(), Formality::Auto);
+        assert_eq!(
+            serde_json::to_string(&Formality::Polite).unwrap(),
+            "\"polite\""
+        );
+        let formality: Formality = serde_json::from_str("\"casual\"").unwrap();
+        assert_eq!(formality, Formality::Casual);
+    }
+
     #[test]
     fn test_clean_translation_result_prefix_removal() {
         assert_eq!(
@@ -1099,6 +2486,88 @@ mod tests {
         assert_eq!(options["num_predict"], 4096);
     }
 
+    #[test]
+    fn test_build_alternative_prompt() {
+        let standard = build_alternative_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            "qwen2.5:3b",
+            TranslationStyle::Standard,
+            &TranslationOptions::default(),
+        );
+        assert_eq!(standard, "以下の英文を日本語に翻訳してください:\nHello");
+
+        let formal = build_alternative_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            "qwen2.5:3b",
+            TranslationStyle::Formal,
+            &TranslationOptions {
+                formality: Formality::Plain,
+                ..Default::default()
+            },
+        );
+        assert!(formal.starts_with("「です・ます調」の丁寧語"));
+        assert!(!formal.contains("である調"));
+        assert!(formal.ends_with("Hello"));
+
+        // 翻訳特化モデルにはスタイル指示を付加しない
+        let plamo = build_alternative_prompt(
+            "こんにちは",
+            Language::Japanese,
+            Language::English,
+            "plamo-2-translate",
+            TranslationStyle::Casual,
+            &TranslationOptions::default(),
+        );
+        assert_eq!(
+            plamo,
+            "Translate the following Japanese text to English:\nこんにちは"
+        );
+    }
+
+    #[test]
+    fn test_build_alternative_options() {
+        let first = build_alternative_options(ModelType::GeneralPurpose, Domain::Technical, 0);
+        assert_eq!(first["temperature"], 0.2);
+        assert_eq!(first["seed"], 0);
+        assert_eq!(first["num_predict"], 4096);
+
+        let third = build_alternative_options(ModelType::GeneralPurpose, Domain::Technical, 2);
+        assert!((third["temperature"].as_f64().unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(third["seed"], 2);
+    }
+
+    #[test]
+    fn test_rank_candidates() {
+        let candidate = |style, text: &str, warnings: usize| TranslationCandidate {
+            style,
+            translated_text: text.to_string(),
+            warnings: vec![
+                QaWarning {
+                    kind: crate::services::quality::QaWarningKind::Number,
+                    message: String::new(),
+                };
+                warnings
+            ],
+        };
+
+        let ranked = rank_candidates(vec![
+            candidate(TranslationStyle::Standard, "こんにちは", 1),
+            candidate(TranslationStyle::Formal, "こんにちは。", 0),
+            candidate(TranslationStyle::Casual, "こんにちは", 0),
+            candidate(TranslationStyle::Concise, "", 0),
+        ]);
+
+        let styles: Vec<_> = ranked.iter().map(|c| c.style).collect();
+        assert_eq!(
+            styles,
+            vec![TranslationStyle::Formal, TranslationStyle::Standard]
+        );
+    }
+
     #[test]
     fn test_translation_error_display() {
         let err = TranslationError::Timeout;
@@ -1122,6 +2591,10 @@ mod tests {
             source_lang: Language::English,
             target_lang: Language::Japanese,
             duration_ms: 500,
+            warnings: Vec::new(),
+            output_path: OutputPath::Structured,
+            reading: None,
+            prompt: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -1129,6 +2602,65 @@ mod tests {
         assert!(json.contains("\"sourceLang\""));
         assert!(json.contains("\"targetLang\""));
         assert!(json.contains("\"durationMs\""));
+        assert!(json.contains("\"outputPath\":\"structured\""));
+        assert!(!json.contains("\"prompt\""));
+    }
+
+    #[test]
+    fn test_build_translation_request_format() {
+        let options = build_api_options(ModelType::GeneralPurpose);
+        let body =
+            build_translation_request("prompt", "qwen2.5:3b", ModelType::GeneralPurpose, options);
+        assert_eq!(
+            body["format"]["required"],
+            serde_json::json!(["translation"])
+        );
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "prompt");
+
+        // 翻訳特化モデルには構造化出力を指定しない
+        let options = build_api_options(ModelType::PlamoTranslate);
+        let body = build_translation_request(
+            "prompt",
+            "plamo-2-translate",
+            ModelType::PlamoTranslate,
+            options,
+        );
+        assert!(body.get("format").is_none());
+        assert_eq!(body["messages"][0]["role"], "user");
+    }
+
+    #[test]
+    fn test_extract_translation_paths() {
+        let options = TranslationOptions::default();
+
+        let (translated, path) = extract_translation(
+            r#"{"translation": "こんにちは世界"}"#,
+            "Hello world",
+            ModelType::GeneralPurpose,
+            &options,
+        );
+        assert_eq!(translated, "こんにちは世界");
+        assert_eq!(path, OutputPath::Structured);
+
+        // 構造化出力に従わなかった場合はヒューリスティックな整形
+        let (translated, path) = extract_translation(
+            "翻訳: こんにちは世界",
+            "Hello world",
+            ModelType::GeneralPurpose,
+            &options,
+        );
+        assert_eq!(translated, "こんにちは世界");
+        assert_eq!(path, OutputPath::Fallback);
+
+        let (translated, path) = extract_translation(
+            "\"Hello world\"",
+            "こんにちは世界",
+            ModelType::PlamoTranslate,
+            &options,
+        );
+        assert_eq!(translated, "Hello world");
+        assert_eq!(path, OutputPath::Heuristic);
     }
 
     #[test]
@@ -1167,7 +2699,11 @@ mod tests {
 
     #[test]
     fn test_build_summarize_prompt_japanese() {
-        let prompt = build_summarize_prompt("テストテキスト", Language::Japanese);
+        let prompt = build_summarize_prompt(
+            "テストテキスト",
+            Language::Japanese,
+            SummaryLength::default(),
+        );
         assert!(prompt.contains("要約"));
         assert!(prompt.contains("テストテキスト"));
         assert!(prompt.contains("3文"));
@@ -1175,7 +2711,8 @@ mod tests {
 
     #[test]
     fn test_build_summarize_prompt_english() {
-        let prompt = build_summarize_prompt("Test text", Language::English);
+        let prompt =
+            build_summarize_prompt("Test text", Language::English, SummaryLength::default());
         assert!(prompt.contains("Summarize"));
         assert!(prompt.contains("Test text"));
         assert!(prompt.contains("3 sentences"));
@@ -1183,7 +2720,12 @@ mod tests {
 
     #[test]
     fn test_build_reply_prompt_japanese() {
-        let prompt = build_reply_prompt("こんにちは", Language::Japanese, Language::English);
+        let prompt = build_reply_prompt(
+            "こんにちは",
+            Language::Japanese,
+            Language::English,
+            ReplyIntent::default(),
+        );
         assert!(prompt.contains("返信"));
         assert!(prompt.contains("こんにちは"));
         assert!(prompt.contains("丁寧"));
@@ -1191,12 +2733,138 @@ mod tests {
 
     #[test]
     fn test_build_reply_prompt_english() {
-        let prompt = build_reply_prompt("Hello", Language::English, Language::Japanese);
+        let prompt = build_reply_prompt(
+            "Hello",
+            Language::English,
+            Language::Japanese,
+            ReplyIntent::default(),
+        );
         assert!(prompt.contains("reply"));
         assert!(prompt.contains("Hello"));
         assert!(prompt.contains("polite"));
     }
 
+    #[test]
+    fn test_build_summarize_prompt_with_length() {
+        let prompt = build_summarize_prompt("Test text", Language::English, SummaryLength::Bullets);
+        assert!(prompt.contains("bullet points"));
+        assert!(!prompt.contains("3 sentences"));
+
+        let prompt = build_summarize_prompt(
+            "テストテキスト",
+            Language::Japanese,
+            SummaryLength::TldrDetails,
+        );
+        assert!(prompt.contains("TL;DR:"));
+
+        let prompt =
+            build_summarize_prompt("テストテキスト", Language::Japanese, SummaryLength::OneLine);
+        assert!(prompt.contains("1文で日本語で要約"));
+    }
+
+    #[test]
+    fn test_build_reply_prompt_with_intent() {
+        let prompt = build_reply_prompt(
+            "Can you join the meeting?",
+            Language::English,
+            Language::Japanese,
+            ReplyIntent::Decline,
+        );
+        assert!(prompt.contains("in English, politely declining the request or proposal."));
+
+        let prompt = build_reply_prompt(
+            "会議に参加できますか？",
+            Language::Japanese,
+            Language::English,
+            ReplyIntent::Clarify,
+        );
+        assert!(prompt.contains("不明点を確認する質問をする丁寧なビジネスメール"));
+    }
+
+    #[test]
+    fn test_build_bilingual_reply_prompt() {
+        let prompt = build_bilingual_reply_prompt(
+            "Can you join the meeting?",
+            Language::English,
+            Language::Japanese,
+            ReplyIntent::Accept,
+        );
+        assert!(prompt.contains("in English, accepting the request or proposal."));
+        assert!(prompt.contains("its Japanese translation in \"translation\""));
+
+        let prompt = build_bilingual_reply_prompt(
+            "会議に参加できますか？",
+            Language::Japanese,
+            Language::English,
+            ReplyIntent::General,
+        );
+        assert!(prompt.contains("\"translation\"にその返信の英語訳"));
+    }
+
+    #[test]
+    fn test_build_bilingual_reply_request_has_schema() {
+        let body = build_bilingual_reply_request("prompt", Language::English, "qwen2.5:7b", false);
+        assert_eq!(body["stream"], false);
+        let body = build_bilingual_reply_request("prompt", Language::English, "qwen2.5:7b", true);
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["format"]["type"], "object");
+        assert_eq!(
+            body["format"]["required"],
+            serde_json::json!(["reply", "translation"])
+        );
+    }
+
+    #[test]
+    fn test_parse_structured_reply() {
+        assert_eq!(
+            parse_structured_reply(
+                r#"{"reply": "Thank you.", "translation": "ありがとうございます。"}"#
+            ),
+            Some((
+                "Thank you.".to_string(),
+                Some("ありがとうございます。".to_string())
+            ))
+        );
+
+        // コードブロックで囲まれている
+        let content = "```json\n{\"reply\": \"Thank you.\", \"translation\": \"\"}\n```";
+        assert_eq!(
+            parse_structured_reply(content),
+            Some(("Thank you.".to_string(), None))
+        );
+
+        // JSONでない・返信が空
+        assert_eq!(parse_structured_reply("Thank you."), None);
+        assert_eq!(parse_structured_reply(r#"{"reply": " "}"#), None);
+    }
+
+    #[test]
+    fn test_partial_json_string_field() {
+        assert_eq!(partial_json_string_field("", "reply"), "");
+        assert_eq!(partial_json_string_field(r#"{"rep"#, "reply"), "");
+        assert_eq!(
+            partial_json_string_field(r#"{"reply": "Dear team,\nThank"#, "reply"),
+            "Dear team,\nThank"
+        );
+        assert_eq!(
+            partial_json_string_field(
+                r#"{"reply": "Say \"hi\" \u3042", "translation": "x"}"#,
+                "reply"
+            ),
+            "Say \"hi\" あ"
+        );
+    }
+
+    #[test]
+    fn test_summary_length_and_reply_intent_serialization() {
+        assert_eq!(
+            serde_json::to_string(&SummaryLength::TldrDetails).unwrap(),
+            "\"tldr-details\""
+        );
+        let intent: ReplyIntent = serde_json::from_str("\"follow-up\"").unwrap();
+        assert_eq!(intent, ReplyIntent::FollowUp);
+    }
+
     #[test]
     fn test_summarize_result_serialization() {
         let result = SummarizeResult {
@@ -1229,71 +2897,4 @@ mod tests {
         assert!(json.contains("\"durationMs\""));
         assert!(json.contains("\"japanese\""));
     }
-
-    #[test]
-    fn test_parse_reply_response_with_markers() {
-        let response = r#"[返信]
-こちらは日本語の返信です。
-
-[翻訳]
-This is the English translation."#;
-
-        let (reply, translation) = parse_reply_response(response);
-        assert_eq!(reply, "こちらは日本語の返信です。");
-        assert_eq!(translation, "This is the English translation.");
-    }
-
-    #[test]
-    fn test_parse_reply_response_english_markers() {
-        let response = r#"[Reply]
-This is an English reply.
-
-[Translation]
-これは日本語の翻訳です。"#;
-
-        let (reply, translation) = parse_reply_response(response);
-        assert_eq!(reply, "This is an English reply.");
-        assert_eq!(translation, "これは日本語の翻訳です。");
-    }
-
-    #[test]
-    fn test_parse_reply_response_no_markers() {
-        let response = "This is a reply without markers.";
-
-        let (reply, translation) = parse_reply_response(response);
-        assert_eq!(reply, "This is a reply without markers.");
-        assert_eq!(translation, "");
-    }
-
-    #[test]
-    fn test_parse_reply_response_new_format_japanese() {
-        let response = r#"REPLY: ご連絡ありがとうございます。承知いたしました。
-TRANSLATION: Thank you for your message. I understand."#;
-
-        let (reply, translation) = parse_reply_response(response);
-        assert_eq!(reply, "ご連絡ありがとうございます。承知いたしました。");
-        assert_eq!(translation, "Thank you for your message. I understand.");
-    }
-
-    #[test]
-    fn test_parse_reply_response_new_format_english() {
-        let response = r#"REPLY: Thank you for your message. I understand.
-TRANSLATION: ご連絡ありがとうございます。承知いたしました。"#;
-
-        let (reply, translation) = parse_reply_response(response);
-        assert_eq!(reply, "Thank you for your message. I understand.");
-        assert_eq!(
-            translation,
-            "ご連絡ありがとうございます。承知いたしました。"
-        );
-    }
-
-    #[test]
-    fn test_parse_reply_response_fallback_two_lines() {
-        let response = "First line as reply\nSecond line as translation";
-
-        let (reply, translation) = parse_reply_response(response);
-        assert_eq!(reply, "First line as reply");
-        assert_eq!(translation, "Second line as translation");
-    }
 }
@@ -0,0 +1,111 @@
+//! ウィンドウ管理サービス
+//!
+//! ポップアップ表示位置の計算に使用するカーソル座標の変換を提供
+
+/// モニターの表示領域（物理ピクセル）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    /// 左上のX座標
+    pub x: f64,
+    /// 左上のY座標
+    pub y: f64,
+    /// 幅
+    pub width: f64,
+    /// 高さ
+    pub height: f64,
+    /// スケールファクター
+    pub scale_factor: f64,
+}
+
+impl MonitorArea {
+    /// 物理座標がこのモニター内にあるか
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// 物理ピクセル・左上原点のカーソル座標を、macOSと同じ論理座標・左下原点に変換する
+///
+/// フロントエンドはmacOSの`NSEvent.mouseLocation`と同じ座標系（全モニターの下端を
+/// 基準にした左下原点の論理座標）を前提にしているため、他プラットフォームでも
+/// 同じ座標系で返す
+///
+/// # Arguments
+/// * `cursor` - カーソル位置（物理ピクセル、左上原点）
+/// * `monitors` - 全モニターの表示領域
+///
+/// # Returns
+/// 変換後の座標（論理座標、左下原点）
+pub fn to_bottom_left_logical(cursor: (f64, f64), monitors: &[MonitorArea]) -> (f64, f64) {
+    let (x, y) = cursor;
+
+    // カーソルがあるモニターのスケールファクターで論理座標に変換
+    let scale_factor = monitors
+        .iter()
+        .find(|m| m.contains(x, y))
+        .or_else(|| monitors.first())
+        .map(|m| m.scale_factor)
+        .unwrap_or(1.0);
+
+    let logical_x = x / scale_factor;
+    let logical_y = y / scale_factor;
+
+    // 全モニターの下端（論理座標）を基準にY座標を反転
+    let max_bottom = monitors
+        .iter()
+        .map(|m| (m.y + m.height) / m.scale_factor)
+        .fold(logical_y, f64::max);
+
+    (logical_x, max_bottom - logical_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(x: f64, y: f64, width: f64, height: f64, scale_factor: f64) -> MonitorArea {
+        MonitorArea {
+            x,
+            y,
+            width,
+            height,
+            scale_factor,
+        }
+    }
+
+    #[test]
+    fn test_to_bottom_left_logical_single_monitor() {
+        let monitors = [monitor(0.0, 0.0, 1920.0, 1080.0, 1.0)];
+        assert_eq!(
+            to_bottom_left_logical((100.0, 80.0), &monitors),
+            (100.0, 1000.0)
+        );
+    }
+
+    #[test]
+    fn test_to_bottom_left_logical_hidpi() {
+        let monitors = [monitor(0.0, 0.0, 2880.0, 1800.0, 2.0)];
+        assert_eq!(
+            to_bottom_left_logical((200.0, 100.0), &monitors),
+            (100.0, 850.0)
+        );
+    }
+
+    #[test]
+    fn test_to_bottom_left_logical_multiple_monitors() {
+        // 右側に縦長のモニターがある場合は、最も下の端を基準にする
+        let monitors = [
+            monitor(0.0, 0.0, 1920.0, 1080.0, 1.0),
+            monitor(1920.0, 0.0, 1080.0, 1920.0, 1.0),
+        ];
+        assert_eq!(
+            to_bottom_left_logical((100.0, 100.0), &monitors),
+            (100.0, 1820.0)
+        );
+    }
+
+    #[test]
+    fn test_to_bottom_left_logical_no_monitors() {
+        assert_eq!(to_bottom_left_logical((10.0, 20.0), &[]), (10.0, 0.0));
+    }
+}
@@ -4,7 +4,7 @@
  * メインアプリケーションコンポーネント
  */
 
-import { useState, useEffect, useCallback } from 'react';
+import { useState, useEffect, useCallback, useMemo } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { TranslationPopup } from '@/components/TranslationPopup';
 import { SettingsPanel } from '@/components/SettingsPanel';
@@ -13,6 +13,7 @@ import { useShortcut } from '@/hooks/useShortcut';
 import { usePermissions } from '@/hooks/usePermissions';
 import { useSettingsContext } from '@/contexts/SettingsContext';
 import { useClipboard } from '@/hooks/useClipboard';
+import { effectiveKeymap, type ShortcutBinding } from '@/types';
 
 function App() {
   const [isSettingsOpen, setIsSettingsOpen] = useState(false);
@@ -20,7 +21,7 @@ function App() {
   // Hooks
   const { settings, isLoading: settingsLoading } = useSettingsContext();
   const { isAccessibilityGranted, checkAccessibility } = usePermissions();
-  const { registerShortcut, isRegistered } = useShortcut();
+  const { registerKeymap, isRegistered } = useShortcut();
   const { writeText } = useClipboard();
 
   // 翻訳フロー
@@ -38,6 +39,8 @@ function App() {
     actionError,
     summarize,
     generateReply,
+    dictionaryMatches,
+    translationReading,
   } = useTranslationFlow({
     onTranslationComplete: () => {
       // 翻訳完了時の処理（必要に応じてサウンド再生など）
@@ -47,14 +50,19 @@ function App() {
     },
   });
 
-  // 初期化: ショートカット登録
+  // 保存されたキーマップのショートカットを登録（キーマップの変更時は登録し直す）
+  const keymapKey = useMemo(
+    () => (settingsLoading ? null : JSON.stringify(effectiveKeymap(settings))),
+    [settings, settingsLoading]
+  );
   useEffect(() => {
-    if (!isRegistered && !settingsLoading) {
-      void registerShortcut(settings.shortcut).catch((err: unknown) => {
-        console.error('Failed to register shortcut:', err);
-      });
-    }
-  }, [settings, isRegistered, settingsLoading, registerShortcut]);
+    if (keymapKey === null) return;
+
+    const keymap = JSON.parse(keymapKey) as ShortcutBinding[];
+    void registerKeymap(keymap).catch((err: unknown) => {
+      console.error('Failed to register keymap:', err);
+    });
+  }, [keymapKey, registerKeymap]);
 
   // 初期化: アクセシビリティ権限確認
   useEffect(() => {
@@ -237,6 +245,8 @@ function App() {
         onGenerateReply={() => {
           void generateReply();
         }}
+        dictionaryMatches={dictionaryMatches}
+        translationReading={translationReading}
       />
 
       {/* 設定パネル */}
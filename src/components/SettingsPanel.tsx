@@ -5,13 +5,16 @@
  * - ショートカットキーのカスタマイズ
  * - Ollamaモデル・エンドポイント設定
  * - 接続テスト
+ * - オフライン辞書の取り込み
  */
 
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useSettingsContext } from '@/contexts/SettingsContext';
 import { usePermissions } from '@/hooks/usePermissions';
 import { useShortcut } from '@/hooks/useShortcut';
+import { effectiveKeymap, replaceKeymapShortcut } from '@/types';
+import type { AppSettings, DictionaryStatus } from '@/types';
 
 /**
  * SettingsPanelのProps
@@ -214,8 +217,7 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
 
   const {
     isRegistered: shortcutRegistered,
-    registerShortcut,
-    unregisterShortcut,
+    registerKeymap,
     validateShortcut,
   } = useShortcut();
 
@@ -236,6 +238,21 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
   >(null);
   const [statusMessage, setStatusMessage] = useState('');
   const [claudeCliWarning, setClaudeCliWarning] = useState('');
+  const [dictionaryPathInput, setDictionaryPathInput] = useState('');
+  const [dictionaryStatus, setDictionaryStatus] =
+    useState<DictionaryStatus | null>(null);
+  const [isImportingDictionary, setIsImportingDictionary] = useState(false);
+
+  // 辞書の状態を取得
+  useEffect(() => {
+    if (!isOpen) return;
+
+    invoke<DictionaryStatus>('get_dictionary_status')
+      .then(setDictionaryStatus)
+      .catch((err: unknown) => {
+        console.error('Failed to get dictionary status:', err);
+      });
+  }, [isOpen]);
 
   // ショートカット変更
   const handleShortcutChange = useCallback(async () => {
@@ -247,17 +264,22 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
       return;
     }
 
-    try {
-      // 既存のショートカットを解除
-      if (settings.shortcut && shortcutRegistered) {
-        await unregisterShortcut(settings.shortcut);
-      }
+    // 保存済みのキーマップがある場合は、その中の割り当ても差し替えて設定と一致させる
+    const changes: Partial<AppSettings> = { shortcut: shortcutInput };
+    if (settings.keymap && settings.keymap.length > 0) {
+      changes.keymap = replaceKeymapShortcut(
+        settings.keymap,
+        settings.shortcut,
+        shortcutInput
+      );
+    }
 
-      // 新しいショートカットを登録
-      await registerShortcut(shortcutInput);
+    try {
+      // キーマップ全体を登録し直す（古い割り当てはバックエンドで解除される）
+      await registerKeymap(effectiveKeymap({ ...settings, ...changes }));
 
       // 設定を保存
-      await updateSettings({ shortcut: shortcutInput });
+      await updateSettings(changes);
       setStatusMessage('ショートカットを更新しました');
     } catch {
       setStatusMessage('ショートカットの登録に失敗しました');
@@ -265,10 +287,8 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
   }, [
     shortcutInput,
     settings,
-    shortcutRegistered,
     validateShortcut,
-    unregisterShortcut,
-    registerShortcut,
+    registerKeymap,
     updateSettings,
   ]);
 
@@ -330,6 +350,25 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
     }
   }, [checkProviderStatus]);
 
+  // 辞書ファイルの取り込み
+  const handleImportDictionary = useCallback(async () => {
+    if (!dictionaryPathInput) return;
+
+    setIsImportingDictionary(true);
+    setStatusMessage('辞書を取り込み中...');
+    try {
+      const status = await invoke<DictionaryStatus>('import_dictionary', {
+        path: dictionaryPathInput,
+      });
+      setDictionaryStatus(status);
+      setStatusMessage(`辞書を取り込みました（${status.entryCount}語）`);
+    } catch (err) {
+      setStatusMessage(`辞書の取り込みに失敗しました: ${String(err)}`);
+    } finally {
+      setIsImportingDictionary(false);
+    }
+  }, [dictionaryPathInput]);
+
   // デバッグ情報を取得
   const handleGetDebugInfo = useCallback(async () => {
     try {
@@ -652,6 +691,60 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
               </div>
             )}
           </section>
+
+          {/* オフライン辞書 */}
+          <section>
+            <h3 className="text-sm font-semibold text-gray-800 dark:text-gray-200 mb-3">
+              オフライン辞書
+            </h3>
+            <div className="space-y-3">
+              <InputField
+                label="辞書ファイルのパス"
+                value={dictionaryPathInput}
+                onChange={setDictionaryPathInput}
+                placeholder="/path/to/JMdict_e.xml"
+              />
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                JMdict（XML）またはEDICT形式のUTF-8ファイルを指定してください。1語だけを選択したときに、翻訳と並行して辞書の語義を表示します。
+              </p>
+              <button
+                onClick={() => {
+                  void handleImportDictionary();
+                }}
+                disabled={isImportingDictionary || !dictionaryPathInput}
+                className="w-full px-4 py-2 bg-gray-800 dark:bg-gray-600 hover:bg-gray-700 dark:hover:bg-gray-500 disabled:bg-gray-400 text-white text-sm rounded-lg transition-colors"
+              >
+                {isImportingDictionary ? '取り込み中...' : '辞書を取り込む'}
+              </button>
+              {dictionaryStatus && (
+                <p className="text-xs text-gray-500 dark:text-gray-400">
+                  {dictionaryStatus.loaded
+                    ? `取り込み済み: ${dictionaryStatus.entryCount}語`
+                    : '辞書は取り込まれていません'}
+                </p>
+              )}
+            </div>
+          </section>
+
+          {/* ふりがな */}
+          <section>
+            <h3 className="text-sm font-semibold text-gray-800 dark:text-gray-200 mb-3">
+              ふりがな
+            </h3>
+            <label className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+              <input
+                type="checkbox"
+                checked={settings.furigana ?? false}
+                onChange={(e) => {
+                  void updateSettings({ furigana: e.target.checked });
+                }}
+              />
+              日本語の訳文にふりがな・ローマ字を表示する
+            </label>
+            <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+              取り込み済みの辞書で読みを付け、辞書にない漢字はLLMで生成します。
+            </p>
+          </section>
         </div>
       </div>
     </div>
@@ -3,6 +3,8 @@
  *
  * 翻訳結果を表示するポップアップUI
  * - ローディング状態、翻訳結果、エラー状態の表示切り替え
+ * - 1語だけの選択ではオフライン辞書の語義を翻訳と並行して表示
+ * - 訳文の読みがある場合はルビとローマ字を表示
  * - タイトルバーなしの角丸デザイン
  */
 
@@ -11,6 +13,11 @@ import type {
   TranslationFlowState,
   TranslationFlowError,
 } from '@/hooks/useTranslationFlow';
+import type {
+  DeinflectionReason,
+  DictionaryMatch,
+  ReadingAnnotation,
+} from '@/types';
 
 /**
  * TranslationPopupのProps
@@ -44,6 +51,112 @@ export interface TranslationPopupProps {
   onSummarize?: () => void | Promise<void>;
   /** 返信を生成する関数 */
   onGenerateReply?: () => void | Promise<void>;
+  /** オフライン辞書の検索結果 */
+  dictionaryMatches?: DictionaryMatch[];
+  /** 訳文の読み（ふりがな・ローマ字） */
+  translationReading?: ReadingAnnotation | null;
+}
+
+/**
+ * 活用の表示名
+ */
+const DEINFLECTION_LABELS: Record<DeinflectionReason, string> = {
+  negative: '否定',
+  past: '過去',
+  te: 'て形',
+  polite: '丁寧',
+  desiderative: '希望',
+  potential: '可能',
+  passive: '受身',
+  causative: '使役',
+  imperative: '命令',
+  volitional: '意志',
+  conditional: '仮定',
+  progressive: '進行',
+  completion: '完了',
+  adverbial: '連用',
+  noun: '名詞化',
+  appearance: '様態',
+  excess: '過度',
+};
+
+/**
+ * ルビ付きの訳文とローマ字
+ */
+function ReadingText({ reading }: { reading: ReadingAnnotation }) {
+  return (
+    <>
+      <div className="leading-loose">
+        {reading.segments.map((segment, index) =>
+          segment.reading ? (
+            <ruby key={index}>
+              {segment.text}
+              <rt className="text-[0.6em] text-gray-500 dark:text-gray-400">
+                {segment.reading}
+              </rt>
+            </ruby>
+          ) : (
+            <span key={index}>{segment.text}</span>
+          )
+        )}
+      </div>
+      {reading.romaji && (
+        <div className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+          {reading.romaji}
+        </div>
+      )}
+    </>
+  );
+}
+
+/**
+ * オフライン辞書の検索結果
+ */
+function DictionaryPanel({ matches }: { matches: DictionaryMatch[] }) {
+  return (
+    <div className="bg-amber-50 dark:bg-amber-900/30 rounded-lg p-3 mb-4">
+      <div className="text-xs font-medium text-amber-700 dark:text-amber-300 mb-2">
+        辞書
+      </div>
+      <div className="space-y-3 max-h-48 overflow-y-auto">
+        {matches.map((match, index) => (
+          <div
+            key={index}
+            className="text-sm text-amber-900 dark:text-amber-100"
+          >
+            <div className="flex flex-wrap items-baseline gap-2">
+              <span className="font-medium">
+                {match.entry.kanji[0] ?? match.entry.readings[0]}
+              </span>
+              {match.entry.kanji.length > 0 && (
+                <span className="text-xs">
+                  {match.entry.readings.join('・')}
+                </span>
+              )}
+              {match.reasons.length > 0 && (
+                <span className="text-xs text-amber-600 dark:text-amber-400">
+                  {match.baseForm} ←{' '}
+                  {match.reasons.map((r) => DEINFLECTION_LABELS[r]).join('・')}
+                </span>
+              )}
+            </div>
+            <ol className="list-decimal list-inside text-xs mt-1 space-y-0.5">
+              {match.entry.senses.map((sense, senseIndex) => (
+                <li key={senseIndex}>
+                  {sense.partOfSpeech.length > 0 && (
+                    <span className="text-amber-600 dark:text-amber-400">
+                      ({sense.partOfSpeech.join(', ')}){' '}
+                    </span>
+                  )}
+                  {sense.glosses.join('; ')}
+                </li>
+              ))}
+            </ol>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
 }
 
 /**
@@ -153,6 +266,8 @@ export function TranslationPopup({
   actionError,
   onSummarize,
   onGenerateReply,
+  dictionaryMatches = [],
+  translationReading = null,
 }: TranslationPopupProps) {
   // idle状態では何も表示しない
   if (state === 'idle') {
@@ -204,6 +319,12 @@ export function TranslationPopup({
 
         {/* コンテンツ */}
         <div className="p-4 overflow-y-auto flex-1 min-h-0">
+          {/* オフライン辞書（翻訳と並行して表示） */}
+          {(state === 'translating' || state === 'completed') &&
+            dictionaryMatches.length > 0 && (
+              <DictionaryPanel matches={dictionaryMatches} />
+            )}
+
           {/* ローディング状態 */}
           {(state === 'getting-selection' || state === 'translating') && (
             <LoadingSpinner />
@@ -268,7 +389,11 @@ export function TranslationPopup({
                   <CopyButton text={translatedText} onCopy={onCopy} />
                 </div>
                 <div className="text-base text-gray-800 dark:text-white bg-blue-50 dark:bg-blue-900/30 rounded-lg p-3 max-h-48 overflow-y-auto">
-                  {translatedText}
+                  {translationReading ? (
+                    <ReadingText reading={translationReading} />
+                  ) : (
+                    translatedText
+                  )}
                 </div>
               </div>
 
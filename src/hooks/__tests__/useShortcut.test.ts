@@ -90,6 +90,28 @@ describe('useShortcut', () => {
     expect(result.current.currentShortcut).toBe('CommandOrControl+Shift+T');
   });
 
+  it('registerKeymapでキーマップ全体を登録できる', async () => {
+    mockInvoke.mockResolvedValue(undefined);
+
+    const { result } = renderHook(() => useShortcut());
+    const keymap = [
+      { shortcut: 'CommandOrControl+J', action: 'translate' as const },
+      {
+        shortcut: 'CommandOrControl+Shift+J',
+        action: 'translate-and-replace' as const,
+        domain: 'chat' as const,
+      },
+    ];
+
+    await act(async () => {
+      await result.current.registerKeymap(keymap);
+    });
+
+    expect(mockInvoke).toHaveBeenCalledWith('register_keymap', { keymap });
+    expect(result.current.isRegistered).toBe(true);
+    expect(result.current.currentShortcut).toBe('CommandOrControl+J');
+  });
+
   it('unregisterShortcutでショートカットを解除できる', async () => {
     mockInvoke.mockImplementation(async (cmd: string) => {
       if (cmd === 'get_shortcut_status') {
@@ -216,11 +238,16 @@ describe('useShortcut', () => {
     });
 
     // イベントをシミュレート
+    const payload = {
+      action: 'summarize',
+      shortcut: 'CommandOrControl+Shift+S',
+      domain: null,
+    };
     act(() => {
-      capturedCallback?.({});
+      capturedCallback?.({ payload });
     });
 
-    expect(callback).toHaveBeenCalled();
+    expect(callback).toHaveBeenCalledWith(payload);
   });
 
   it('isLoadingが処理中に正しく設定される', async () => {
@@ -6,7 +6,11 @@ import { renderHook, act, waitFor } from '@testing-library/react';
 import { describe, it, expect, vi, beforeEach } from 'vitest';
 import { useTranslationFlow } from '../useTranslationFlow';
 import type { ClipboardContent } from '../useClipboard';
-import type { TranslationResult } from '@/types';
+import type {
+  DictionaryMatch,
+  ShortcutTriggered,
+  TranslationResult,
+} from '@/types';
 
 // Tauriのinvokeをモック
 vi.mock('@tauri-apps/api/core', () => ({
@@ -14,9 +18,10 @@ vi.mock('@tauri-apps/api/core', () => ({
 }));
 
 // Tauriのイベントリスナーをモック
-const mockListeners = new Map<string, (() => void)[]>();
+type MockListener = (event: { payload: unknown }) => void;
+const mockListeners = new Map<string, MockListener[]>();
 vi.mock('@tauri-apps/api/event', () => ({
-  listen: vi.fn((event: string, callback: () => void) => {
+  listen: vi.fn((event: string, callback: MockListener) => {
     const listeners = mockListeners.get(event) || [];
     listeners.push(callback);
     mockListeners.set(event, listeners);
@@ -69,10 +74,17 @@ import { invoke } from '@tauri-apps/api/core';
 const mockInvoke = vi.mocked(invoke);
 
 // ショートカットイベントを発火するヘルパー
-function triggerShortcut() {
+function triggerShortcut(payload: Partial<ShortcutTriggered> = {}) {
   const listeners = mockListeners.get('shortcut-triggered') || [];
   listeners.forEach((listener) => {
-    listener();
+    listener({
+      payload: {
+        action: 'translate',
+        shortcut: 'CommandOrControl+J',
+        domain: null,
+        ...payload,
+      },
+    });
   });
 }
 
@@ -216,6 +228,138 @@ describe('useTranslationFlow', () => {
       expect(result.current.translatedText).toBe('Test');
     });
 
+    it('ショートカットのドメインが翻訳に渡されること', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        text: '契約を解除する。',
+        success: true,
+      } as ClipboardContent);
+
+      // カーソル位置取得
+      mockInvoke.mockResolvedValueOnce([100, 200]);
+
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'Terminate the contract',
+        sourceLang: 'japanese',
+        targetLang: 'english',
+        durationMs: 100,
+      } as TranslationResult);
+
+      const { result } = renderHook(() => useTranslationFlow());
+
+      await act(async () => {
+        triggerShortcut({ domain: 'legal' });
+        await new Promise((resolve) => setTimeout(resolve, 100));
+      });
+
+      await waitFor(() => {
+        expect(result.current.state).toBe('completed');
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        'translate',
+        expect.objectContaining({ domain: 'legal' })
+      );
+    });
+
+    it('翻訳して置換のアクションではtranslate_and_replaceを呼び出すこと', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'Hello',
+        sourceLang: 'japanese',
+        targetLang: 'english',
+        durationMs: 100,
+      } as TranslationResult);
+
+      const { result } = renderHook(() => useTranslationFlow());
+
+      await act(async () => {
+        triggerShortcut({ action: 'translate-and-replace', domain: 'chat' });
+        await new Promise((resolve) => setTimeout(resolve, 50));
+      });
+
+      expect(mockInvoke).toHaveBeenCalledTimes(1);
+      expect(mockInvoke).toHaveBeenCalledWith('translate_and_replace', {
+        domain: 'chat',
+      });
+      // ポップアップは表示しない
+      expect(result.current.state).toBe('idle');
+    });
+
+    it('クリップボード翻訳のアクションではクリップボードのテキストを翻訳すること', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        text: 'Good morning',
+        success: true,
+      } as ClipboardContent);
+
+      // カーソル位置取得
+      mockInvoke.mockResolvedValueOnce([100, 200]);
+
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'おはようございます',
+        sourceLang: 'english',
+        targetLang: 'japanese',
+        durationMs: 100,
+      } as TranslationResult);
+
+      const { result } = renderHook(() => useTranslationFlow());
+
+      await act(async () => {
+        triggerShortcut({ action: 'translate-clipboard' });
+        await new Promise((resolve) => setTimeout(resolve, 100));
+      });
+
+      await waitFor(() => {
+        expect(result.current.state).toBe('completed');
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('read_clipboard');
+      expect(mockInvoke).not.toHaveBeenCalledWith('get_selected_text');
+      expect(result.current.translatedText).toBe('おはようございます');
+    });
+
+    it('要約のアクションでは翻訳の完了後に要約を実行すること', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        text: 'This is a long article about testing.',
+        success: true,
+      } as ClipboardContent);
+
+      // カーソル位置取得
+      mockInvoke.mockResolvedValueOnce([100, 200]);
+
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'これはテストに関する長い記事です。',
+        sourceLang: 'english',
+        targetLang: 'japanese',
+        durationMs: 100,
+      } as TranslationResult);
+
+      // 要約（元の言語で要約→翻訳）
+      mockInvoke.mockResolvedValueOnce({ summary: 'An article on testing.' });
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'テストに関する記事。',
+        sourceLang: 'english',
+        targetLang: 'japanese',
+        durationMs: 50,
+      } as TranslationResult);
+
+      const { result } = renderHook(() => useTranslationFlow());
+
+      await act(async () => {
+        triggerShortcut({ action: 'summarize' });
+        await new Promise((resolve) => setTimeout(resolve, 100));
+      });
+
+      await waitFor(() => {
+        expect(result.current.summaryText).toBe('テストに関する記事。');
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        'summarize',
+        expect.objectContaining({
+          text: 'This is a long article about testing.',
+        })
+      );
+    });
+
     it('isShortcutEnabledがfalseの場合はフローが開始されないこと', async () => {
       const { result } = renderHook(() => useTranslationFlow());
 
@@ -329,7 +473,123 @@ describe('useTranslationFlow', () => {
         text: 'こんにちは',
         sourceLang: 'japanese',
         targetLang: 'english',
+        context: undefined,
+      });
+    });
+
+    it('選択範囲の前後の文脈が翻訳に渡されること', async () => {
+      const context = {
+        before: '昨日は雨でした。',
+        after: '明日も晴れるでしょう。',
+      };
+      mockInvoke.mockResolvedValueOnce({
+        text: '今日は晴れです。',
+        success: true,
+        context,
+      } as ClipboardContent);
+
+      // カーソル位置取得
+      mockInvoke.mockResolvedValueOnce([100, 200]);
+
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'It is sunny today.',
+        sourceLang: 'japanese',
+        targetLang: 'english',
+        durationMs: 100,
+      } as TranslationResult);
+
+      const { result } = renderHook(() =>
+        useTranslationFlow({ autoStart: false })
+      );
+
+      await act(async () => {
+        await result.current.startFlow();
+      });
+
+      expect(result.current.translatedText).toBe('It is sunny today.');
+      expect(mockInvoke).toHaveBeenCalledWith(
+        'translate',
+        expect.objectContaining({ text: '今日は晴れです。', context })
+      );
+    });
+  });
+
+  describe('辞書検索', () => {
+    it('1語だけの選択では辞書の検索結果を設定すること', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        text: '食べた',
+        success: true,
+      } as ClipboardContent);
+
+      // カーソル位置取得
+      mockInvoke.mockResolvedValueOnce([100, 200]);
+
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'ate',
+        sourceLang: 'japanese',
+        targetLang: 'english',
+        durationMs: 100,
+      } as TranslationResult);
+
+      // 辞書検索（翻訳の呼び出し後に並行して実行される）
+      const matches: DictionaryMatch[] = [
+        {
+          entry: {
+            kanji: ['食べる'],
+            readings: ['たべる'],
+            senses: [{ partOfSpeech: ['v1'], glosses: ['to eat'] }],
+          },
+          baseForm: '食べる',
+          reasons: ['past'],
+        },
+      ];
+      mockInvoke.mockResolvedValueOnce(matches);
+
+      const { result } = renderHook(() =>
+        useTranslationFlow({ autoStart: false })
+      );
+
+      await act(async () => {
+        await result.current.startFlow();
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('lookup_dictionary', {
+        text: '食べた',
+      });
+      await waitFor(() => {
+        expect(result.current.dictionaryMatches).toEqual(matches);
+      });
+    });
+
+    it('文の選択では辞書を検索しないこと', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        text: 'Hello, world!',
+        success: true,
+      } as ClipboardContent);
+
+      // カーソル位置取得
+      mockInvoke.mockResolvedValueOnce([100, 200]);
+
+      mockInvoke.mockResolvedValueOnce({
+        translatedText: 'こんにちは、世界！',
+        sourceLang: 'english',
+        targetLang: 'japanese',
+        durationMs: 100,
+      } as TranslationResult);
+
+      const { result } = renderHook(() =>
+        useTranslationFlow({ autoStart: false })
+      );
+
+      await act(async () => {
+        await result.current.startFlow();
       });
+
+      expect(mockInvoke).not.toHaveBeenCalledWith(
+        'lookup_dictionary',
+        expect.anything()
+      );
+      expect(result.current.dictionaryMatches).toEqual([]);
     });
   });
 });
@@ -22,5 +22,6 @@ export type {
   TranslationFlowState,
   TranslationFlowErrorType,
   TranslationFlowError,
+  StartFlowOptions,
   UseTranslationFlowReturn,
 } from './useTranslationFlow';
@@ -6,6 +6,7 @@
 
 import { useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import type { TranslationContext } from '@/types';
 
 /**
  * クリップボードコンテンツ
@@ -15,6 +16,8 @@ export interface ClipboardContent {
   text: string;
   /** 読み取り成功かどうか */
   success: boolean;
+  /** 選択範囲の前後の文脈（選択テキストの取得時のみ） */
+  context?: TranslationContext;
 }
 
 /**
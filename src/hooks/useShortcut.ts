@@ -7,6 +7,7 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { ShortcutBinding, ShortcutTriggered } from '@/types';
 
 /**
  * ショートカット登録状態
@@ -32,6 +33,8 @@ export interface UseShortcutReturn {
   error: string | null;
   /** ショートカットを登録する */
   registerShortcut: (shortcut: string) => Promise<void>;
+  /** キーマップ全体を登録する */
+  registerKeymap: (keymap: ShortcutBinding[]) => Promise<void>;
   /** ショートカットを解除する */
   unregisterShortcut: (shortcut: string) => Promise<void>;
   /** 全てのショートカットを解除する */
@@ -45,7 +48,7 @@ export interface UseShortcutReturn {
 /**
  * ショートカット管理Hook
  *
- * @param onShortcutTriggered - ショートカットが押された時のコールバック
+ * @param onShortcutTriggered - ショートカットが押された時のコールバック（アクションとドメインを受け取る）
  * @returns ショートカットの状態と操作関数
  *
  * @example
@@ -55,8 +58,8 @@ export interface UseShortcutReturn {
  *     isRegistered,
  *     registerShortcut,
  *     error,
- *   } = useShortcut(() => {
- *     console.log('ショートカットが押されました！');
+ *   } = useShortcut(({ action }) => {
+ *     console.log('ショートカットが押されました！', action);
  *   });
  *
  *   useEffect(() => {
@@ -68,7 +71,7 @@ export interface UseShortcutReturn {
  * ```
  */
 export function useShortcut(
-  onShortcutTriggered?: () => void
+  onShortcutTriggered?: (event: ShortcutTriggered) => void
 ): UseShortcutReturn {
   const [isRegistered, setIsRegistered] = useState(false);
   const [currentShortcut, setCurrentShortcut] = useState<string | null>(null);
@@ -81,9 +84,12 @@ export function useShortcut(
 
     async function setupListener() {
       try {
-        unlisten = await listen('shortcut-triggered', () => {
-          onShortcutTriggered?.();
-        });
+        unlisten = await listen<ShortcutTriggered>(
+          'shortcut-triggered',
+          (event) => {
+            onShortcutTriggered?.(event.payload);
+          }
+        );
       } catch (err) {
         console.error('Failed to setup shortcut listener:', err);
       }
@@ -149,6 +155,28 @@ export function useShortcut(
     }
   }, []);
 
+  /**
+   * キーマップ全体を登録
+   *
+   * 各ショートカットは押下時に割り当てたアクションとドメインを添えてイベントを発行する
+   */
+  const registerKeymap = useCallback(async (keymap: ShortcutBinding[]) => {
+    setIsLoading(true);
+    setError(null);
+
+    try {
+      await invoke('register_keymap', { keymap });
+      setIsRegistered(keymap.length > 0);
+      setCurrentShortcut(keymap[0]?.shortcut ?? null);
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      setError(errorMessage);
+      throw err;
+    } finally {
+      setIsLoading(false);
+    }
+  }, []);
+
   /**
    * ショートカットを解除
    */
@@ -195,6 +223,7 @@ export function useShortcut(
     isLoading,
     error,
     registerShortcut,
+    registerKeymap,
     unregisterShortcut,
     unregisterAll,
     validateShortcut,
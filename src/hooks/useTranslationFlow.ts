@@ -15,6 +15,11 @@ import type {
   TranslationResult,
   SummarizeResult,
   ReplyResult,
+  DictionaryMatch,
+  ReadingAnnotation,
+  TranslationContext,
+  Domain,
+  ShortcutTriggered,
 } from '@/types';
 import { toBackendLanguage } from '@/types';
 import type { ClipboardContent } from './useClipboard';
@@ -28,6 +33,9 @@ const DEFAULT_SOURCE_LANG: Language = 'ja';
 /** デフォルトの翻訳先言語 */
 const DEFAULT_TARGET_LANG: Language = 'en';
 
+/** 辞書で検索する1語（空白・句読点を含まない24文字以内の語） */
+const SINGLE_TOKEN_PATTERN = /^[\p{L}\p{M}々ー'-]{1,24}$/u;
+
 /**
  * 翻訳フローの状態
  */
@@ -55,6 +63,16 @@ export interface TranslationFlowError {
   message: string;
 }
 
+/**
+ * 翻訳フローの開始オプション
+ */
+export interface StartFlowOptions {
+  /** 翻訳に使用するドメイン（未指定の場合は設定の既定値） */
+  domain?: Domain;
+  /** 翻訳するテキストの取得元（デフォルト: 選択テキスト） */
+  source?: 'selection' | 'clipboard';
+}
+
 /**
  * useTranslationFlow Hookの戻り値
  */
@@ -72,7 +90,7 @@ export interface UseTranslationFlowReturn {
   /** ショートカットが有効かどうか */
   isShortcutEnabled: boolean;
   /** 手動で翻訳フローを開始する */
-  startFlow: () => Promise<void>;
+  startFlow: (options?: StartFlowOptions) => Promise<void>;
   /** 状態をリセットする */
   reset: () => Promise<void>;
   /** ショートカットを有効/無効にする */
@@ -93,6 +111,10 @@ export interface UseTranslationFlowReturn {
   summarize: () => Promise<void>;
   /** 返信を生成する */
   generateReply: () => Promise<void>;
+  /** オフライン辞書の検索結果（1語だけの選択時） */
+  dictionaryMatches: DictionaryMatch[];
+  /** 訳文の読み（翻訳先が日本語で、ふりがな表示が有効な場合） */
+  translationReading: ReadingAnnotation | null;
 }
 
 /**
@@ -167,6 +189,11 @@ export function useTranslationFlow(options?: {
   const [actionError, setActionError] = useState<string | null>(null);
   const [targetLanguage, setTargetLanguage] = useState<Language | null>(null);
   const [sourceLanguage, setSourceLanguage] = useState<Language | null>(null);
+  const [dictionaryMatches, setDictionaryMatches] = useState<
+    DictionaryMatch[]
+  >([]);
+  const [translationReading, setTranslationReading] =
+    useState<ReadingAnnotation | null>(null);
 
   // コールバックの参照を保持
   const onTranslationCompleteRef = useRef(onTranslationComplete);
@@ -175,6 +202,9 @@ export function useTranslationFlow(options?: {
   // 実行中フラグ（同期的に重複実行を防ぐ）
   const isExecutingRef = useRef(false);
 
+  // 翻訳完了後に続けて実行するアクション（要約・返信のショートカット用）
+  const pendingActionRef = useRef<'summarize' | 'reply' | null>(null);
+
   useEffect(() => {
     onTranslationCompleteRef.current = onTranslationComplete;
     onErrorRef.current = onError;
@@ -194,28 +224,41 @@ export function useTranslationFlow(options?: {
   );
 
   /**
-   * 選択テキストを取得する
+   * 選択テキストを前後の文脈とともに取得する
+   *
+   * 取得元がクリップボードの場合はクリップボードのテキストを読み取る
    */
-  const getSelectedText = useCallback(async (): Promise<string | null> => {
-    try {
-      const content = await invoke<ClipboardContent>('get_selected_text');
+  const getSelectedText = useCallback(
+    async (
+      source: StartFlowOptions['source'] = 'selection'
+    ): Promise<ClipboardContent | null> => {
+      try {
+        const content = await invoke<ClipboardContent>(
+          source === 'clipboard' ? 'read_clipboard' : 'get_selected_text'
+        );
+
+        if (!content.success || !content.text.trim()) {
+          return null;
+        }
 
-      if (!content.success || !content.text.trim()) {
+        return content;
+      } catch (err) {
+        console.error('Failed to get selected text:', err);
         return null;
       }
-
-      return content.text;
-    } catch (err) {
-      console.error('Failed to get selected text:', err);
-      return null;
-    }
-  }, []);
+    },
+    []
+  );
 
   /**
-   * テキストを翻訳する
+   * テキストを翻訳する（前後の文脈は参考情報として渡す）
    */
   const translateText = useCallback(
-    async (text: string): Promise<string | null> => {
+    async (
+      text: string,
+      context?: TranslationContext,
+      domain?: Domain
+    ): Promise<string | null> => {
       try {
         // 言語検出
         const detection = detectLanguage(text);
@@ -237,11 +280,14 @@ export function useTranslationFlow(options?: {
           text,
           sourceLang: toBackendLanguage(sourceLang),
           targetLang: toBackendLanguage(targetLang),
+          context,
+          domain,
         });
 
         setDurationMs(result.durationMs);
         setTargetLanguage(targetLang); // 翻訳先言語を保存
         setSourceLanguage(sourceLang); // 翻訳元言語を保存
+        setTranslationReading(result.reading ?? null);
         return result.translatedText;
       } catch (err) {
         console.error('Translation failed:', err);
@@ -251,6 +297,20 @@ export function useTranslationFlow(options?: {
     []
   );
 
+  /**
+   * オフライン辞書で1語を検索する
+   */
+  const lookupDictionary = useCallback(async (text: string) => {
+    try {
+      const matches = await invoke<DictionaryMatch[]>('lookup_dictionary', {
+        text,
+      });
+      setDictionaryMatches(matches ?? []);
+    } catch (err) {
+      console.error('Dictionary lookup failed:', err);
+    }
+  }, []);
+
   /**
    * ウィンドウをカーソル位置の近くに移動し、前面に表示する
    */
@@ -349,7 +409,9 @@ export function useTranslationFlow(options?: {
   /**
    * 翻訳フローを開始する
    */
-  const startFlow = useCallback(async () => {
+  const startFlow = useCallback(async (options?: StartFlowOptions) => {
+    const { domain, source = 'selection' } = options ?? {};
+
     console.log(
       '[翻訳フロー] 開始リクエスト - 実行中:',
       isExecutingRef.current
@@ -377,17 +439,26 @@ export function useTranslationFlow(options?: {
       setReplyText(null);
       setReplyExplanation(null);
       setActionError(null);
+      setDictionaryMatches([]);
+      setTranslationReading(null);
 
       // Step 1: 選択テキストを取得（フォーカスが変わる前に実行）
       setState('getting-selection');
 
-      const selectedText = await getSelectedText();
+      const selection = await getSelectedText(source);
 
-      if (!selectedText) {
-        handleError('no-selection', 'テキストが選択されていません');
+      if (!selection) {
+        handleError(
+          'no-selection',
+          source === 'clipboard'
+            ? 'クリップボードにテキストがありません'
+            : 'テキストが選択されていません'
+        );
         return;
       }
 
+      const selectedText = selection.text;
+
       setOriginalText(selectedText);
 
       // Step 2: ウィンドウをカーソル位置に移動（テキスト取得後）
@@ -396,7 +467,18 @@ export function useTranslationFlow(options?: {
       // Step 3: 翻訳を実行
       setState('translating');
 
-      const result = await translateText(selectedText);
+      const translation = translateText(
+        selectedText,
+        selection.context,
+        domain
+      );
+
+      // 1語だけの選択は、辞書の語義を翻訳の完了を待たずに表示する
+      if (SINGLE_TOKEN_PATTERN.test(selectedText.trim())) {
+        void lookupDictionary(selectedText.trim());
+      }
+
+      const result = await translation;
 
       if (!result) {
         handleError('translation-failed', '翻訳に失敗しました');
@@ -416,13 +498,13 @@ export function useTranslationFlow(options?: {
       isExecutingRef.current = false;
       console.log('[翻訳フロー] フラグ解除');
     }
-  }, [getSelectedText, translateText, handleError, moveWindowToCursor]);
-
-  // startFlowの参照を保持（useEffectでの重複登録を防ぐ）
-  const startFlowRef = useRef(startFlow);
-  useEffect(() => {
-    startFlowRef.current = startFlow;
-  }, [startFlow]);
+  }, [
+    getSelectedText,
+    translateText,
+    lookupDictionary,
+    handleError,
+    moveWindowToCursor,
+  ]);
 
   /**
    * 要約を実行する（2段階処理：元の言語で要約→翻訳）
@@ -500,37 +582,22 @@ export function useTranslationFlow(options?: {
       setActionError(null);
       setActionState('generating-reply');
 
-      console.log('[返信生成] デバッグ情報（2段階処理）:');
+      console.log('[返信生成] デバッグ情報:');
       console.log('  originalText:', originalText);
       console.log('  sourceLanguage:', sourceLanguage);
       console.log('  targetLanguage:', targetLanguage);
 
-      // ステップ1: 元の言語で返信を作成
+      // 元の文章の言語で返信を作成し、翻訳先言語での説明（返信の翻訳）も同時に取得
       const replyResult = await invoke<ReplyResult>('generate_reply', {
         originalText: originalText,
-        language: toBackendLanguage(sourceLanguage),
-        sourceLanguage: toBackendLanguage(sourceLanguage), // 同じ言語
-      });
-
-      console.log(
-        '[返信生成] ステップ1完了（元の言語で返信）:',
-        replyResult.reply
-      );
-
-      // ステップ2: 返信を目的の言語に翻訳
-      const translationResult = await invoke<TranslationResult>('translate', {
-        text: replyResult.reply,
-        sourceLang: toBackendLanguage(sourceLanguage),
-        targetLang: toBackendLanguage(targetLanguage),
+        replyLanguage: toBackendLanguage(sourceLanguage),
+        explanationLanguage: toBackendLanguage(targetLanguage),
       });
 
-      console.log(
-        '[返信生成] ステップ2完了（翻訳）:',
-        translationResult.translatedText
-      );
+      console.log('[返信生成] 完了:', replyResult.reply);
 
       // 元の言語の返信と翻訳版の両方を保存
-      setReplyText(translationResult.translatedText); // 翻訳版（目的の言語）
+      setReplyText(replyResult.explanation); // 翻訳版（目的の言語）
       setReplyExplanation(replyResult.reply); // 元の言語版
       setActionState('idle');
     } catch (err) {
@@ -540,6 +607,78 @@ export function useTranslationFlow(options?: {
     }
   }, [actionState, originalText, sourceLanguage, targetLanguage]);
 
+  // 要約・返信のショートカットでは、翻訳の完了後に続けてアクションを実行する
+  useEffect(() => {
+    if (state === 'error') {
+      pendingActionRef.current = null;
+      return;
+    }
+    if (state !== 'completed' || !pendingActionRef.current) return;
+
+    const action = pendingActionRef.current;
+    pendingActionRef.current = null;
+    void (action === 'summarize' ? summarize() : generateReply());
+  }, [state, summarize, generateReply]);
+
+  /**
+   * ウィンドウの表示・非表示を切り替える
+   */
+  const toggleWindow = useCallback(async () => {
+    try {
+      const window = getCurrentWindow();
+      if (await window.isVisible()) {
+        await window.hide();
+      } else {
+        await window.show();
+        await window.setFocus();
+      }
+    } catch (err) {
+      console.error('Failed to toggle window:', err);
+    }
+  }, []);
+
+  /**
+   * ショートカットに割り当てられたアクションを実行する
+   */
+  const handleShortcut = useCallback(
+    async ({ action, domain }: ShortcutTriggered) => {
+      const options: StartFlowOptions = { domain: domain ?? undefined };
+      pendingActionRef.current =
+        action === 'summarize' || action === 'reply' ? action : null;
+
+      switch (action) {
+        case 'translate':
+        case 'summarize':
+        case 'reply':
+          await startFlow(options);
+          break;
+        case 'translate-clipboard':
+          await startFlow({ ...options, source: 'clipboard' });
+          break;
+        case 'translate-and-replace':
+          try {
+            await invoke('translate_and_replace', { domain: options.domain });
+          } catch (err) {
+            handleError(
+              'translation-failed',
+              err instanceof Error ? err.message : String(err)
+            );
+          }
+          break;
+        case 'toggle-window':
+          await toggleWindow();
+          break;
+      }
+    },
+    [startFlow, handleError, toggleWindow]
+  );
+
+  // handleShortcutの参照を保持（useEffectでの重複登録を防ぐ）
+  const handleShortcutRef = useRef(handleShortcut);
+  useEffect(() => {
+    handleShortcutRef.current = handleShortcut;
+  }, [handleShortcut]);
+
   /**
    * 状態をリセットする
    */
@@ -561,6 +700,8 @@ export function useTranslationFlow(options?: {
     setActionError(null);
     setTargetLanguage(null);
     setSourceLanguage(null);
+    setDictionaryMatches([]);
+    setTranslationReading(null);
 
     // ウィンドウを通常状態に戻す（常に前面表示を解除）
     try {
@@ -580,12 +721,18 @@ export function useTranslationFlow(options?: {
 
     async function setupListener() {
       try {
-        unlisten = await listen('shortcut-triggered', () => {
-          console.log('[リスナー] ショートカットイベント受信');
-          if (isShortcutEnabled) {
-            void startFlowRef.current();
+        unlisten = await listen<ShortcutTriggered>(
+          'shortcut-triggered',
+          (event) => {
+            console.log(
+              '[リスナー] ショートカットイベント受信:',
+              event.payload.action
+            );
+            if (isShortcutEnabled) {
+              void handleShortcutRef.current(event.payload);
+            }
           }
-        });
+        );
         console.log('[リスナー] 登録完了');
       } catch (err) {
         console.error('Failed to setup shortcut listener:', err);
@@ -618,5 +765,7 @@ export function useTranslationFlow(options?: {
     targetLanguage,
     summarize,
     generateReply,
+    dictionaryMatches,
+    translationReading,
   };
 }
@@ -22,6 +22,61 @@ export interface DetectionResult {
  */
 export type TranslationProvider = 'ollama' | 'claude-cli';
 
+/**
+ * 翻訳文の文体
+ */
+export type Formality = 'auto' | 'polite' | 'plain' | 'casual';
+
+/**
+ * 翻訳ドメイン
+ */
+export type Domain = 'technical' | 'legal' | 'marketing' | 'chat';
+
+/**
+ * 翻訳対象の前後の文脈（翻訳せず参考情報としてモデルに渡す）
+ */
+export interface TranslationContext {
+  /** 翻訳対象より前のテキスト */
+  before: string;
+  /** 翻訳対象より後のテキスト */
+  after: string;
+}
+
+/**
+ * ショートカットに割り当て可能なアクション
+ */
+export type ShortcutAction =
+  | 'translate'
+  | 'translate-and-replace'
+  | 'summarize'
+  | 'reply'
+  | 'translate-clipboard'
+  | 'toggle-window';
+
+/**
+ * ショートカットとアクションの割り当て
+ */
+export interface ShortcutBinding {
+  /** ショートカット文字列（例: "CommandOrControl+J"） */
+  shortcut: string;
+  /** 割り当てるアクション */
+  action: ShortcutAction;
+  /** 翻訳に使用するドメイン（未指定の場合は設定の既定値） */
+  domain?: Domain;
+}
+
+/**
+ * ショートカット押下時のイベントペイロード
+ */
+export interface ShortcutTriggered {
+  /** 実行するアクション */
+  action: ShortcutAction;
+  /** 押されたショートカット文字列 */
+  shortcut: string;
+  /** 翻訳に使用するドメイン（未指定の場合は設定の既定値） */
+  domain: Domain | null;
+}
+
 /**
  * アプリケーション設定
  */
@@ -36,6 +91,16 @@ export interface AppSettings {
   provider: TranslationProvider;
   /** Claude CLIの実行パス */
   claudeCliPath: string | null;
+  /** ショートカットとアクションの割り当て（空の場合はshortcutに翻訳を割り当てる） */
+  keymap?: ShortcutBinding[];
+  /** 母国語（クリップボード監視でこの言語のテキストは翻訳しない） */
+  nativeLanguage?: BackendLanguage;
+  /** 翻訳文の文体の既定値 */
+  formality?: Formality;
+  /** 翻訳ドメインの既定値 */
+  domain?: Domain;
+  /** 翻訳先が日本語の場合に訳文のふりがな・ローマ字を表示する */
+  furigana?: boolean;
 }
 
 /**
@@ -45,6 +110,27 @@ export type ProviderStatus =
   | { status: 'available' }
   | { status: 'unavailable'; reason: string };
 
+/**
+ * 品質チェックの警告種別
+ */
+export type QaWarningKind =
+  | 'number'
+  | 'url'
+  | 'code'
+  | 'placeholder'
+  | 'untranslated'
+  | 'truncated'
+  | 'bracket'
+  | 'line-count';
+
+/**
+ * 品質チェックの警告
+ */
+export interface QaWarning {
+  kind: QaWarningKind;
+  message: string;
+}
+
 /**
  * 翻訳結果（バックエンドから返される）
  */
@@ -53,6 +139,67 @@ export interface TranslationResult {
   sourceLang: BackendLanguage;
   targetLang: BackendLanguage;
   durationMs: number;
+  /** 品質チェックの警告 */
+  warnings?: QaWarning[];
+  /** 訳文を取り出した経路 */
+  outputPath?: OutputPath;
+  /** 訳文の読み（翻訳先が日本語で、ふりがな表示が有効な場合のみ） */
+  reading?: ReadingAnnotation;
+  /** 翻訳時に送ったプロンプト（Claude CLIのみ、リファインメントの開始時に渡す） */
+  prompt?: TranslationPrompt;
+}
+
+/**
+ * Claude CLIでの翻訳時に送ったプロンプト
+ */
+export interface TranslationPrompt {
+  systemPrompt: string;
+  /** 翻訳対象のメッセージ（前後の文脈を含む） */
+  message: string;
+}
+
+/**
+ * 訳文を取り出した経路
+ * - structured: 構造化出力（JSONスキーマ）から取得
+ * - fallback: 構造化出力に従わなかったため整形処理にフォールバック
+ * - heuristic: 整形処理のみ（翻訳特化モデル・ストリーミング・Claude CLI）
+ */
+export type OutputPath = 'structured' | 'fallback' | 'heuristic';
+
+/**
+ * 翻訳候補のスタイル
+ */
+export type TranslationStyle = 'standard' | 'formal' | 'casual' | 'concise';
+
+/**
+ * 翻訳候補
+ */
+export interface TranslationCandidate {
+  style: TranslationStyle;
+  translatedText: string;
+  warnings: QaWarning[];
+}
+
+/**
+ * 複数候補の翻訳結果
+ */
+export interface AlternativesResult {
+  /** 品質チェックの警告が少ない順に並べた候補 */
+  candidates: TranslationCandidate[];
+  sourceLang: BackendLanguage;
+  targetLang: BackendLanguage;
+  durationMs: number;
+}
+
+/**
+ * 逆翻訳による整合性チェックの結果
+ */
+export interface BackTranslationResult {
+  translation: TranslationResult;
+  /** 翻訳結果を翻訳元言語に戻した逆翻訳 */
+  backTranslation: string;
+  /** 原文と逆翻訳の類似度 (0.0 - 1.0) */
+  similarity: number;
 }
 
 /**
@@ -66,6 +213,36 @@ export const DEFAULT_SETTINGS: AppSettings = {
   claudeCliPath: null,
 };
 
+/**
+ * 実際に登録するキーマップを取得する
+ *
+ * キーマップ未保存の既存設定では、shortcutに翻訳アクションを割り当てる
+ */
+export function effectiveKeymap(settings: AppSettings): ShortcutBinding[] {
+  if (settings.keymap && settings.keymap.length > 0) {
+    return settings.keymap;
+  }
+  return [{ shortcut: settings.shortcut, action: 'translate' }];
+}
+
+/**
+ * キーマップ内のショートカットを差し替える
+ *
+ * 差し替え元の割り当てがない場合は、新しいショートカットに翻訳アクションを割り当てて追加する
+ */
+export function replaceKeymapShortcut(
+  keymap: ShortcutBinding[],
+  from: string,
+  to: string
+): ShortcutBinding[] {
+  if (!keymap.some((binding) => binding.shortcut === from)) {
+    return [{ shortcut: to, action: 'translate' }, ...keymap];
+  }
+  return keymap.map((binding) =>
+    binding.shortcut === from ? { ...binding, shortcut: to } : binding
+  );
+}
+
 /**
  * フロントエンド言語をバックエンド言語に変換
  */
@@ -95,8 +272,53 @@ export interface StreamChunk {
 export interface StreamComplete {
   translatedText: string;
   durationMs: number;
+  warnings?: QaWarning[];
+}
+
+/**
+ * 会話メッセージ
+ */
+export interface ChatMessage {
+  role: 'user' | 'assistant';
+  content: string;
+}
+
+/**
+ * 翻訳のリファインメントセッション
+ */
+export interface RefinementSession {
+  /** セッションID */
+  id: string;
+  /** 翻訳元テキスト */
+  sourceText: string;
+  sourceLang: BackendLanguage;
+  targetLang: BackendLanguage;
+  /** 元の翻訳で使用したシステムプロンプト（Claude CLIのみ） */
+  systemPrompt?: string;
+  /** 会話履歴（元のプロンプト、訳文、追加指示、改訂版の訳文の順） */
+  messages: ChatMessage[];
+}
+
+/**
+ * リファインメント完了イベント
+ */
+export interface RefinementComplete {
+  sessionId: string;
+  translatedText: string;
+  durationMs: number;
+  warnings: QaWarning[];
 }
 
+/**
+ * 要約の長さ・形式
+ */
+export type SummaryLength = 'one-line' | 'three-sentences' | 'bullets' | 'tldr-details';
+
+/**
+ * 返信の意図
+ */
+export type ReplyIntent = 'general' | 'accept' | 'decline' | 'clarify' | 'follow-up';
+
 /**
  * 要約結果（バックエンドから返される）
  */
@@ -111,17 +333,241 @@ export interface SummarizeResult {
   durationMs: number;
 }
 
+/**
+ * 辞書形に戻すために適用した活用
+ */
+export type DeinflectionReason =
+  | 'negative'
+  | 'past'
+  | 'te'
+  | 'polite'
+  | 'desiderative'
+  | 'potential'
+  | 'passive'
+  | 'causative'
+  | 'imperative'
+  | 'volitional'
+  | 'conditional'
+  | 'progressive'
+  | 'completion'
+  | 'adverbial'
+  | 'noun'
+  | 'appearance'
+  | 'excess';
+
+/**
+ * 辞書の語義
+ */
+export interface DictionarySense {
+  /** 品詞（JMdictの品詞コード。例: "n", "v5k", "adj-i"） */
+  partOfSpeech: string[];
+  /** 英語の語義 */
+  glosses: string[];
+}
+
+/**
+ * 辞書の見出し
+ */
+export interface DictionaryEntry {
+  /** 漢字表記 */
+  kanji: string[];
+  /** 読み（かな） */
+  readings: string[];
+  senses: DictionarySense[];
+}
+
+/**
+ * 辞書の検索結果
+ */
+export interface DictionaryMatch {
+  entry: DictionaryEntry;
+  /** 検索に使用した辞書形 */
+  baseForm: string;
+  /** 辞書形に戻すために適用した活用（活用していない場合は空） */
+  reasons: DeinflectionReason[];
+}
+
+/**
+ * 辞書の状態
+ */
+export interface DictionaryStatus {
+  /** 辞書を読み込み済みかどうか */
+  loaded: boolean;
+  /** 見出し数 */
+  entryCount: number;
+}
+
+/**
+ * ルビ付きの区間
+ */
+export interface RubySegment {
+  /** 表記 */
+  text: string;
+  /** ひらがなの読み（漢字を含む区間のみ） */
+  reading: string | null;
+}
+
+/**
+ * 読みの取得元
+ * - kana: 漢字を含まないため、かなから変換
+ * - dictionary: オフライン辞書
+ * - llm: LLMの構造化出力
+ */
+export type ReadingSource = 'kana' | 'dictionary' | 'llm';
+
+/**
+ * 読みの注釈（バックエンドから返される）
+ */
+export interface ReadingAnnotation {
+  /** ルビ付きの区間（つなげると元のテキストになる） */
+  segments: RubySegment[];
+  /** 修正ヘボン式ローマ字（長音は長音符号で表記、単語ごとに空白で区切る） */
+  romaji: string;
+  /** 読みの取得元 */
+  source: ReadingSource;
+}
+
+/**
+ * 読み（日本語の語句のみ）
+ */
+export interface Reading {
+  /** ふりがな（ひらがな） */
+  furigana: string;
+  /** ローマ字（ヘボン式） */
+  romaji: string;
+}
+
+/**
+ * 例文
+ */
+export interface ExampleSentence {
+  /** 語句の言語での例文 */
+  sentence: string;
+  /** 例文の訳 */
+  translation: string;
+}
+
+/**
+ * 単語・慣用句の解説（バックエンドから返される）
+ */
+export interface Explanation {
+  /** 解説した語句 */
+  term: string;
+  /** 読み（日本語の語句のみ） */
+  reading: Reading | null;
+  /** 品詞 */
+  partOfSpeech: string;
+  /** 意味（解説言語） */
+  meanings: string[];
+  /** 例文 */
+  examples: ExampleSentence[];
+  /** ニュアンス・使い分けの補足 */
+  nuance: string | null;
+  /** 語句の言語 */
+  sourceLang: BackendLanguage;
+  /** 解説の言語 */
+  targetLang: BackendLanguage;
+  /** 処理時間（ミリ秒） */
+  durationMs: number;
+}
+
+/**
+ * スレッドの形式
+ * - email: 引用付きのメール
+ * - chat: `名前: 発言`形式のチャット
+ * - plain: 発言者を判別できないテキスト
+ */
+export type ThreadFormat = 'email' | 'chat' | 'plain';
+
+/**
+ * スレッド内の1つの発言
+ */
+export interface ThreadMessage {
+  /** 発言者（判別できない場合はnull） */
+  speaker: string | null;
+  /** 発言内容 */
+  text: string;
+}
+
+/**
+ * 参加者ごとの要約
+ */
+export interface ParticipantSummary {
+  /** 参加者名 */
+  name: string;
+  /** 参加者が担当するアクションアイテム */
+  actionItems: string[];
+}
+
+/**
+ * スレッド要約結果（バックエンドから返される）
+ */
+export interface ThreadSummary {
+  /** スレッド全体の要約 */
+  summary: string;
+  /** 参加者ごとのアクションアイテム */
+  participants: ParticipantSummary[];
+  /** 決定事項 */
+  decisions: string[];
+  /** 発言者ごとに分割した発言 */
+  messages: ThreadMessage[];
+  /** スレッドの形式 */
+  format: ThreadFormat;
+  /** 処理時間（ミリ秒） */
+  durationMs: number;
+}
+
+/**
+ * 原文上の範囲（UTF-16のコード単位でのオフセット。`String.prototype.slice`で使用できる）
+ */
+export interface EditSpan {
+  /** 開始位置 */
+  start: number;
+  /** 終了位置（この位置を含まない） */
+  end: number;
+}
+
+/**
+ * 校正の修正箇所
+ */
+export interface ProofreadEdit {
+  /** 原文上の範囲 */
+  span: EditSpan;
+  /** 修正前の文字列 */
+  original: string;
+  /** 修正後の文字列 */
+  replacement: string;
+  /** 修正の理由（日本語） */
+  reason: string;
+}
+
+/**
+ * 校正結果（バックエンドから返される）
+ */
+export interface ProofreadResult {
+  /** 校正前のテキスト */
+  originalText: string;
+  /** 校正後のテキスト */
+  correctedText: string;
+  /** 修正箇所（原文上の出現順） */
+  edits: ProofreadEdit[];
+  /** 文章の言語 */
+  language: BackendLanguage;
+  /** 処理時間（ミリ秒） */
+  durationMs: number;
+}
+
 /**
  * 返信結果（バックエンドから返される）
  */
 export interface ReplyResult {
-  /** 返信テキスト（翻訳先言語）
-   * 例: 日→英翻訳の場合は英語の返信 */
+  /** 返信テキスト（返信言語）
+   * 例: 英語のメールに返信する場合は英語の返信 */
   reply: string;
-  /** 返信の翻訳（翻訳元言語）
-   * 例: 日→英翻訳の場合は日本語の返信（上記replyを翻訳したもの） */
+  /** 返信の翻訳（説明言語）
+   * 例: 説明言語が日本語の場合は日本語の訳（上記replyを翻訳したもの） */
   explanation: string;
-  /** 返信の言語（翻訳先言語） */
+  /** 返信の言語（返信言語） */
   language: BackendLanguage;
   /** 処理時間（ミリ秒） */
   durationMs: number;